@@ -17,7 +17,7 @@ async fn main() -> Result<(), CosmosClient> {
     let input = input.unwrap_or_default();
 
     let mut client = Rpc::new("https://rpc-kichain-ia.cosmosia.notional.ventures/").await?;
-    let signer = Signer::from_mnemonic(input.trim(), "ki", "uxki", None, 30, 25000)?;
+    let signer = Signer::from_mnemonic(input.trim(), "ki", None, 30, "0.025uxki".parse()?)?;
     let address = signer.public_address.to_string();
     client.attach_signer(signer).await?;
     println!("signer loaded for {address}");
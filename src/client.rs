@@ -5,56 +5,74 @@ pub mod bank;
 pub mod distribution;
 pub mod evidence;
 pub mod feegrant;
+pub mod feemarket;
 pub mod gov;
 pub mod mint;
 pub mod params;
 pub mod slashing;
 pub mod staking;
 pub mod tx;
+pub mod txfees;
 pub mod upgrade;
 pub mod wasm;
 
-use crate::client::any_helper::{any_to_cosmos, CosmosType};
-use crate::client::tx::Response;
+use crate::client::tx::{
+    broadcast_error, check_tx_log, expected_sequence, is_sequence_mismatch, tx_error, Confirmation,
+    Response,
+};
 use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
 use cosmos_sdk_proto::cosmos::base::v1beta1::Coin;
 use cosmos_sdk_proto::cosmos::distribution::v1beta1::MsgWithdrawDelegatorReward;
 use cosmos_sdk_proto::cosmos::staking::v1beta1::{MsgDelegate, MsgUndelegate};
-use cosmos_sdk_proto::cosmos::tx::v1beta1::{BroadcastMode, GetTxResponse};
+use cosmos_sdk_proto::cosmos::tx::v1beta1::{
+    AuthInfo, BroadcastMode, GetTxResponse, SimulateResponse, TxRaw,
+};
 use cosmos_sdk_proto::ibc::applications::transfer::v1::MsgTransfer;
 use cosmos_sdk_proto::ibc::core::client::v1::Height;
 use cosmos_sdk_proto::traits::MessageExt;
-use cosmrs::tendermint::chain;
-use cosmrs::tx::{Fee, SignDoc, SignerInfo};
-use std::ops::{DivAssign, MulAssign};
-use std::str::FromStr;
+use cosmrs::crypto::PublicKey;
+use cosmrs::tx::{Body, Fee};
+use prost::Message;
 use std::sync::Arc;
-use std::thread::sleep;
-use std::time::Duration;
 use tendermint_rpc::{Client, HttpClient};
 
 use crate::error::CosmosClient;
-use crate::error::CosmosClient::{AccountDoesNotExistOnChain, NoSignerAttached};
-use crate::signer::Signer;
-use crate::tx::Cosmos;
+use crate::error::CosmosClient::{AccountDoesNotExistOnChain, NoSignerAttached, RpcError};
+use crate::fee::{Adjustment, Estimate, FeeStrategy, GasPrice, Quote, SimulatedFee};
+use crate::sequence::SequenceManager;
+use crate::signer::hd::{DiscoveredAccount, HdWallet};
+use crate::signer::{KeyType, Signer, TxSigner};
+use crate::tx::balance::BalanceChanges;
+use crate::tx::multi::{MultiSignerTx, SignerData};
+use crate::tx::offline::{SignedTx, UnsignedTx};
+use crate::tx::validate::{validate, Limits};
+use crate::tx::{sign_body_with, simulation_tx, Cosmos};
 
 pub struct Rpc {
+    client: Arc<HttpClient>,
     chain_id: String,
-    signer: Option<Signer>,
+    signer: Option<Box<dyn TxSigner>>,
+    signer_fee: Option<SimulatedFee>,
     account_id: Option<u64>,
     sequence_id: Option<u64>,
+    sequence_retries: u8,
+    confirmation: Confirmation,
+    limits: Option<Limits>,
+    fee_strategy: Option<Box<dyn FeeStrategy>>,
     pub bank: bank::Module,
     pub auth: auth::Module,
     pub authz: authz::Module,
     pub distribution: distribution::Module,
     pub evidence: evidence::Module,
     pub feegrant: feegrant::Module,
+    pub feemarket: feemarket::Module,
     pub gov: gov::Module,
     pub mint: mint::Module,
     pub params: params::Module,
     pub slashing: slashing::Module,
     pub staking: staking::Module,
     pub tx: tx::Module,
+    pub txfees: txfees::Module,
     pub upgrade: upgrade::Module,
     pub wasm: wasm::Module,
 }
@@ -70,22 +88,30 @@ impl Rpc {
         Ok(Rpc {
             chain_id: rpc.status().await?.node_info.network.to_string(),
             signer: None,
+            signer_fee: None,
             account_id: None,
             sequence_id: None,
+            sequence_retries: 3,
+            confirmation: Confirmation::default(),
+            limits: None,
+            fee_strategy: None,
             auth: auth::Module::new(rpc.clone()),
             authz: authz::Module::new(rpc.clone()),
             bank: bank::Module::new(rpc.clone()),
             distribution: distribution::Module::new(rpc.clone()),
             evidence: evidence::Module::new(rpc.clone()),
             feegrant: feegrant::Module::new(rpc.clone()),
+            feemarket: feemarket::Module::new(rpc.clone()),
             gov: gov::Module::new(rpc.clone()),
             mint: mint::Module::new(rpc.clone()),
             params: params::Module::new(rpc.clone()),
             slashing: slashing::Module::new(rpc.clone()),
             staking: staking::Module::new(rpc.clone()),
             tx: tx::Module::new(rpc.clone()),
+            txfees: txfees::Module::new(rpc.clone()),
             upgrade: upgrade::Module::new(rpc.clone()),
-            wasm: wasm::Module::new(rpc),
+            wasm: wasm::Module::new(rpc.clone()),
+            client: rpc,
         })
     }
 
@@ -94,169 +120,799 @@ impl Rpc {
     /// Will return `Err` if :
     /// - we cannot update `sequence_id` for `signer`
     pub async fn attach_signer(&mut self, signer: Signer) -> Result<(), CosmosClient> {
-        self.signer = Some(signer);
+        let gas_price = signer.gas_price.clone();
+        let gas_adjustment_percent = signer.gas_adjustment_percent;
+
+        self.attach_tx_signer(signer, gas_price, gas_adjustment_percent)
+            .await
+    }
+
+    /// Attach a signer whose key may live outside the process, like a
+    /// `RemoteSigner`. Without a fee strategy, the fee is the simulated gas
+    /// times `gas_price`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - we cannot update `sequence_id` for `signer`
+    pub async fn attach_tx_signer(
+        &mut self,
+        signer: impl TxSigner + 'static,
+        gas_price: GasPrice,
+        gas_adjustment_percent: u8,
+    ) -> Result<(), CosmosClient> {
+        self.signer = Some(Box::new(signer));
+        self.signer_fee = Some(SimulatedFee {
+            gas_price,
+            adjustment: Adjustment {
+                gas_adjustment_percent,
+                ..Adjustment::default()
+            },
+        });
         self.update_sequence_id().await?;
         Ok(())
     }
 
+    /// How many times `sign_and_broadcast` re-signs a tx rejected for an
+    /// account sequence mismatch, 3 by default.
+    pub fn set_sequence_retries(&mut self, retries: u8) {
+        self.sequence_retries = retries;
+    }
+
+    /// How `send`, `stake` and the other helpers wait for their tx to be
+    /// included in a block, every 3s for up to 3 minutes by default.
+    pub fn set_confirmation(&mut self, confirmation: Confirmation) {
+        self.confirmation = confirmation;
+    }
+
+    /// Fetch the chain limits and check every tx against them before it is
+    /// signed, see `validate`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - cosmos auth or gov `params` endpoint fails
+    /// - tendermint `consensus_params` endpoint fails
+    pub async fn update_limits(&mut self) -> Result<(), CosmosClient> {
+        self.limits = Some(self.limits().await?);
+        Ok(())
+    }
+
+    /// Use `strategy` to compute the fee of the signed txs instead of the
+    /// simulated gas times the signer gas price.
+    pub fn set_fee_strategy(&mut self, strategy: impl FeeStrategy + 'static) {
+        self.fee_strategy = Some(Box::new(strategy));
+    }
+
     /// # Errors
     ///
     /// Will return `Err` if :
     /// - there is no signer attached
     /// - cosmos `account` endpoint fails
     pub async fn update_sequence_id(&mut self) -> Result<(), CosmosClient> {
-        let signer = self.signer()?;
+        let address = self.signer()?.address().to_string();
+        let (account_id, sequence_id) = self.account_sequence(address.as_str()).await?;
 
-        let account = self
-            .auth
-            .account(signer.public_address.to_string().as_str())
-            .await?;
+        self.account_id = Some(account_id);
+        self.sequence_id = Some(sequence_id);
+        Ok(())
+    }
 
-        if let Some(account) = account.account {
-            match any_to_cosmos(&account)? {
-                CosmosType::BaseAccount(account) => {
-                    self.sequence_id = Some(account.sequence);
-                    self.account_id = Some(account.account_number);
-                    return Ok(());
-                }
-                CosmosType::ContinuousVestingAccount(account) => {
-                    let account = account
-                        .base_vesting_account
-                        .ok_or(CosmosClient::NoVestingBaseAccount)?
-                        .base_account
-                        .ok_or(CosmosClient::NoVestingBaseAccount)?;
-                    self.sequence_id = Some(account.sequence);
-                    self.account_id = Some(account.account_number);
-                    return Ok(());
-                }
-                _ => {}
-            }
-        }
+    /// Account number and sequence of `address`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - the account does not exist or is not a base, vesting or ethermint
+    ///   account
+    /// - cosmos `account` endpoint fails
+    pub async fn account_sequence(&self, address: &str) -> Result<(u64, u64), CosmosClient> {
+        self.auth.account_sequence(address).await
+    }
 
-        Err(AccountDoesNotExistOnChain {
-            address: signer.public_address.to_string(),
-        })
+    /// Recover the addresses of `account` in `wallet` that have an account
+    /// or balances on chain, scanning until `gap_limit` unused addresses in
+    /// a row, see [`crate::signer::hd::DEFAULT_GAP_LIMIT`].
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - an index is out of the non hardened range
+    /// - if the prefix is bad
+    /// - cosmos `account` or `all_balances` endpoint fails
+    pub async fn discover_accounts(
+        &self,
+        wallet: &HdWallet,
+        account: u32,
+        prefix: &str,
+        gap_limit: u32,
+    ) -> Result<Vec<DiscoveredAccount>, CosmosClient> {
+        wallet
+            .discover(account, prefix, gap_limit, |index, address| async move {
+                let account = match self.auth.account(address.as_ref()).await {
+                    Ok(response) => response.account,
+                    Err(RpcError(log)) if log.contains("not found") => None,
+                    Err(e) => return Err(e),
+                };
+                let balances = self
+                    .bank
+                    .all_balances(address.as_ref(), None)
+                    .await?
+                    .balances;
+
+                Ok(
+                    (account.is_some() || !balances.is_empty()).then_some(DiscoveredAccount {
+                        index,
+                        address,
+                        account,
+                        balances,
+                    }),
+                )
+            })
+            .await
     }
 
     /// # Errors
     ///
     /// Will return `Err` if :
     /// - there is no signer attached
+    /// - the fee payer of `tx` is not the signer
     /// - cosmos `simulate` endpoint fails
+    /// - the fee is above the max fee of `tx`
+    /// - the fee granter of `tx` gave no allowance to the signer
+    /// - tendermint `status` endpoint fails for a relative timeout
     /// - the is a sign or encode error
+    ///
+    /// The tx is signed with the cached sequence, which is only bumped by
+    /// `broadcast` once the tx passes `CheckTx`.
     pub async fn sign(&mut self, tx: Cosmos) -> Result<Vec<u8>, CosmosClient> {
+        self.sign_tx(&tx).await
+    }
+
+    async fn sign_tx(&self, tx: &Cosmos) -> Result<Vec<u8>, CosmosClient> {
         let account_id = self.account_id.ok_or(AccountDoesNotExistOnChain {
-            address: self.signer()?.public_address.to_string(),
+            address: self.signer()?.address().to_string(),
         })?;
         let sequence_id = self.sequence_id.ok_or(AccountDoesNotExistOnChain {
-            address: self.signer()?.public_address.to_string(),
+            address: self.signer()?.address().to_string(),
         })?;
-        self.sequence_id = Some(self.sequence_id.unwrap_or_default() + 1u64);
 
+        self.sign_with_sequence(tx, account_id, sequence_id).await
+    }
+
+    /// Sign `tx` with an explicit account number and sequence, leaving the
+    /// cached sequence untouched.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - there is no signer attached
+    /// - the fee payer of `tx` is not the signer
+    /// - cosmos `simulate` endpoint fails
+    /// - the fee is above the max fee of `tx`
+    /// - the fee granter of `tx` gave no allowance to the signer
+    /// - tendermint `status` endpoint fails for a relative timeout
+    /// - the is a sign or encode error
+    pub async fn sign_with_sequence(
+        &self,
+        tx: &Cosmos,
+        account_id: u64,
+        sequence_id: u64,
+    ) -> Result<Vec<u8>, CosmosClient> {
+        self.sign_simulated(tx, account_id, sequence_id, sequence_id)
+            .await
+    }
+
+    /// Sign `tx` with `sequence_id` and simulate it with
+    /// `simulation_sequence_id`. The gas used does not depend on the
+    /// sequence, but a simulation fails unless it uses the sequence of the
+    /// account in the chain check state, lower than `sequence_id` while txs
+    /// are in flight.
+    async fn sign_simulated(
+        &self,
+        tx: &Cosmos,
+        account_id: u64,
+        sequence_id: u64,
+        simulation_sequence_id: u64,
+    ) -> Result<Vec<u8>, CosmosClient> {
         let signer = self.signer()?;
+        let address = signer.address().to_string();
+        tx.check_fee_payer(&address)?;
 
-        let tx_body = tx.finish();
-        let auth_info = SignerInfo::single_direct(Some(signer.public_key), sequence_id).auth_info(
-            Fee::from_amount_and_gas(
-                cosmrs::Coin {
-                    amount: signer.gas_price,
-                    denom: signer.denom.parse()?,
-                },
-                100u64,
-            ),
-        );
+        if let Some(granter) = &tx.fee_granter {
+            self.check_fee_allowance(granter, &address).await?;
+        }
 
-        let sign_doc = SignDoc::new(
-            &tx_body,
-            &auth_info,
-            &chain::Id::from_str(self.chain_id.as_str())?,
-            account_id,
+        let tx_body = self.tx_body(tx).await?;
+
+        let simulate = match &self.fee_strategy {
+            Some(strategy) => strategy.simulate(),
+            None => true,
+        };
+        let gas_used = if simulate {
+            self.simulated_gas(signer, tx, &tx_body, simulation_sequence_id)
+                .await?
+        } else {
+            0
+        };
+
+        let quote = self.quote(gas_used).await?;
+        let fee = tx.fee.clone().unwrap_or(quote.amount);
+        tx.check_max_fee(&fee)?;
+        let fee = tx.build_fee(
+            vec![cosmrs::Coin {
+                amount: fee.amount.parse()?,
+                denom: fee.denom.parse()?,
+            }],
+            quote.gas_limit,
         )?;
-        let tx_raw = sign_doc.sign(&signer.private_key)?;
-        let tx = self.tx.simulate(tx_raw.to_bytes()?).await?;
 
-        if tx.gas_info.is_none() {
-            return Err(CosmosClient::CannotSimulateTxGasFee);
+        if let Some(limits) = &self.limits {
+            validate(&tx_body, &fee, 1, tx.sign_mode, limits)?;
         }
 
-        let mut gas_info = tx.gas_info.unwrap_or_default().gas_used;
-        gas_info.mul_assign(100u64 + u64::from(signer.gas_adjustment_percent));
-        gas_info.div_assign(100);
-
-        let auth_info = SignerInfo::single_direct(Some(signer.public_key), sequence_id).auth_info(
-            Fee::from_amount_and_gas(
-                cosmrs::Coin {
-                    amount: signer.gas_price,
-                    denom: signer.denom.parse()?,
-                },
-                gas_info,
-            ),
-        );
+        self.sign_with_mode(signer, tx, &tx_body, fee, account_id, sequence_id)
+            .await
+    }
 
-        let sign_doc = SignDoc::new(
-            &tx_body,
-            &auth_info,
-            &chain::Id::from_str(self.chain_id.as_str())?,
-            account_id,
+    /// Dry run `tx` for the attached signer : simulate it and compute its fee
+    /// like `sign` would, without signing it for real, broadcasting it or
+    /// touching the cached sequence.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - there is no signer attached
+    /// - the fee payer of `tx` is not the signer
+    /// - the tx fails in simulation
+    /// - the fee is above the max fee of `tx`
+    /// - tendermint `status` endpoint fails for a relative timeout
+    /// - there is an encode error
+    pub async fn estimate(&self, tx: Cosmos) -> Result<Estimate, CosmosClient> {
+        let signer = self.signer()?;
+        let sequence_id = self.sequence_id.ok_or(AccountDoesNotExistOnChain {
+            address: signer.address().to_string(),
+        })?;
+        tx.check_fee_payer(signer.address().as_ref())?;
+
+        let tx_body = self.tx_body(&tx).await?;
+        let simulation = self.simulate_tx(signer, &tx, &tx_body, sequence_id).await?;
+
+        self.estimate_from(&tx, signer.address().to_string().as_str(), simulation)
+            .await
+    }
+
+    /// Dry run `tx` on behalf of `address`, without its private key. The
+    /// public key and sequence of the account are fetched from chain, with
+    /// a placeholder key for accounts that never signed a tx.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - the account of `address` does not exist
+    /// - there is no fee strategy and no signer attached to price the gas
+    /// - the tx fails in simulation
+    /// - the fee is above the max fee of `tx`
+    /// - cosmos `account` or tendermint `status` endpoint fails
+    /// - there is an encode error
+    pub async fn simulate_as(&self, tx: Cosmos, address: &str) -> Result<Estimate, CosmosClient> {
+        let account = self.auth.base_account(address).await?;
+
+        let tx_raw = simulation_tx(
+            &self.tx_body(&tx).await?,
+            tx.build_fee(vec![], 100u64)?,
+            account.pub_key,
+            account.sequence,
+            tx.sign_mode,
+        )?;
+        let simulation = self.tx.simulate(tx_raw).await?;
+
+        self.estimate_from(&tx, address, simulation).await
+    }
+
+    /// Estimate of `tx` sent by `sender` from its simulation.
+    async fn estimate_from(
+        &self,
+        tx: &Cosmos,
+        sender: &str,
+        simulation: SimulateResponse,
+    ) -> Result<Estimate, CosmosClient> {
+        let gas_used = simulation
+            .gas_info
+            .ok_or(CosmosClient::CannotSimulateTxGasFee)?
+            .gas_used;
+
+        let quote = self.quote(gas_used).await?;
+        let fee = tx.fee.clone().unwrap_or(quote.amount);
+        tx.check_max_fee(&fee)?;
+
+        let result = simulation.result.unwrap_or_default();
+        let mut balance_changes = BalanceChanges::from_events(&result.events)?;
+        let fee_payer = tx
+            .fee_granter
+            .as_deref()
+            .or(tx.fee_payer.as_deref())
+            .unwrap_or(sender);
+        balance_changes.add(fee_payer, fee.denom.as_str(), -fee.amount.parse::<i128>()?);
+
+        #[allow(deprecated)]
+        let data = result.data;
+        Ok(Estimate {
+            gas_used,
+            gas_limit: quote.gas_limit,
+            fee,
+            balance_changes,
+            events: result.events,
+            data,
+            msg_responses: result.msg_responses,
+            log: result.log,
+        })
+    }
+
+    /// Simulate `tx` signed with an empty fee.
+    async fn simulate_tx(
+        &self,
+        signer: &dyn TxSigner,
+        tx: &Cosmos,
+        tx_body: &Body,
+        sequence_id: u64,
+    ) -> Result<SimulateResponse, CosmosClient> {
+        let tx_raw = simulation_tx(
+            tx_body,
+            tx.build_fee(vec![], 100u64)?,
+            Some(signer.signer_public_key().into()),
+            sequence_id,
+            tx.sign_mode,
         )?;
 
-        Ok(sign_doc.sign(&signer.private_key)?.to_bytes()?)
+        self.tx.simulate(tx_raw).await
+    }
+
+    /// Gas used by `tx` simulated with `sequence_id`, or with the sequence
+    /// the chain expects on a sequence mismatch.
+    async fn simulated_gas(
+        &self,
+        signer: &dyn TxSigner,
+        tx: &Cosmos,
+        tx_body: &Body,
+        sequence_id: u64,
+    ) -> Result<u64, CosmosClient> {
+        let simulation = match self.simulate_tx(signer, tx, tx_body, sequence_id).await {
+            Err(RpcError(log)) => match expected_sequence(&log) {
+                Some(expected) if expected != sequence_id => {
+                    self.simulate_tx(signer, tx, tx_body, expected).await?
+                }
+                _ => return Err(RpcError(log)),
+            },
+            result => result?,
+        };
+
+        Ok(simulation
+            .gas_info
+            .ok_or(CosmosClient::CannotSimulateTxGasFee)?
+            .gas_used)
+    }
+
+    /// Fee for `gas_used` with the fee strategy, or the gas price of the
+    /// attached signer if there is none.
+    async fn quote(&self, gas_used: u64) -> Result<Quote, CosmosClient> {
+        if let Some(strategy) = &self.fee_strategy {
+            return strategy.quote(gas_used).await;
+        }
+
+        self.signer_fee
+            .as_ref()
+            .ok_or(NoSignerAttached)?
+            .quote(gas_used)
+            .await
+    }
+
+    /// On-chain limits of the auth, gov and consensus params.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - cosmos auth or gov `params` endpoint fails
+    /// - tendermint `consensus_params` endpoint fails
+    pub async fn limits(&self) -> Result<Limits, CosmosClient> {
+        let auth = self.auth.params().await?.params.unwrap_or_default();
+        let deposit = self.gov.params("deposit").await?.deposit_params;
+        let consensus = self.client.latest_consensus_params().await?;
+
+        Ok(Limits {
+            max_memo_characters: auth.max_memo_characters,
+            tx_sig_limit: auth.tx_sig_limit,
+            max_tx_bytes: consensus.consensus_params.block.max_bytes,
+            min_deposit: deposit.map(|params| params.min_deposit).unwrap_or_default(),
+        })
+    }
+
+    /// Check `tx`, paying `fee` for `gas_limit` gas, against the chain limits
+    /// and the coins rules, without signing it. The limits cached by
+    /// `update_limits` are used if any.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - the tx breaks a limit, with all the violations found
+    /// - the limits cannot be fetched
+    /// - tendermint `status` endpoint fails for a relative timeout
+    /// - there is a decode or encode error
+    pub async fn validate(
+        &self,
+        tx: &Cosmos,
+        fee: Vec<cosmrs::Coin>,
+        gas_limit: u64,
+    ) -> Result<(), CosmosClient> {
+        let limits = match &self.limits {
+            Some(limits) => limits.clone(),
+            None => self.limits().await?,
+        };
+
+        validate(
+            &self.tx_body(tx).await?,
+            &tx.build_fee(fee, gas_limit)?,
+            1,
+            tx.sign_mode,
+            &limits,
+        )
+    }
+
+    /// Check that `granter` gave `grantee` a feegrant allowance.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - there is no allowance from `granter` to `grantee`
+    /// - cosmos feegrant `allowance` endpoint fails
+    #[allow(clippy::similar_names)]
+    pub async fn check_fee_allowance(
+        &self,
+        granter: &str,
+        grantee: &str,
+    ) -> Result<(), CosmosClient> {
+        let no_allowance = || CosmosClient::NoFeeAllowance {
+            granter: granter.to_string(),
+            grantee: grantee.to_string(),
+        };
+
+        match self.feegrant.allowance(granter, grantee).await {
+            Ok(response) if response.allowance.is_some() => Ok(()),
+            Ok(_) => Err(no_allowance()),
+            Err(CosmosClient::RpcError(log)) if log.contains("fee-grant not found") => {
+                Err(no_allowance())
+            }
+            Err(e) => Err(e),
+        }
     }
 
     /// # Errors
     ///
     /// Will return `Err` if :
     /// - cosmos `tx` broadcast endpoint fails
+    ///
+    /// If `payload` is signed by the attached signer and passes `CheckTx`,
+    /// the cached sequence is moved past the sequence it was signed with.
+    /// An async broadcast gives no `CheckTx` result and always moves it.
     pub async fn broadcast(
         &mut self,
         payload: Vec<u8>,
         mode: BroadcastMode,
     ) -> Result<Response, CosmosClient> {
-        self.tx.broadcast(payload, mode).await
+        let sequence_id = self.signed_sequence(&payload);
+        let response = self.tx.broadcast(payload, mode).await?;
+
+        if check_tx_log(&response).is_none() {
+            if let Some(sequence_id) = sequence_id {
+                self.sequence_id = Some(self.sequence_id.unwrap_or_default().max(sequence_id + 1));
+            }
+        }
+
+        Ok(response)
     }
 
     /// # Errors
     ///
     /// Will return `Err` if :
     /// - sign or broadcast fails
+    ///
+    /// A tx rejected for an account sequence mismatch is re-signed with the
+    /// sequence expected by the chain, up to `set_sequence_retries` times.
     pub async fn sign_and_broadcast(
         &mut self,
         tx: Cosmos,
         mode: BroadcastMode,
     ) -> Result<Response, CosmosClient> {
-        let payload = self.sign(tx).await?;
+        let mut retries = 0;
+
+        loop {
+            let result = match self.sign_tx(&tx).await {
+                Ok(payload) => self.broadcast(payload, mode).await,
+                Err(e) => Err(e),
+            };
+            let log = match &result {
+                Ok(response) => check_tx_log(response),
+                Err(CosmosClient::RpcError(log)) => Some(log.as_str()),
+                Err(_) => None,
+            };
+            let Some(log) = log.filter(|log| is_sequence_mismatch(log)) else {
+                return result;
+            };
+            if retries >= self.sequence_retries {
+                return result;
+            }
+            retries += 1;
 
-        self.tx.broadcast(payload, mode).await
+            if let Some(expected) = expected_sequence(log) {
+                self.sequence_id = Some(expected);
+            } else {
+                self.update_sequence_id().await?;
+            }
+        }
     }
 
-    fn signer(&self) -> Result<&Signer, CosmosClient> {
-        self.signer.as_ref().ok_or(NoSignerAttached)
+    /// Sequence manager of the attached signer, to sign and broadcast txs
+    /// concurrently with `sign_and_broadcast_managed`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - there is no signer attached
+    /// - cosmos `account` endpoint fails
+    pub async fn sequence_manager(&self) -> Result<SequenceManager, CosmosClient> {
+        SequenceManager::new(
+            self.auth.clone(),
+            self.signer()?.address().to_string().as_str(),
+        )
+        .await
     }
 
-    async fn poll_for_tx(&self, tx: Response) -> Result<GetTxResponse, CosmosClient> {
-        let hash = match tx {
-            Response::Sync(tx) => tx.hash,
-            Response::Async(tx) => tx.hash,
-            Response::Commit(tx) => tx.hash,
-        };
+    /// Sign and broadcast `tx` with a sequence taken from `manager`. Unlike
+    /// `sign_and_broadcast` it only borrows `self`, so many txs can be in
+    /// flight at once, e.g. with `futures::future::join_all`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - sign or broadcast fails
+    ///
+    /// A tx rejected for an account sequence mismatch is re-signed with a new
+    /// sequence, up to `set_sequence_retries` times. The manager resyncs from
+    /// chain when the expected sequence is not in the error.
+    pub async fn sign_and_broadcast_managed(
+        &self,
+        manager: &SequenceManager,
+        tx: &Cosmos,
+        mode: BroadcastMode,
+    ) -> Result<Response, CosmosClient> {
+        let mut retries = 0;
 
-        for _ in 0..60 {
-            let tx = self.tx.get_tx(hash.to_string().as_str()).await;
+        loop {
+            let sequence_id = manager.acquire();
+            let result = match self
+                .sign_simulated(tx, manager.account_number(), sequence_id, manager.floor())
+                .await
+            {
+                Ok(payload) => self.tx.broadcast(payload, mode).await,
+                Err(e) => Err(e),
+            };
+            let log = match &result {
+                Ok(response) => check_tx_log(response),
+                Err(CosmosClient::RpcError(log)) => Some(log.as_str()),
+                Err(_) => None,
+            };
 
-            if tx.is_ok() {
-                return tx;
+            match log {
+                None if result.is_ok() => {
+                    manager.confirm(sequence_id);
+                    return result;
+                }
+                Some(log) if is_sequence_mismatch(log) && retries < self.sequence_retries => {
+                    retries += 1;
+                    let expected = expected_sequence(log);
+                    manager.fail(sequence_id, expected);
+                    if expected.is_none() {
+                        manager.resync().await?;
+                    }
+                }
+                _ => {
+                    manager.fail(sequence_id, log.and_then(expected_sequence));
+                    return result;
+                }
             }
-            sleep(Duration::from_secs(3));
         }
+    }
 
-        Err(CosmosClient::TXPollingTimeout)
+    /// Export `tx` to be signed offline by `address`, paying `fee` for
+    /// `gas_limit` gas.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - the account of `address` does not exist
+    /// - cosmos `account` or tendermint `status` endpoint fails
+    /// - the tx cannot be encoded to protobuf or JSON
+    pub async fn export_unsigned(
+        &self,
+        tx: &Cosmos,
+        address: &str,
+        fee: Coin,
+        gas_limit: u64,
+    ) -> Result<UnsignedTx, CosmosClient> {
+        let (account_id, sequence_id) = self.account_sequence(address).await?;
+        tx.check_max_fee(&fee)?;
+
+        UnsignedTx::new(
+            &self.tx_body(tx).await?,
+            tx.build_fee(
+                vec![cosmrs::Coin {
+                    amount: fee.amount.parse()?,
+                    denom: fee.denom.parse()?,
+                }],
+                gas_limit,
+            )?,
+            self.chain_id.as_str(),
+            account_id,
+            sequence_id,
+        )
+    }
+
+    /// Account number and sequence of `address`, a `key_type` key, for a
+    /// multi-signer tx.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - the account of `address` does not exist
+    /// - cosmos `account` endpoint fails
+    pub async fn signer_data(
+        &self,
+        address: &str,
+        public_key: &PublicKey,
+        key_type: KeyType,
+    ) -> Result<SignerData, CosmosClient> {
+        let (account_id, sequence_id) = self.account_sequence(address).await?;
+        Ok(SignerData::new_with_key_type(
+            key_type,
+            public_key,
+            address,
+            account_id,
+            sequence_id,
+        ))
+    }
+
+    /// Export `tx` to be signed by all of `signers`, paying `fee` for
+    /// `gas_limit` gas. The fee payer of `tx` must be one of the signers.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - `signers` are not the signers of the tx
+    /// - tendermint `status` endpoint fails
+    /// - the tx cannot be encoded to protobuf or JSON
+    pub async fn export_multi_signer(
+        &self,
+        tx: &Cosmos,
+        signers: Vec<SignerData>,
+        fee: Coin,
+        gas_limit: u64,
+    ) -> Result<MultiSignerTx, CosmosClient> {
+        tx.check_max_fee(&fee)?;
+
+        MultiSignerTx::new(
+            &self.tx_body(tx).await?,
+            tx.build_fee(
+                vec![cosmrs::Coin {
+                    amount: fee.amount.parse()?,
+                    denom: fee.denom.parse()?,
+                }],
+                gas_limit,
+            )?,
+            self.chain_id.as_str(),
+            signers,
+        )
+    }
+
+    /// Broadcast a tx signed offline and return its hash.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - `signed` bytes are not valid base64
+    /// - cosmos `tx` broadcast endpoint fails
+    /// - the tx is rejected by `CheckTx`
+    pub async fn broadcast_signed(
+        &mut self,
+        signed: &SignedTx,
+        mode: BroadcastMode,
+    ) -> Result<String, CosmosClient> {
+        let response = self.tx.broadcast(signed.to_bytes()?, mode).await?;
+
+        match broadcast_error(&response) {
+            Some(e) => Err(e),
+            None => Ok(signed.hash.clone()),
+        }
+    }
+
+    async fn sign_with_mode(
+        &self,
+        signer: &dyn TxSigner,
+        tx: &Cosmos,
+        tx_body: &Body,
+        fee: Fee,
+        account_id: u64,
+        sequence_id: u64,
+    ) -> Result<Vec<u8>, CosmosClient> {
+        sign_body_with(
+            signer,
+            tx.sign_mode,
+            tx_body,
+            fee,
+            self.chain_id.as_str(),
+            account_id,
+            sequence_id,
+        )
+        .await
+    }
+
+    /// The body of `tx`, with a relative timeout resolved against the
+    /// latest height.
+    async fn tx_body(&self, tx: &Cosmos) -> Result<Body, CosmosClient> {
+        if tx.timeout_blocks.is_some() {
+            let status = self.client.status().await?;
+            tx.finish_at(status.sync_info.latest_block_height.value())
+        } else {
+            Ok(tx.finish())
+        }
+    }
+
+    /// Sequence `payload` was signed with by the attached signer.
+    fn signed_sequence(&self, payload: &[u8]) -> Option<u64> {
+        let public_key: prost_types::Any = self.signer.as_ref()?.signer_public_key().into();
+        let raw = TxRaw::decode(payload).ok()?;
+
+        AuthInfo::decode(raw.auth_info_bytes.as_slice())
+            .ok()?
+            .signer_infos
+            .into_iter()
+            .find(|signer_info| signer_info.public_key.as_ref() == Some(&public_key))
+            .map(|signer_info| signer_info.sequence)
+    }
+
+    fn signer(&self) -> Result<&dyn TxSigner, CosmosClient> {
+        self.signer.as_deref().ok_or(NoSignerAttached)
+    }
+
+    /// Wait for `tx`, broadcast with `broadcast` or `sign_and_broadcast`, to
+    /// be included in a block, as configured by `set_confirmation`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - the tx was rejected by `CheckTx`, without waiting
+    /// - the tx is not found before the confirmation timeout
+    /// - the tx failed in `DeliverTx`
+    pub async fn confirm_tx(&self, tx: &Response) -> Result<GetTxResponse, CosmosClient> {
+        if let Some(e) = broadcast_error(tx) {
+            return Err(e);
+        }
+
+        let response = self
+            .tx
+            .confirm(tx.hash().to_string().as_str(), &self.confirmation)
+            .await?;
+
+        match tx_error(&response) {
+            Some(e) => Err(e),
+            None => Ok(response),
+        }
     }
 
     /// # Errors
     ///
     /// Will return `Err` if :
     /// - `sign_and_broadcast` returns an err
-    /// - cannot find the hash of the tx on chain after 60'
+    /// - the tx fails or is not found on chain before the confirmation timeout
     /// - cannot Serialize `MsgSend`
     pub async fn send(
         &mut self,
@@ -268,7 +924,7 @@ impl Rpc {
 
         let mut payload = Cosmos::build().add_msg(
             MsgSend {
-                from_address: signer.public_address.to_string(),
+                from_address: signer.address().to_string(),
                 to_address: to.to_string(),
                 amount: coin,
             }
@@ -281,14 +937,14 @@ impl Rpc {
         let tx = self
             .sign_and_broadcast(payload, BroadcastMode::Sync)
             .await?;
-        self.poll_for_tx(tx).await
+        self.confirm_tx(&tx).await
     }
 
     /// # Errors
     ///
     /// Will return `Err` if :
     /// - `sign_and_broadcast` returns an err
-    /// - cannot find the hash of the tx on chain after 60'
+    /// - the tx fails or is not found on chain before the confirmation timeout
     /// - cannot Serialize `MsgDelegate`
     pub async fn stake(
         &mut self,
@@ -300,7 +956,7 @@ impl Rpc {
 
         let mut payload = Cosmos::build().add_msg(
             MsgDelegate {
-                delegator_address: signer.public_address.to_string(),
+                delegator_address: signer.address().to_string(),
                 validator_address: to.to_string(),
                 amount: Some(coin),
             }
@@ -313,14 +969,14 @@ impl Rpc {
         let tx = self
             .sign_and_broadcast(payload, BroadcastMode::Sync)
             .await?;
-        self.poll_for_tx(tx).await
+        self.confirm_tx(&tx).await
     }
 
     /// # Errors
     ///
     /// Will return `Err` if :
     /// - `sign_and_broadcast` returns an err
-    /// - cannot find the hash of the tx on chain after 60'
+    /// - the tx fails or is not found on chain before the confirmation timeout
     /// - cannot Serialize `MsgUndelegate`
     pub async fn unstake(
         &mut self,
@@ -332,7 +988,7 @@ impl Rpc {
 
         let mut payload = Cosmos::build().add_msg(
             MsgUndelegate {
-                delegator_address: signer.public_address.to_string(),
+                delegator_address: signer.address().to_string(),
                 validator_address: to.to_string(),
                 amount: Some(coin),
             }
@@ -345,14 +1001,14 @@ impl Rpc {
         let tx = self
             .sign_and_broadcast(payload, BroadcastMode::Sync)
             .await?;
-        self.poll_for_tx(tx).await
+        self.confirm_tx(&tx).await
     }
 
     /// # Errors
     ///
     /// Will return `Err` if :
     /// - `sign_and_broadcast` returns an err
-    /// - cannot find the hash of the tx on chain after 60'
+    /// - the tx fails or is not found on chain before the confirmation timeout
     /// - cannot Serialize `MsgWithdrawDelegatorReward`
     pub async fn claim_rewards(
         &mut self,
@@ -363,7 +1019,7 @@ impl Rpc {
 
         let mut payload = Cosmos::build().add_msg(
             MsgWithdrawDelegatorReward {
-                delegator_address: signer.public_address.to_string(),
+                delegator_address: signer.address().to_string(),
                 validator_address: to.to_string(),
             }
             .to_any()?,
@@ -375,14 +1031,14 @@ impl Rpc {
         let tx = self
             .sign_and_broadcast(payload, BroadcastMode::Sync)
             .await?;
-        self.poll_for_tx(tx).await
+        self.confirm_tx(&tx).await
     }
 
     /// # Errors
     ///
     /// Will return `Err` if :
     /// - `sign_and_broadcast` returns an err
-    /// - cannot find the hash of the tx on chain after 60'
+    /// - the tx fails or is not found on chain before the confirmation timeout
     /// - cannot Serialize `MsgWithdrawDelegatorReward`
     #[allow(clippy::too_many_arguments)]
     pub async fn ibc_send(
@@ -402,7 +1058,7 @@ impl Rpc {
                 source_port: source_port.to_string(),
                 source_channel: source_channel.to_string(),
                 token: Some(coin),
-                sender: signer.public_address.to_string(),
+                sender: signer.address().to_string(),
                 receiver: to.to_string(),
                 timeout_height,
                 timeout_timestamp,
@@ -416,6 +1072,6 @@ impl Rpc {
         let tx = self
             .sign_and_broadcast(payload, BroadcastMode::Sync)
             .await?;
-        self.poll_for_tx(tx).await
+        self.confirm_tx(&tx).await
     }
 }
@@ -1,4 +1,5 @@
 use crate::error::CosmosClient;
+use crate::proto::{ethermint, injective};
 use cosmos_sdk_proto::cosmos::auth::v1beta1::BaseAccount;
 use cosmos_sdk_proto::cosmos::crypto::ed25519::PubKey;
 use cosmos_sdk_proto::cosmos::evidence::v1beta1::Equivocation;
@@ -12,6 +13,8 @@ use prost::Message;
 pub enum CosmosType {
     BaseAccount(BaseAccount),
     ContinuousVestingAccount(ContinuousVestingAccount),
+    EthAccount(ethermint::types::v1::EthAccount),
+    InjectiveEthAccount(injective::types::v1beta1::EthAccount),
     PubKey(PubKey),
     Equivocation(Equivocation),
     BasicAllowance(BasicAllowance),
@@ -41,6 +44,12 @@ pub fn any_to_cosmos(cosmos: &Any) -> Result<CosmosType, CosmosClient> {
                 ContinuousVestingAccount::decode(cosmos.value.as_slice())?,
             ))
         }
+        "/ethermint.types.v1.EthAccount" => Ok(CosmosType::EthAccount(
+            ethermint::types::v1::EthAccount::decode(cosmos.value.as_slice())?,
+        )),
+        "/injective.types.v1beta1.EthAccount" => Ok(CosmosType::InjectiveEthAccount(
+            injective::types::v1beta1::EthAccount::decode(cosmos.value.as_slice())?,
+        )),
         _ => Err(CosmosClient::UnknownCosmosMsg),
     }
 }
@@ -1,8 +1,10 @@
+use crate::client::any_helper::{any_to_cosmos, CosmosType};
 use crate::error::CosmosClient;
-use crate::error::CosmosClient::{ProstDecodeError, RpcError};
+use crate::error::CosmosClient::{AccountDoesNotExistOnChain, ProstDecodeError, RpcError};
+use crate::proto::{ethermint, injective};
 use cosmos_sdk_proto::cosmos::auth::v1beta1::{
-    QueryAccountRequest, QueryAccountResponse, QueryAccountsRequest, QueryAccountsResponse,
-    QueryParamsRequest, QueryParamsResponse,
+    BaseAccount, QueryAccountRequest, QueryAccountResponse, QueryAccountsRequest,
+    QueryAccountsResponse, QueryParamsRequest, QueryParamsResponse,
 };
 use cosmos_sdk_proto::cosmos::base::query::v1beta1::PageRequest;
 use prost::Message;
@@ -10,6 +12,7 @@ use std::sync::Arc;
 use tendermint::abci::Code;
 use tendermint_rpc::{Client, HttpClient};
 
+#[derive(Clone)]
 pub struct Module {
     rpc: Arc<HttpClient>,
 }
@@ -95,4 +98,55 @@ impl Module {
         }
         QueryParamsResponse::decode(query.value.as_slice()).map_err(ProstDecodeError)
     }
+
+    /// Account number and sequence of `address`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - the account does not exist or is not a base, vesting or ethermint
+    ///   account
+    /// - cosmos `account` endpoint fails
+    pub async fn account_sequence(&self, address: &str) -> Result<(u64, u64), CosmosClient> {
+        let account = self.base_account(address).await?;
+        Ok((account.account_number, account.sequence))
+    }
+
+    /// Base account of `address`, unwrapped from a vesting account.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - the account does not exist or is not a base, vesting or ethermint
+    ///   account
+    /// - cosmos `account` endpoint fails
+    pub async fn base_account(&self, address: &str) -> Result<BaseAccount, CosmosClient> {
+        let account = self.account(address).await?;
+
+        if let Some(account) = account.account {
+            match any_to_cosmos(&account)? {
+                CosmosType::BaseAccount(account)
+                | CosmosType::EthAccount(ethermint::types::v1::EthAccount {
+                    base_account: Some(account),
+                    ..
+                })
+                | CosmosType::InjectiveEthAccount(injective::types::v1beta1::EthAccount {
+                    base_account: Some(account),
+                    ..
+                }) => return Ok(account),
+                CosmosType::ContinuousVestingAccount(account) => {
+                    return account
+                        .base_vesting_account
+                        .ok_or(CosmosClient::NoVestingBaseAccount)?
+                        .base_account
+                        .ok_or(CosmosClient::NoVestingBaseAccount);
+                }
+                _ => {}
+            }
+        }
+
+        Err(AccountDoesNotExistOnChain {
+            address: address.to_string(),
+        })
+    }
 }
@@ -0,0 +1,44 @@
+use crate::error::CosmosClient;
+use crate::error::CosmosClient::{ProstDecodeError, RpcError};
+use crate::proto::feemarket::feemarket::v1::{GasPriceRequest, GasPriceResponse};
+use prost::Message;
+use std::sync::Arc;
+use tendermint::abci::Code;
+use tendermint_rpc::{Client, HttpClient};
+
+#[derive(Clone)]
+pub struct Module {
+    rpc: Arc<HttpClient>,
+}
+
+impl Module {
+    pub fn new(rpc: Arc<HttpClient>) -> Self {
+        Module { rpc }
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - a prost encode / decode fail
+    /// - the json-rpc return an error code
+    /// - if there is some network error
+    pub async fn gas_price(&self, denom: &str) -> Result<GasPriceResponse, CosmosClient> {
+        let query = GasPriceRequest {
+            denom: denom.to_string(),
+        };
+        let query = self
+            .rpc
+            .abci_query(
+                Some("/feemarket.feemarket.v1.Query/GasPrice".to_string()),
+                query.encode_to_vec(),
+                None,
+                false,
+            )
+            .await?;
+
+        if query.code != Code::Ok {
+            return Err(RpcError(query.log));
+        }
+        GasPriceResponse::decode(query.value.as_slice()).map_err(ProstDecodeError)
+    }
+}
@@ -43,7 +43,7 @@ impl Module {
         let query = self
             .rpc
             .abci_query(
-                Some("/cosmos.staking.v1beta1.Query/Validators".to_string()),
+                Some("/cosmos.staking.v1beta1.Query/Validator".to_string()),
                 query.encode_to_vec(),
                 None,
                 false,
@@ -74,7 +74,7 @@ impl Module {
         let query = self
             .rpc
             .abci_query(
-                Some("/cosmos.staking.v1beta1.Query/Validator".to_string()),
+                Some("/cosmos.staking.v1beta1.Query/Validators".to_string()),
                 query.encode_to_vec(),
                 None,
                 false,
@@ -4,8 +4,11 @@ use cosmos_sdk_proto::cosmos::tx::v1beta1::{
     BroadcastMode, GetTxRequest, GetTxResponse, SimulateRequest, SimulateResponse,
 };
 use prost::Message;
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
 use tendermint::abci::Code;
+use tendermint::Hash;
 use tendermint_rpc::endpoint::broadcast::{tx_async, tx_commit, tx_sync};
 use tendermint_rpc::{Client, HttpClient};
 
@@ -16,6 +19,39 @@ pub enum Response {
     Commit(tx_commit::Response),
 }
 
+impl Response {
+    #[must_use]
+    pub fn hash(&self) -> Hash {
+        match self {
+            Response::Async(tx) => tx.hash,
+            Response::Sync(tx) => tx.hash,
+            Response::Commit(tx) => tx.hash,
+        }
+    }
+}
+
+/// How [`Module::confirm`] waits for a tx to be included in a block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Confirmation {
+    /// Delay between two `GetTx` queries.
+    pub poll_interval: Duration,
+    pub timeout: Duration,
+    /// Websocket endpoint, e.g. `ws://localhost:26657/websocket`, to wait for
+    /// the tx event instead of polling. Only used with the `websocket`
+    /// feature, polling is used otherwise or if the subscription fails.
+    pub websocket: Option<String>,
+}
+
+impl Default for Confirmation {
+    fn default() -> Self {
+        Confirmation {
+            poll_interval: Duration::from_secs(3),
+            timeout: Duration::from_secs(180),
+            websocket: None,
+        }
+    }
+}
+
 pub struct Module {
     rpc: Arc<HttpClient>,
 }
@@ -100,4 +136,165 @@ impl Module {
         }
         GetTxResponse::decode(query.value.as_slice()).map_err(ProstDecodeError)
     }
+
+    /// Wait for the tx `hash` to be included in a block, without blocking the
+    /// runtime.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - the tx is not found before `confirmation.timeout`
+    pub async fn confirm(
+        &self,
+        hash: &str,
+        confirmation: &Confirmation,
+    ) -> Result<GetTxResponse, CosmosClient> {
+        self.confirm_or_cancel(hash, confirmation, std::future::pending())
+            .await
+    }
+
+    /// Like [`Module::confirm`], but gives up as soon as `cancel` completes,
+    /// e.g. a shutdown signal.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - the tx is not found before `confirmation.timeout`
+    /// - `cancel` completes first
+    pub async fn confirm_or_cancel(
+        &self,
+        hash: &str,
+        confirmation: &Confirmation,
+        cancel: impl Future<Output = ()>,
+    ) -> Result<GetTxResponse, CosmosClient> {
+        let wait = tokio::time::timeout(confirmation.timeout, self.wait_for_tx(hash, confirmation));
+
+        tokio::select! {
+            result = wait => result.map_err(|_| CosmosClient::TXPollingTimeout),
+            () = cancel => Err(CosmosClient::TXConfirmationCancelled),
+        }
+    }
+
+    async fn wait_for_tx(&self, hash: &str, confirmation: &Confirmation) -> GetTxResponse {
+        #[cfg(feature = "websocket")]
+        if let Some(url) = &confirmation.websocket {
+            if let Ok(tx) = self.wait_for_event(url.as_str(), hash).await {
+                return tx;
+            }
+        }
+
+        loop {
+            if let Ok(tx) = self.get_tx(hash).await {
+                return tx;
+            }
+            tokio::time::sleep(confirmation.poll_interval).await;
+        }
+    }
+
+    /// Wait for the `Tx` event of `hash` on a websocket subscription.
+    #[cfg(feature = "websocket")]
+    async fn wait_for_event(&self, url: &str, hash: &str) -> Result<GetTxResponse, CosmosClient> {
+        use futures::StreamExt;
+        use tendermint_rpc::query::{EventType, Query};
+        use tendermint_rpc::{SubscriptionClient, WebSocketClient};
+
+        let (client, driver) = WebSocketClient::new(url).await?;
+        let driver = tokio::spawn(driver.run());
+        let mut subscription = client
+            .subscribe(Query::from(EventType::Tx).and_eq("tx.hash", hash))
+            .await?;
+
+        // the tx may have been included before the subscription
+        let result = match self.get_tx(hash).await {
+            Ok(tx) => Ok(tx),
+            Err(_) => match subscription.next().await {
+                Some(Ok(_)) => self.get_tx(hash).await,
+                Some(Err(e)) => Err(e.into()),
+                None => Err(CosmosClient::TXPollingTimeout),
+            },
+        };
+
+        client.close()?;
+        let _ = driver.await;
+        result
+    }
+}
+
+/// Log of a tx rejected by `CheckTx`, `None` if it passed or if the
+/// broadcast was async.
+#[must_use]
+pub fn check_tx_log(response: &Response) -> Option<&str> {
+    match response {
+        Response::Sync(tx) if tx.code.is_err() => Some(tx.log.as_str()),
+        Response::Commit(tx) if tx.check_tx.code.is_err() => Some(tx.check_tx.log.as_str()),
+        _ => None,
+    }
+}
+
+/// `TxFailed` error of a tx rejected by `CheckTx`, or by `DeliverTx` for a
+/// commit broadcast. The gas used is unknown for a sync broadcast.
+#[must_use]
+pub fn broadcast_error(response: &Response) -> Option<CosmosClient> {
+    let hash = response.hash().to_string();
+
+    match response {
+        Response::Sync(tx) if tx.code.is_err() => Some(CosmosClient::TxFailed {
+            hash,
+            codespace: String::new(),
+            code: tx.code.value(),
+            raw_log: tx.log.clone(),
+            gas_used: 0,
+        }),
+        Response::Commit(tx) if tx.check_tx.code.is_err() => Some(CosmosClient::TxFailed {
+            hash,
+            codespace: tx.check_tx.codespace.clone(),
+            code: tx.check_tx.code.value(),
+            raw_log: tx.check_tx.log.clone(),
+            gas_used: tx.check_tx.gas_used,
+        }),
+        Response::Commit(tx) if tx.tx_result.code.is_err() => Some(CosmosClient::TxFailed {
+            hash,
+            codespace: tx.tx_result.codespace.clone(),
+            code: tx.tx_result.code.value(),
+            raw_log: tx.tx_result.log.clone(),
+            gas_used: tx.tx_result.gas_used,
+        }),
+        _ => None,
+    }
+}
+
+/// `TxFailed` error of an included tx that failed in `DeliverTx`.
+#[must_use]
+pub fn tx_error(tx: &GetTxResponse) -> Option<CosmosClient> {
+    tx.tx_response
+        .as_ref()
+        .filter(|response| response.code != 0)
+        .map(|response| CosmosClient::TxFailed {
+            hash: response.txhash.clone(),
+            codespace: response.codespace.clone(),
+            code: response.code,
+            raw_log: response.raw_log.clone(),
+            gas_used: response.gas_used,
+        })
+}
+
+#[must_use]
+pub fn is_sequence_mismatch(log: &str) -> bool {
+    log.contains("account sequence mismatch")
+}
+
+/// Sequence expected by the chain in an `account sequence mismatch,
+/// expected N, got M` error log.
+#[must_use]
+pub fn expected_sequence(log: &str) -> Option<u64> {
+    if !is_sequence_mismatch(log) {
+        return None;
+    }
+
+    log.split_once("expected ")?
+        .1
+        .split(|c: char| !c.is_ascii_digit())
+        .next()?
+        .parse()
+        .ok()
 }
@@ -0,0 +1,42 @@
+use crate::error::CosmosClient;
+use crate::error::CosmosClient::{ProstDecodeError, RpcError};
+use crate::proto::osmosis::txfees::v1beta1::{QueryEipBaseFeeRequest, QueryEipBaseFeeResponse};
+use prost::Message;
+use std::sync::Arc;
+use tendermint::abci::Code;
+use tendermint_rpc::{Client, HttpClient};
+
+#[derive(Clone)]
+pub struct Module {
+    rpc: Arc<HttpClient>,
+}
+
+impl Module {
+    pub fn new(rpc: Arc<HttpClient>) -> Self {
+        Module { rpc }
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - a prost encode / decode fail
+    /// - the json-rpc return an error code
+    /// - if there is some network error
+    pub async fn eip_base_fee(&self) -> Result<QueryEipBaseFeeResponse, CosmosClient> {
+        let query = QueryEipBaseFeeRequest {};
+        let query = self
+            .rpc
+            .abci_query(
+                Some("/osmosis.txfees.v1beta1.Query/GetEipBaseFee".to_string()),
+                query.encode_to_vec(),
+                None,
+                false,
+            )
+            .await?;
+
+        if query.code != Code::Ok {
+            return Err(RpcError(query.log));
+        }
+        QueryEipBaseFeeResponse::decode(query.value.as_slice()).map_err(ProstDecodeError)
+    }
+}
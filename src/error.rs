@@ -1,9 +1,10 @@
+use crate::tx::validate::Violation;
 use cosmrs::ErrorReport;
 use hex::FromHexError;
 use prost::{DecodeError, EncodeError};
 use std::convert::Infallible;
 use std::io;
-use std::num::ParseIntError;
+use std::num::{ParseIntError, TryFromIntError};
 use std::str::Utf8Error;
 use thiserror::Error;
 
@@ -19,6 +20,8 @@ pub enum CosmosClient {
     JsonError(#[from] serde_json::Error),
     #[error("CosmosRs tendermint Error")]
     CosmRsTendermintError(#[from] cosmrs::tendermint::Error),
+    #[error("Tendermint Error")]
+    TendermintError(#[from] tendermint::Error),
     #[error("bip32 Error")]
     Bip32Error(#[from] cosmrs::bip32::Error),
     #[error("Timestamp Error")]
@@ -35,6 +38,14 @@ pub enum CosmosClient {
     Infaillible(#[from] Infallible),
     #[error("Parse Int Error")]
     ParseIntError(#[from] ParseIntError),
+    #[error("Try From Int Error")]
+    TryFromIntError(#[from] TryFromIntError),
+    #[error("Base64 Decode Error")]
+    Base64DecodeError(#[from] base64::DecodeError),
+    #[error("Ecdsa Error")]
+    EcdsaError(#[from] k256::ecdsa::Error),
+    #[error("Bcrypt Error")]
+    BcryptError(#[from] bcrypt::BcryptError),
 
     #[error("Unknown cosmos-sdk Msg")]
     UnknownCosmosMsg,
@@ -44,10 +55,62 @@ pub enum CosmosClient {
     CannotSimulateTxGasFee,
     #[error("No signer attached")]
     NoSignerAttached,
+    #[error("Remote signer error : {0}")]
+    RemoteSigner(String),
+    #[error("Invalid armor : {0}")]
+    InvalidArmor(String),
+    #[error("Invalid keystore : {0}")]
+    InvalidKeystore(String),
+    #[error("Invalid keyring : {0}")]
+    InvalidKeyring(String),
+    #[error("Key {0} not found in the keyring")]
+    KeyNotFound(String),
+    #[error("Key {0} already exists in the keyring")]
+    KeyAlreadyExists(String),
+    #[error("Cannot encrypt the key")]
+    KeyEncryption,
+    #[error("Cannot decrypt the key, wrong passphrase or corrupted data")]
+    KeyDecryption,
     #[error("Rpc errors : {0}")]
     RpcError(String),
     #[error("Tx Polling Timeout")]
     TXPollingTimeout,
+    #[error("Tx confirmation cancelled")]
+    TXConfirmationCancelled,
+    #[error("Tx {hash} failed with code {code} in {codespace} : {raw_log}")]
+    TxFailed {
+        hash: String,
+        codespace: String,
+        code: u32,
+        raw_log: String,
+        gas_used: i64,
+    },
     #[error("No base account for vesting wallet")]
     NoVestingBaseAccount,
+    #[error("Invalid gas price {0}")]
+    InvalidGasPrice(String),
+    #[error("Fee {fee} is above the max fee {max_fee}")]
+    FeeAboveMax { fee: String, max_fee: String },
+    #[error("No fee allowance from {granter} to {grantee}")]
+    NoFeeAllowance { granter: String, grantee: String },
+    #[error("Unsigned tx JSON does not match its body and auth info bytes")]
+    UnsignedTxMismatch,
+    #[error("No amino JSON encoding for {0}")]
+    UnsupportedAminoMsg(String),
+    #[error("No JSON encoding for {0}")]
+    UnsupportedJsonMsg(String),
+    #[error("No EIP-712 types for {0}")]
+    UnsupportedEip712Msg(String),
+    #[error("Invalid EIP-712 typed data : {0}")]
+    InvalidTypedData(String),
+    #[error("Invalid multisig : {0}")]
+    InvalidMultisig(String),
+    #[error("Invalid signers : {0}")]
+    InvalidSigners(String),
+    #[error("Invalid tx : {0:?}")]
+    InvalidTx(Vec<Violation>),
+    #[error("Trusted header hash mismatch at height {height}")]
+    TrustedHashMismatch { height: u64 },
+    #[error("Light client verification failed : {0}")]
+    LightClientVerification(String),
 }
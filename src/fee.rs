@@ -0,0 +1,294 @@
+use crate::client::{feemarket, txfees, Rpc};
+use crate::error::CosmosClient;
+use crate::tx::balance::BalanceChanges;
+use async_trait::async_trait;
+use cosmos_sdk_proto::cosmos::base::v1beta1::Coin;
+use cosmos_sdk_proto::tendermint::abci::Event;
+use prost_types::Any;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+const PRECISION: u32 = 18;
+const ONE: u128 = 10u128.pow(PRECISION);
+
+/// Price of one unit of gas, e.g. `0.025uatom`. The amount is kept as an
+/// 18 decimals fixed point number like the cosmos-sdk `Dec`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GasPrice {
+    amount: u128,
+    pub denom: String,
+}
+
+impl GasPrice {
+    /// # Errors
+    ///
+    /// Will return `Err` if `amount` is not a positive decimal with at most
+    /// 18 fractional digits
+    pub fn new(amount: &str, denom: &str) -> Result<Self, CosmosClient> {
+        let invalid = || CosmosClient::InvalidGasPrice(format!("{amount}{denom}"));
+
+        let (int, frac) = amount.split_once('.').unwrap_or((amount, ""));
+        if int.is_empty() && frac.is_empty()
+            || frac.len() > PRECISION as usize
+            || !int.chars().chain(frac.chars()).all(|c| c.is_ascii_digit())
+            || denom.is_empty()
+        {
+            return Err(invalid());
+        }
+        let int = if int.is_empty() {
+            0
+        } else {
+            int.parse::<u128>()?
+        };
+        let frac = if frac.is_empty() {
+            0
+        } else {
+            format!("{frac:0<18}").parse::<u128>()?
+        };
+
+        Ok(GasPrice {
+            amount: int
+                .checked_mul(ONE)
+                .and_then(|amount| amount.checked_add(frac))
+                .ok_or_else(invalid)?,
+            denom: denom.to_string(),
+        })
+    }
+
+    /// Parse a `Dec` as returned by the chain : either a decimal string or
+    /// its 18 decimals fixed point integer encoding used in protobuf.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if `dec` is not a valid decimal
+    pub fn from_sdk_dec(dec: &str, denom: &str) -> Result<Self, CosmosClient> {
+        if dec.contains('.') {
+            return GasPrice::new(dec, denom);
+        }
+        if denom.is_empty() {
+            return Err(CosmosClient::InvalidGasPrice(dec.to_string()));
+        }
+
+        Ok(GasPrice {
+            amount: dec.parse()?,
+            denom: denom.to_string(),
+        })
+    }
+
+    /// Fee for `gas_limit` units of gas, rounded up : `ceil(gas_limit * price)`
+    #[must_use]
+    pub fn fee(&self, gas_limit: u64) -> Coin {
+        let atto = u128::from(gas_limit).saturating_mul(self.amount);
+
+        Coin {
+            denom: self.denom.clone(),
+            amount: (atto / ONE + u128::from(atto % ONE != 0)).to_string(),
+        }
+    }
+}
+
+impl FromStr for GasPrice {
+    type Err = CosmosClient;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let split = s
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .ok_or(CosmosClient::InvalidGasPrice(s.to_string()))?;
+        let (amount, denom) = s.split_at(split);
+
+        GasPrice::new(amount, denom)
+    }
+}
+
+impl Display for GasPrice {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let frac = format!("{:018}", self.amount % ONE);
+        let frac = frac.trim_end_matches('0');
+
+        if frac.is_empty() {
+            write!(f, "{}{}", self.amount / ONE, self.denom)
+        } else {
+            write!(f, "{}.{frac}{}", self.amount / ONE, self.denom)
+        }
+    }
+}
+
+/// Gas limit and fee chosen by a [`FeeStrategy`] for a tx.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Quote {
+    pub gas_limit: u64,
+    pub amount: Coin,
+}
+
+/// Dry run of a tx, see `Rpc::estimate`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Estimate {
+    pub gas_used: u64,
+    /// Gas limit after adjustment, as the tx would be signed with.
+    pub gas_limit: u64,
+    pub fee: Coin,
+    /// Net balance changes of the simulation, with the fee taken from the
+    /// fee payer.
+    pub balance_changes: BalanceChanges,
+    pub events: Vec<Event>,
+    /// Result data of the simulation.
+    pub data: Vec<u8>,
+    pub msg_responses: Vec<Any>,
+    pub log: String,
+}
+
+/// Shared settings of the fee strategies : the gas adjustment applied on
+/// top of the gas estimate, the floor the fee amount is raised to and the
+/// cap it may not exceed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Adjustment {
+    pub gas_adjustment_percent: u8,
+    pub min_fee: Option<u128>,
+    pub max_fee: Option<u128>,
+}
+
+impl Adjustment {
+    #[must_use]
+    pub fn gas_limit(&self, gas: u64) -> u64 {
+        gas.saturating_mul(100 + u64::from(self.gas_adjustment_percent)) / 100
+    }
+
+    /// Raise the `quote` amount to `min_fee`. A quote above `max_fee` is
+    /// rejected rather than lowered, the tx would not pay for its gas.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - `quote` amount is not an integer
+    /// - `quote` amount is above `max_fee`
+    pub fn clamp(&self, mut quote: Quote) -> Result<Quote, CosmosClient> {
+        let mut amount = quote.amount.amount.parse::<u128>()?;
+        if let Some(min_fee) = self.min_fee {
+            amount = amount.max(min_fee);
+        }
+        if let Some(max_fee) = self.max_fee.filter(|max_fee| amount > *max_fee) {
+            return Err(CosmosClient::FeeAboveMax {
+                fee: format!("{amount}{}", quote.amount.denom),
+                max_fee: format!("{max_fee}{}", quote.amount.denom),
+            });
+        }
+        quote.amount.amount = amount.to_string();
+
+        Ok(quote)
+    }
+}
+
+#[async_trait]
+pub trait FeeStrategy: Send + Sync {
+    /// Whether `Rpc::sign` must simulate the tx to get the gas used before
+    /// asking for a quote.
+    fn simulate(&self) -> bool {
+        true
+    }
+
+    /// Quote the fee of a tx. `gas_used` is the simulated gas, or 0 if
+    /// `simulate` returns false.
+    async fn quote(&self, gas_used: u64) -> Result<Quote, CosmosClient>;
+}
+
+/// Fixed gas limit at a static gas price, the tx is not simulated.
+pub struct StaticFee {
+    pub gas_limit: u64,
+    pub gas_price: GasPrice,
+    pub adjustment: Adjustment,
+}
+
+#[async_trait]
+impl FeeStrategy for StaticFee {
+    fn simulate(&self) -> bool {
+        false
+    }
+
+    async fn quote(&self, _gas_used: u64) -> Result<Quote, CosmosClient> {
+        let gas_limit = self.adjustment.gas_limit(self.gas_limit);
+
+        self.adjustment.clamp(Quote {
+            gas_limit,
+            amount: self.gas_price.fee(gas_limit),
+        })
+    }
+}
+
+/// Simulated gas times a static gas price.
+pub struct SimulatedFee {
+    pub gas_price: GasPrice,
+    pub adjustment: Adjustment,
+}
+
+#[async_trait]
+impl FeeStrategy for SimulatedFee {
+    async fn quote(&self, gas_used: u64) -> Result<Quote, CosmosClient> {
+        let gas_limit = self.adjustment.gas_limit(gas_used);
+
+        self.adjustment.clamp(Quote {
+            gas_limit,
+            amount: self.gas_price.fee(gas_limit),
+        })
+    }
+}
+
+pub enum BaseFeeSource {
+    /// Osmosis EIP-1559 base fee from the `txfees` module, in the chain
+    /// base denom.
+    OsmosisTxFees(txfees::Module),
+    /// Gas price of the `feemarket` module for the strategy denom.
+    FeeMarket(feemarket::Module),
+}
+
+/// Simulated gas times the base fee currently enforced by the chain.
+pub struct BaseFee {
+    pub source: BaseFeeSource,
+    pub denom: String,
+    pub adjustment: Adjustment,
+}
+
+impl BaseFee {
+    #[must_use]
+    pub fn osmosis(rpc: &Rpc, denom: &str, adjustment: Adjustment) -> Self {
+        BaseFee {
+            source: BaseFeeSource::OsmosisTxFees(rpc.txfees.clone()),
+            denom: denom.to_string(),
+            adjustment,
+        }
+    }
+
+    #[must_use]
+    pub fn feemarket(rpc: &Rpc, denom: &str, adjustment: Adjustment) -> Self {
+        BaseFee {
+            source: BaseFeeSource::FeeMarket(rpc.feemarket.clone()),
+            denom: denom.to_string(),
+            adjustment,
+        }
+    }
+}
+
+#[async_trait]
+impl FeeStrategy for BaseFee {
+    async fn quote(&self, gas_used: u64) -> Result<Quote, CosmosClient> {
+        let gas_price = match &self.source {
+            BaseFeeSource::OsmosisTxFees(txfees) => GasPrice::from_sdk_dec(
+                txfees.eip_base_fee().await?.base_fee.as_str(),
+                self.denom.as_str(),
+            )?,
+            BaseFeeSource::FeeMarket(feemarket) => {
+                let price = feemarket
+                    .gas_price(self.denom.as_str())
+                    .await?
+                    .price
+                    .ok_or(CosmosClient::InvalidGasPrice(self.denom.clone()))?;
+                GasPrice::from_sdk_dec(price.amount.as_str(), price.denom.as_str())?
+            }
+        };
+        let gas_limit = self.adjustment.gas_limit(gas_used);
+
+        self.adjustment.clamp(Quote {
+            gas_limit,
+            amount: gas_price.fee(gas_limit),
+        })
+    }
+}
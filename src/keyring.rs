@@ -0,0 +1,495 @@
+pub(crate) mod jose;
+
+use crate::error::CosmosClient;
+use crate::fee::GasPrice;
+use crate::multisig::Multisig;
+use crate::proto::cosmos::crypto::keyring::v1::{record, Record};
+use crate::proto::ethermint::crypto::v1::ethsecp256k1;
+use crate::signer::{KeyType, Signer};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use bcrypt::Version;
+use cosmos_sdk_proto::cosmos::crypto::multisig::LegacyAminoPubKey;
+use cosmrs::crypto::secp256k1::VerifyingKey;
+use cosmrs::crypto::PublicKey;
+use cosmrs::AccountId;
+use prost::Message;
+use prost_types::Any;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use zeroize::Zeroizing;
+
+/// Directory of the `test` backend in the home of a chain binary.
+pub const TEST_BACKEND_DIR: &str = "keyring-test";
+
+/// Directory of the `file` backend in the home of a chain binary.
+pub const FILE_BACKEND_DIR: &str = "keyring-file";
+
+/// Fixed password of the `test` backend.
+const TEST_PASSWORD: &str = "test";
+
+/// bcrypt hash of the passphrase of the `file` backend.
+const KEYHASH: &str = "keyhash";
+
+/// `bcrypt.DefaultCost` of Go, used for the `keyhash`.
+const KEYHASH_COST: u32 = 10;
+
+const INFO_SUFFIX: &str = ".info";
+const ADDRESS_SUFFIX: &str = ".address";
+
+const MULTISIG_TYPE_URL: &str = "/cosmos.crypto.multisig.LegacyAminoPubKey";
+
+/// Item of the Go keyring, stored as JSON in a JWE file.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Item {
+    key: String,
+    #[serde(serialize_with = "to_base64", deserialize_with = "from_base64")]
+    data: Vec<u8>,
+    #[serde(default)]
+    label: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    keychain_not_trust_application: bool,
+    #[serde(default)]
+    keychain_not_synchronizable: bool,
+}
+
+fn to_base64<S: Serializer>(data: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&STANDARD.encode(data))
+}
+
+fn from_base64<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    Option::<String>::deserialize(deserializer)?
+        .map_or(Ok(vec![]), |data| STANDARD.decode(data))
+        .map_err(serde::de::Error::custom)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyKind {
+    Local,
+    Ledger,
+    Multi,
+    Offline,
+}
+
+/// Public part of a keyring record.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyInfo {
+    pub name: String,
+    pub kind: KeyKind,
+    pub pub_key: Any,
+}
+
+impl KeyInfo {
+    fn new(record: Record) -> Result<Self, CosmosClient> {
+        let kind = match record.item {
+            Some(record::Item::Local(_)) => KeyKind::Local,
+            Some(record::Item::Ledger(_)) => KeyKind::Ledger,
+            Some(record::Item::Multi(_)) => KeyKind::Multi,
+            Some(record::Item::Offline(_)) => KeyKind::Offline,
+            None => {
+                return Err(CosmosClient::InvalidKeyring(format!(
+                    "{} has no key, a legacy record can be migrated with `keys migrate`",
+                    record.name
+                )))
+            }
+        };
+        let pub_key = record.pub_key.ok_or_else(|| {
+            CosmosClient::InvalidKeyring(format!("{} has no public key", record.name))
+        })?;
+
+        Ok(KeyInfo {
+            name: record.name,
+            kind,
+            pub_key,
+        })
+    }
+
+    /// Type of the key, `None` for a multisig.
+    #[must_use]
+    pub fn key_type(&self) -> Option<KeyType> {
+        KeyType::from_type_url(&self.pub_key.type_url)
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - the key is a multisig
+    /// - the public key is invalid
+    pub fn public_key(&self) -> Result<PublicKey, CosmosClient> {
+        if self.key_type().is_none() {
+            return Err(CosmosClient::InvalidKeyring(format!(
+                "unsupported public key {}",
+                self.pub_key.type_url
+            )));
+        }
+        // secp256k1 and ethsecp256k1 keys share the same `{ key }` message
+        let key = ethsecp256k1::PubKey::decode(self.pub_key.value.as_slice())?.key;
+
+        Ok(VerifyingKey::from_sec1_bytes(&key)?.into())
+    }
+
+    /// Address of the key, or of the `LegacyAminoPubKey` of a multisig.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - the public key is invalid
+    /// - if the prefix is bad
+    pub fn address(&self, prefix: &str) -> Result<AccountId, CosmosClient> {
+        if self.pub_key.type_url == MULTISIG_TYPE_URL {
+            let key = LegacyAminoPubKey::decode(self.pub_key.value.as_slice())?;
+            let public_keys = key
+                .public_keys
+                .iter()
+                .map(PublicKey::try_from)
+                .collect::<Result<Vec<_>, _>>()?;
+
+            return Multisig::new(key.threshold, public_keys)?.account_id(prefix);
+        }
+        let key_type = self.key_type().unwrap_or_default();
+
+        key_type.address(&self.public_key()?, prefix)
+    }
+}
+
+/// Keys of the `test` or `file` backend of a Cosmos SDK keyring, shared
+/// with `<chain binary> keys` : `<name>.info` records and
+/// `<hex address>.address` index, each encrypted as a JWE file.
+pub struct Keyring {
+    dir: PathBuf,
+    password: Zeroizing<String>,
+}
+
+impl Keyring {
+    /// `test` backend of `home`, such as `~/.gaia`.
+    #[must_use]
+    pub fn test(home: impl AsRef<Path>) -> Self {
+        Keyring {
+            dir: home.as_ref().join(TEST_BACKEND_DIR),
+            password: Zeroizing::new(TEST_PASSWORD.to_string()),
+        }
+    }
+
+    /// `file` backend of `home`, such as `~/.gaia`. The `keyhash` of the
+    /// passphrase is created along with the backend directory.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - the passphrase does not match the `keyhash`
+    /// - the backend directory cannot be read or created
+    pub fn file(home: impl AsRef<Path>, passphrase: &str) -> Result<Self, CosmosClient> {
+        let keyring = Keyring {
+            dir: home.as_ref().join(FILE_BACKEND_DIR),
+            password: Zeroizing::new(passphrase.to_string()),
+        };
+        let keyhash = keyring.dir.join(KEYHASH);
+
+        match fs::read_to_string(&keyhash) {
+            Ok(hash) => {
+                if !bcrypt::verify(passphrase, hash.trim())? {
+                    return Err(CosmosClient::KeyDecryption);
+                }
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                keyring.create_dir()?;
+                let hash = bcrypt::hash_with_result(passphrase, KEYHASH_COST)?
+                    .format_for_version(Version::TwoA);
+                write_file(&keyhash, hash.as_bytes(), 0o555)?;
+            }
+            Err(e) => return Err(e.into()),
+        }
+
+        Ok(keyring)
+    }
+
+    /// Keys sorted by name.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - the backend directory cannot be read
+    /// - a record cannot be decrypted or decoded
+    pub fn list(&self) -> Result<Vec<KeyInfo>, CosmosClient> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut keys = vec![];
+        for entry in entries {
+            let key = unescape(&entry?.file_name().to_string_lossy());
+            if let Some(name) = key.strip_suffix(INFO_SUFFIX) {
+                keys.push(self.key(name)?);
+            }
+        }
+        keys.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(keys)
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - the key does not exist
+    /// - the record cannot be decrypted or decoded
+    pub fn key(&self, name: &str) -> Result<KeyInfo, CosmosClient> {
+        KeyInfo::new(self.record(name)?)
+    }
+
+    /// Store the private key of `signer` as `name`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - a key with this name or address already exists
+    /// - the backend directory cannot be written
+    pub fn add(&self, name: &str, signer: &Signer) -> Result<KeyInfo, CosmosClient> {
+        if self.read_item(&info_key(name))?.is_some() {
+            return Err(CosmosClient::KeyAlreadyExists(name.to_string()));
+        }
+        if self
+            .read_item(&address_key(&signer.public_address))?
+            .is_some()
+        {
+            return Err(CosmosClient::KeyAlreadyExists(
+                signer.public_address.to_string(),
+            ));
+        }
+
+        let record = Record {
+            name: name.to_string(),
+            pub_key: Some(Any {
+                type_url: signer.key_type.type_url().to_string(),
+                value: ethsecp256k1::PubKey {
+                    key: signer.public_key.to_bytes(),
+                }
+                .encode_to_vec(),
+            }),
+            item: Some(record::Item::Local(record::Local {
+                priv_key: Some(Any {
+                    type_url: private_key_type_url(signer.key_type).to_string(),
+                    value: ethsecp256k1::PrivKey {
+                        key: signer.secret.to_bytes().to_vec(),
+                    }
+                    .encode_to_vec(),
+                }),
+            })),
+        };
+        self.write_record(&record, &signer.public_address)?;
+
+        KeyInfo::new(record)
+    }
+
+    /// Remove `name` and its address index.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - the key does not exist
+    /// - the public key is invalid
+    /// - the backend directory cannot be written
+    pub fn delete(&self, name: &str) -> Result<(), CosmosClient> {
+        // the index only keeps the address bytes, any prefix will do
+        let address = self.key(name)?.address("cosmos")?;
+
+        self.remove_item(&address_key(&address))?;
+        self.remove_item(&info_key(name))
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - the key does not exist
+    /// - a key named `new_name` already exists
+    /// - the public key is invalid
+    /// - the backend directory cannot be written
+    pub fn rename(&self, name: &str, new_name: &str) -> Result<(), CosmosClient> {
+        if self.read_item(&info_key(new_name))?.is_some() {
+            return Err(CosmosClient::KeyAlreadyExists(new_name.to_string()));
+        }
+        let mut record = self.record(name)?;
+        record.name = new_name.to_string();
+        let address = KeyInfo::new(record.clone())?.address("cosmos")?;
+
+        self.write_record(&record, &address)?;
+        self.remove_item(&info_key(name))
+    }
+
+    /// Load the local key `name` as a [`Signer`].
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - the key does not exist
+    /// - the key is not a local key
+    /// - if the prefix is bad
+    pub fn signer(
+        &self,
+        name: &str,
+        prefix: &str,
+        gas_adjustment_percent: u8,
+        gas_price: GasPrice,
+    ) -> Result<Signer, CosmosClient> {
+        let record = self.record(name)?;
+        let Some(record::Item::Local(record::Local {
+            priv_key: Some(priv_key),
+        })) = record.item
+        else {
+            return Err(CosmosClient::InvalidKeyring(format!(
+                "{name} is not a local key"
+            )));
+        };
+
+        let key_type = [
+            KeyType::Secp256k1,
+            KeyType::EthSecp256k1,
+            KeyType::InjectiveEthSecp256k1,
+        ]
+        .into_iter()
+        .find(|key_type| private_key_type_url(*key_type) == priv_key.type_url)
+        .ok_or_else(|| {
+            CosmosClient::InvalidKeyring(format!("unsupported private key {}", priv_key.type_url))
+        })?;
+        let key = Zeroizing::new(ethsecp256k1::PrivKey::decode(priv_key.value.as_slice())?.key);
+
+        Signer::new(
+            key_type,
+            k256::ecdsa::SigningKey::from_slice(&key)?,
+            None,
+            prefix,
+            gas_adjustment_percent,
+            gas_price,
+        )
+    }
+
+    fn record(&self, name: &str) -> Result<Record, CosmosClient> {
+        let data = self
+            .read_item(&info_key(name))?
+            .ok_or_else(|| CosmosClient::KeyNotFound(name.to_string()))?;
+
+        Ok(Record::decode(data.as_slice())?)
+    }
+
+    /// Write the record and point its address index to it.
+    fn write_record(&self, record: &Record, address: &AccountId) -> Result<(), CosmosClient> {
+        let info_key = info_key(&record.name);
+
+        self.write_item(&info_key, &record.encode_to_vec())?;
+        self.write_item(&address_key(address), info_key.as_bytes())
+    }
+
+    fn read_item(&self, key: &str) -> Result<Option<Zeroizing<Vec<u8>>>, CosmosClient> {
+        let token = match fs::read_to_string(self.dir.join(escape(key))) {
+            Ok(token) => token,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let item: Item = serde_json::from_slice(&jose::decrypt(&token, &self.password)?)?;
+
+        Ok(Some(Zeroizing::new(item.data)))
+    }
+
+    fn write_item(&self, key: &str, data: &[u8]) -> Result<(), CosmosClient> {
+        let item = Zeroizing::new(serde_json::to_vec(&Item {
+            key: key.to_string(),
+            data: data.to_vec(),
+            label: String::new(),
+            description: String::new(),
+            keychain_not_trust_application: false,
+            keychain_not_synchronizable: false,
+        })?);
+
+        self.create_dir()?;
+        write_file(
+            &self.dir.join(escape(key)),
+            jose::encrypt(&item, &self.password)?.as_bytes(),
+            0o600,
+        )
+    }
+
+    fn remove_item(&self, key: &str) -> Result<(), CosmosClient> {
+        match fs::remove_file(self.dir.join(escape(key))) {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn create_dir(&self) -> Result<(), CosmosClient> {
+        let mut builder = fs::DirBuilder::new();
+        builder.recursive(true);
+        #[cfg(unix)]
+        std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+
+        Ok(builder.create(&self.dir)?)
+    }
+}
+
+fn info_key(name: &str) -> String {
+    format!("{name}{INFO_SUFFIX}")
+}
+
+fn address_key(address: &AccountId) -> String {
+    format!("{}{ADDRESS_SUFFIX}", hex::encode(address.to_bytes()))
+}
+
+fn private_key_type_url(key_type: KeyType) -> &'static str {
+    match key_type {
+        KeyType::Secp256k1 => "/cosmos.crypto.secp256k1.PrivKey",
+        KeyType::EthSecp256k1 => "/ethermint.crypto.v1.ethsecp256k1.PrivKey",
+        KeyType::InjectiveEthSecp256k1 => "/injective.crypto.v1beta1.ethsecp256k1.PrivKey",
+    }
+}
+
+/// File name of a keyring item, percent-encoding the bytes that are not
+/// safe in a path.
+fn escape(key: &str) -> String {
+    key.bytes()
+        .map(|byte| match byte {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'.' | b'-' | b'_' => {
+                char::from(byte).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+fn unescape(file_name: &str) -> String {
+    let mut bytes = vec![];
+    let mut rest = file_name.as_bytes();
+
+    while let Some((&byte, tail)) = rest.split_first() {
+        let decoded = tail
+            .get(..2)
+            .filter(|_| byte == b'%')
+            .and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+
+        if let Some(decoded) = decoded {
+            bytes.push(decoded);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+
+    String::from_utf8_lossy(&bytes).to_string()
+}
+
+fn write_file(path: &Path, data: &[u8], mode: u32) -> Result<(), CosmosClient> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, mode);
+    #[cfg(not(unix))]
+    let _ = mode;
+
+    std::io::Write::write_all(&mut options.open(path)?, data)?;
+    Ok(())
+}
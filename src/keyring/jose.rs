@@ -0,0 +1,124 @@
+use crate::error::CosmosClient;
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::Aes256Gcm;
+use aes_kw::KekAes128;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use rand_core::{OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use zeroize::Zeroizing;
+
+const ALG: &str = "PBES2-HS256+A128KW";
+const ENC: &str = "A256GCM";
+const ITERATIONS: u32 = 8192;
+const TAG_SIZE: usize = 16;
+
+/// Protected header of the keyring JWE, the `created` date written by the
+/// Go keyring is ignored.
+#[derive(Serialize, Deserialize)]
+struct Header {
+    alg: String,
+    enc: String,
+    p2c: u32,
+    p2s: String,
+}
+
+/// Encrypt `payload` as a compact JWE with the `password`, the format of the
+/// items of the `file` and `test` keyring backends.
+pub(crate) fn encrypt(payload: &[u8], password: &str) -> Result<String, CosmosClient> {
+    let mut salt = [0u8; 16];
+    OsRng.fill_bytes(&mut salt);
+    let mut cek = Zeroizing::new([0u8; 32]);
+    OsRng.fill_bytes(cek.as_mut());
+    let mut iv = [0u8; 12];
+    OsRng.fill_bytes(&mut iv);
+
+    let header = URL_SAFE_NO_PAD.encode(serde_json::to_vec(&Header {
+        alg: ALG.to_string(),
+        enc: ENC.to_string(),
+        p2c: ITERATIONS,
+        p2s: URL_SAFE_NO_PAD.encode(salt),
+    })?);
+
+    let mut encrypted_key = [0u8; 40];
+    key_encryption_key(password, &salt, ITERATIONS)
+        .wrap(cek.as_ref(), &mut encrypted_key)
+        .map_err(|_| CosmosClient::KeyEncryption)?;
+
+    let mut ciphertext = Aes256Gcm::new(cek.as_ref().into())
+        .encrypt(
+            &iv.into(),
+            Payload {
+                msg: payload,
+                aad: header.as_bytes(),
+            },
+        )
+        .map_err(|_| CosmosClient::KeyEncryption)?;
+    let tag = ciphertext.split_off(ciphertext.len() - TAG_SIZE);
+
+    Ok([
+        header,
+        URL_SAFE_NO_PAD.encode(encrypted_key),
+        URL_SAFE_NO_PAD.encode(iv),
+        URL_SAFE_NO_PAD.encode(ciphertext),
+        URL_SAFE_NO_PAD.encode(tag),
+    ]
+    .join("."))
+}
+
+/// Decrypt a compact JWE of [`encrypt`].
+pub(crate) fn decrypt(token: &str, password: &str) -> Result<Zeroizing<Vec<u8>>, CosmosClient> {
+    let parts: Vec<&str> = token.trim().split('.').collect();
+    let [header, encrypted_key, iv, ciphertext, tag] = parts[..] else {
+        return Err(CosmosClient::InvalidKeyring(
+            "item is not a compact JWE".to_string(),
+        ));
+    };
+
+    let decoded: Header = serde_json::from_slice(&URL_SAFE_NO_PAD.decode(header)?)?;
+    if decoded.alg != ALG || decoded.enc != ENC {
+        return Err(CosmosClient::InvalidKeyring(format!(
+            "unsupported {} / {} encryption",
+            decoded.alg, decoded.enc
+        )));
+    }
+    let iv = URL_SAFE_NO_PAD.decode(iv)?;
+    if iv.len() != 12 {
+        return Err(CosmosClient::InvalidKeyring(
+            "iv is not 12 bytes".to_string(),
+        ));
+    }
+
+    let mut cek = Zeroizing::new([0u8; 32]);
+    key_encryption_key(password, &URL_SAFE_NO_PAD.decode(decoded.p2s)?, decoded.p2c)
+        .unwrap(&URL_SAFE_NO_PAD.decode(encrypted_key)?, cek.as_mut())
+        .map_err(|_| CosmosClient::KeyDecryption)?;
+
+    Aes256Gcm::new(cek.as_ref().into())
+        .decrypt(
+            iv.as_slice().into(),
+            Payload {
+                msg: &[
+                    URL_SAFE_NO_PAD.decode(ciphertext)?,
+                    URL_SAFE_NO_PAD.decode(tag)?,
+                ]
+                .concat(),
+                aad: header.as_bytes(),
+            },
+        )
+        .map(Zeroizing::new)
+        .map_err(|_| CosmosClient::KeyDecryption)
+}
+
+/// AES key wrapping the content key, derived from `password` with PBKDF2
+/// as set by RFC 7518 section 4.8.
+pub(crate) fn key_encryption_key(password: &str, p2s: &[u8], p2c: u32) -> KekAes128 {
+    let salt = [ALG.as_bytes(), &[0], p2s].concat();
+
+    KekAes128::from(pbkdf2::pbkdf2_hmac_array::<Sha256, 16>(
+        password.as_bytes(),
+        &salt,
+        p2c,
+    ))
+}
@@ -1,5 +1,13 @@
 pub mod client;
 pub mod error;
+pub mod fee;
+pub mod keyring;
+#[cfg(feature = "light-client")]
+pub mod light_client;
+pub mod monitor;
+pub mod multisig;
+pub mod proto;
+pub mod sequence;
 pub mod signer;
 pub mod tx;
 pub use cosmos_sdk_proto as cosmos_sdk;
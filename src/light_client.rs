@@ -0,0 +1,454 @@
+use crate::error::CosmosClient;
+use crate::error::CosmosClient::{LightClientVerification, TrustedHashMismatch};
+use std::collections::{BTreeMap, HashSet};
+use std::time::Duration;
+use tendermint::block::signed_header::SignedHeader;
+use tendermint::block::{Commit, CommitSig, Height};
+use tendermint::crypto::default::signature::Verifier;
+use tendermint::crypto::default::Sha256;
+use tendermint::crypto::Sha256 as _;
+use tendermint::merkle::simple_hash_from_byte_vectors;
+use tendermint::trust_threshold::TrustThresholdFraction;
+use tendermint::vote::{SignedVote, Type, ValidatorIndex, Vote};
+use tendermint::{chain, validator, AppHash, Hash, Time};
+use tendermint_rpc::{Client, HttpClient, Paging};
+
+/// A signed header together with the validator sets needed to verify it
+/// and the headers following it.
+#[derive(Clone, Debug)]
+pub struct LightBlock {
+    pub signed_header: SignedHeader,
+    pub validators: validator::Set,
+    pub next_validators: validator::Set,
+}
+
+impl LightBlock {
+    #[must_use]
+    pub fn height(&self) -> u64 {
+        self.signed_header.header.height.value()
+    }
+
+    #[must_use]
+    pub fn time(&self) -> Time {
+        self.signed_header.header.time
+    }
+
+    #[must_use]
+    pub fn hash(&self) -> Hash {
+        self.signed_header.header.hash()
+    }
+
+    #[must_use]
+    pub fn app_hash(&self) -> AppHash {
+        self.signed_header.header.app_hash.clone()
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Options {
+    /// Minimum share of the trusted validators that must sign a
+    /// non-adjacent header for it to be accepted (1/3 by default).
+    pub trust_threshold: TrustThresholdFraction,
+    /// How long a verified header can be used as a trust root. Must be
+    /// shorter than the chain unbonding period.
+    pub trusting_period: Duration,
+    /// Tolerated skew between our clock and the header timestamps.
+    pub clock_drift: Duration,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            trust_threshold: TrustThresholdFraction::ONE_THIRD,
+            trusting_period: Duration::from_secs(14 * 24 * 3600),
+            clock_drift: Duration::from_secs(10),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Success,
+    /// The trusted validators did not sign enough of the untrusted header,
+    /// an intermediate header must be verified first.
+    NotEnoughTrust,
+    Invalid(String),
+}
+
+/// Verify `untrusted` against the `trusted` light block, following the
+/// tendermint skipping verification rules.
+#[must_use]
+pub fn verify(
+    trusted: &LightBlock,
+    untrusted: &LightBlock,
+    options: &Options,
+    now: Time,
+) -> Verdict {
+    let trusted_header = &trusted.signed_header.header;
+    let header = &untrusted.signed_header.header;
+    let commit = &untrusted.signed_header.commit;
+
+    if header.chain_id != trusted_header.chain_id {
+        return Verdict::Invalid(format!(
+            "chain id mismatch, expected {} got {}",
+            trusted_header.chain_id, header.chain_id
+        ));
+    }
+    match trusted_header.time.checked_add(options.trusting_period) {
+        Some(expires) if expires.after(now) => {}
+        _ => return Verdict::Invalid("trusted header is outside the trusting period".to_string()),
+    }
+    match now.checked_add(options.clock_drift) {
+        Some(max) if header.time.before(max) => {}
+        _ => return Verdict::Invalid("header timestamp is in the future".to_string()),
+    }
+    if header.height <= trusted_header.height {
+        return Verdict::Invalid("header height is not above the trusted height".to_string());
+    }
+    if !header.time.after(trusted_header.time) {
+        return Verdict::Invalid("header time is not after the trusted time".to_string());
+    }
+    if untrusted.validators.hash() != header.validators_hash {
+        return Verdict::Invalid("validator set does not match header".to_string());
+    }
+    if untrusted.next_validators.hash() != header.next_validators_hash {
+        return Verdict::Invalid("next validator set does not match header".to_string());
+    }
+    if commit.block_id.hash != header.hash() {
+        return Verdict::Invalid("commit is not for this header".to_string());
+    }
+
+    if header.height == trusted_header.height.increment() {
+        if header.validators_hash != trusted_header.next_validators_hash {
+            return Verdict::Invalid("validator set does not follow the trusted one".to_string());
+        }
+    } else {
+        match has_enough_power(
+            commit,
+            &header.chain_id,
+            &trusted.next_validators,
+            options.trust_threshold,
+        ) {
+            Ok(true) => {}
+            Ok(false) => return Verdict::NotEnoughTrust,
+            Err(e) => return Verdict::Invalid(e),
+        }
+    }
+
+    match has_enough_power(
+        commit,
+        &header.chain_id,
+        &untrusted.validators,
+        TrustThresholdFraction::TWO_THIRDS,
+    ) {
+        Ok(true) => Verdict::Success,
+        Ok(false) => Verdict::Invalid("less than 2/3 of the validators signed".to_string()),
+        Err(e) => Verdict::Invalid(e),
+    }
+}
+
+fn has_enough_power(
+    commit: &Commit,
+    chain_id: &chain::Id,
+    validators: &validator::Set,
+    threshold: TrustThresholdFraction,
+) -> Result<bool, String> {
+    let mut seen = HashSet::new();
+    let mut signed_power = 0u64;
+
+    for (index, signature) in commit.signatures.iter().enumerate() {
+        let CommitSig::BlockIdFlagCommit {
+            validator_address,
+            timestamp,
+            signature,
+        } = signature
+        else {
+            continue;
+        };
+        let Some(validator) = validators.validator(*validator_address) else {
+            continue;
+        };
+        if !seen.insert(*validator_address) {
+            return Err(format!("duplicate vote from {validator_address}"));
+        }
+
+        let vote = Vote {
+            vote_type: Type::Precommit,
+            height: commit.height,
+            round: commit.round,
+            block_id: Some(commit.block_id),
+            timestamp: Some(*timestamp),
+            validator_address: *validator_address,
+            validator_index: u32::try_from(index)
+                .ok()
+                .and_then(|index| ValidatorIndex::try_from(index).ok())
+                .ok_or("invalid validator index")?,
+            signature: signature.clone(),
+            extension: vec![],
+            extension_signature: None,
+        };
+        let vote = SignedVote::from_vote(vote, chain_id.clone())
+            .ok_or(format!("missing signature from {validator_address}"))?;
+        validator
+            .verify_signature::<Verifier>(&vote.sign_bytes(), vote.signature())
+            .map_err(|_| format!("invalid signature from {validator_address}"))?;
+
+        signed_power += validator.power();
+    }
+
+    let total_power = validators.total_voting_power().value();
+    Ok(
+        u128::from(signed_power) * u128::from(threshold.denominator())
+            > u128::from(total_power) * u128::from(threshold.numerator()),
+    )
+}
+
+pub struct LightClient {
+    rpc: HttpClient,
+    options: Options,
+    trusted: BTreeMap<u64, LightBlock>,
+}
+
+impl LightClient {
+    /// Bootstrap a light client from a header the caller trusts, identified
+    /// by its `height` and `hash` (obtained out of band).
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - rpc server is down or invalid
+    /// - the header at `height` does not match `hash`
+    pub async fn new(
+        url: &str,
+        height: u64,
+        hash: Hash,
+        options: Options,
+    ) -> Result<Self, CosmosClient> {
+        let mut client = LightClient {
+            rpc: HttpClient::new(url)?,
+            options,
+            trusted: BTreeMap::new(),
+        };
+
+        let block = client.fetch(Some(height)).await?;
+        if block.hash() != hash {
+            return Err(TrustedHashMismatch { height });
+        }
+        Self::check_validator_sets(&block)?;
+        client.trusted.insert(height, block);
+
+        Ok(client)
+    }
+
+    /// Start from an already verified light block, e.g. one persisted by a
+    /// previous run.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the url is invalid
+    pub fn from_trusted(
+        url: &str,
+        trusted: LightBlock,
+        options: Options,
+    ) -> Result<Self, CosmosClient> {
+        Ok(LightClient {
+            rpc: HttpClient::new(url)?,
+            options,
+            trusted: BTreeMap::from([(trusted.height(), trusted)]),
+        })
+    }
+
+    #[must_use]
+    pub fn latest_trusted(&self) -> Option<&LightBlock> {
+        self.trusted.values().next_back()
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - there is some network error
+    /// - a header between the trusted state and `height` fails verification
+    pub async fn verify_to_height(&mut self, height: u64) -> Result<LightBlock, CosmosClient> {
+        if let Some(block) = self.trusted.get(&height) {
+            return Ok(block.clone());
+        }
+
+        let target = self.fetch(Some(height)).await?;
+        self.verify_to_target(target).await
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - there is some network error
+    /// - a header between the trusted state and the tip fails verification
+    pub async fn verify_to_latest(&mut self) -> Result<LightBlock, CosmosClient> {
+        let target = self.fetch(None).await?;
+        if let Some(block) = self.trusted.get(&target.height()) {
+            return Ok(block.clone());
+        }
+        self.verify_to_target(target).await
+    }
+
+    /// App hash of the state after executing the block at `height`, which
+    /// is committed in the header of `height + 1`. Query proofs for
+    /// `height` must be checked against this root.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if header `height + 1` cannot be verified
+    pub async fn app_hash(&mut self, height: u64) -> Result<AppHash, CosmosClient> {
+        Ok(self.verify_to_height(height + 1).await?.app_hash())
+    }
+
+    /// Check that `tx` is part of the verified block at `height`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - the header at `height` cannot be verified
+    /// - the block returned by the node does not match the verified header
+    pub async fn verify_tx_inclusion(
+        &mut self,
+        height: u64,
+        tx: &[u8],
+    ) -> Result<bool, CosmosClient> {
+        let verified = self.verify_to_height(height).await?;
+        let block = self.rpc.block(Height::try_from(height)?).await?.block;
+
+        if block.header.hash() != verified.hash() {
+            return Err(LightClientVerification(format!(
+                "block {height} does not match the verified header"
+            )));
+        }
+        let leaves: Vec<[u8; 32]> = block.data.iter().map(Sha256::digest).collect();
+        let data_hash = Hash::Sha256(simple_hash_from_byte_vectors::<Sha256>(&leaves));
+        if block.header.data_hash.unwrap_or_default() != data_hash {
+            return Err(LightClientVerification(format!(
+                "block {height} txs do not match the verified data hash"
+            )));
+        }
+
+        Ok(block.data.iter().any(|included| included == tx))
+    }
+
+    async fn verify_to_target(&mut self, target: LightBlock) -> Result<LightBlock, CosmosClient> {
+        let height = target.height();
+        Self::check_validator_sets(&target)?;
+
+        let Some(mut trusted) = self
+            .trusted
+            .range(..height)
+            .next_back()
+            .map(|(_, b)| b.clone())
+        else {
+            return self.verify_backward(target).await;
+        };
+
+        let mut pending = vec![target];
+        while let Some(untrusted) = pending.last() {
+            match verify(&trusted, untrusted, &self.options, Time::now()) {
+                Verdict::Success => {
+                    let verified = pending.pop().ok_or(LightClientVerification(
+                        "empty verification stack".to_string(),
+                    ))?;
+                    self.trusted.insert(verified.height(), verified.clone());
+                    trusted = verified;
+                }
+                Verdict::NotEnoughTrust => {
+                    let pivot = (trusted.height() + untrusted.height()) / 2;
+                    if pivot == trusted.height() {
+                        return Err(LightClientVerification(format!(
+                            "cannot bisect between {} and {}",
+                            trusted.height(),
+                            untrusted.height()
+                        )));
+                    }
+                    let intermediate = self.fetch(Some(pivot)).await?;
+                    pending.push(intermediate);
+                }
+                Verdict::Invalid(e) => {
+                    return Err(LightClientVerification(format!(
+                        "header {} : {e}",
+                        untrusted.height()
+                    )));
+                }
+            }
+        }
+
+        Ok(trusted)
+    }
+
+    /// Headers below the lowest trusted one are verified through the
+    /// `last_block_id` hash chain.
+    async fn verify_backward(&mut self, target: LightBlock) -> Result<LightBlock, CosmosClient> {
+        let Some(mut trusted) = self.trusted.values().next().cloned() else {
+            return Err(LightClientVerification("no trusted state".to_string()));
+        };
+
+        while trusted.height() > target.height() + 1 {
+            let previous = self.fetch(Some(trusted.height() - 1)).await?;
+            Self::check_hash_link(&previous, &trusted)?;
+            trusted = previous;
+        }
+        Self::check_hash_link(&target, &trusted)?;
+        self.trusted.insert(target.height(), target.clone());
+
+        Ok(target)
+    }
+
+    fn check_hash_link(previous: &LightBlock, next: &LightBlock) -> Result<(), CosmosClient> {
+        let linked = next
+            .signed_header
+            .header
+            .last_block_id
+            .is_some_and(|id| id.hash == previous.hash());
+        if linked {
+            Ok(())
+        } else {
+            Err(LightClientVerification(format!(
+                "header {} is not linked to header {}",
+                previous.height(),
+                next.height()
+            )))
+        }
+    }
+
+    fn check_validator_sets(block: &LightBlock) -> Result<(), CosmosClient> {
+        let header = &block.signed_header.header;
+        if block.validators.hash() != header.validators_hash
+            || block.next_validators.hash() != header.next_validators_hash
+        {
+            return Err(LightClientVerification(format!(
+                "validator sets do not match header {}",
+                block.height()
+            )));
+        }
+        Ok(())
+    }
+
+    async fn fetch(&self, height: Option<u64>) -> Result<LightBlock, CosmosClient> {
+        let signed_header = match height {
+            Some(height) => self.rpc.commit(Height::try_from(height)?).await?,
+            None => self.rpc.latest_commit().await?,
+        }
+        .signed_header;
+        let height = signed_header.header.height;
+
+        let validators = self.rpc.validators(height, Paging::All).await?.validators;
+        let next_validators = self
+            .rpc
+            .validators(height.increment(), Paging::All)
+            .await?
+            .validators;
+
+        Ok(LightBlock {
+            validators: validator::Set::with_proposer(
+                validators,
+                signed_header.header.proposer_address,
+            )?,
+            next_validators: validator::Set::without_proposer(next_validators),
+            signed_header,
+        })
+    }
+}
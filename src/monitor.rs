@@ -0,0 +1,350 @@
+use crate::client::{slashing, staking};
+use crate::error::CosmosClient;
+use cosmos_sdk_proto::cosmos::base::query::v1beta1::PageRequest;
+use cosmos_sdk_proto::cosmos::slashing::v1beta1::{Params, ValidatorSigningInfo};
+use cosmrs::crypto::PublicKey;
+use cosmrs::AccountId;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use tendermint::account;
+use tendermint::block::{CommitSig, Height};
+use tendermint_rpc::{Client, HttpClient};
+
+const DEC_PRECISION: u128 = 1_000_000_000_000_000_000;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValidatorStatus {
+    /// Bech32 consensus address (`...valcons...`)
+    pub cons_address: String,
+    pub height: u64,
+    /// Share of the signed blocks window signed by the validator, in percent.
+    pub uptime: f64,
+    pub missed_blocks: i64,
+    /// Consecutive blocks missed up to the latest scanned height.
+    pub missed_streak: u64,
+    /// How many more blocks can be missed in the window before jailing.
+    pub blocks_before_jail: i64,
+    /// Staking `jailed` flag, kept until the validator sends `MsgUnjail`.
+    pub jailed: bool,
+    pub tombstoned: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Alert {
+    MissedStreak {
+        cons_address: String,
+        streak: u64,
+    },
+    LowUptime {
+        cons_address: String,
+        uptime: f64,
+    },
+    NearJailing {
+        cons_address: String,
+        blocks_before_jail: i64,
+    },
+    Jailed {
+        cons_address: String,
+    },
+    Tombstoned {
+        cons_address: String,
+    },
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Thresholds {
+    pub missed_streak: u64,
+    pub min_uptime: f64,
+    pub blocks_before_jail: i64,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Thresholds {
+            missed_streak: 5,
+            min_uptime: 95.0,
+            blocks_before_jail: 500,
+        }
+    }
+}
+
+struct Watched {
+    cons_address: String,
+    address: account::Id,
+}
+
+pub struct ValidatorMonitor {
+    rpc: Arc<HttpClient>,
+    slashing: slashing::Module,
+    staking: staking::Module,
+    validators: Vec<Watched>,
+    thresholds: Thresholds,
+    scan_depth: u64,
+    on_alert: Box<dyn Fn(&Alert) + Send + Sync>,
+}
+
+impl ValidatorMonitor {
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - the url is invalid
+    /// - one of `cons_addresses` is not a bech32 address
+    pub fn new(url: &str, cons_addresses: &[&str]) -> Result<Self, CosmosClient> {
+        let rpc = Arc::new(HttpClient::new(url)?);
+        let validators = cons_addresses
+            .iter()
+            .map(|cons_address| {
+                Ok(Watched {
+                    cons_address: (*cons_address).to_string(),
+                    address: account::Id::try_from(AccountId::from_str(cons_address)?.to_bytes())?,
+                })
+            })
+            .collect::<Result<Vec<Watched>, CosmosClient>>()?;
+
+        Ok(ValidatorMonitor {
+            slashing: slashing::Module::new(rpc.clone()),
+            staking: staking::Module::new(rpc.clone()),
+            rpc,
+            validators,
+            thresholds: Thresholds::default(),
+            scan_depth: 50,
+            on_alert: Box::new(|_| {}),
+        })
+    }
+
+    #[must_use]
+    pub fn thresholds(mut self, thresholds: Thresholds) -> Self {
+        self.thresholds = thresholds;
+        self
+    }
+
+    /// Number of recent blocks whose commit signatures are scanned to
+    /// compute the missed streak.
+    #[must_use]
+    pub fn scan_depth(mut self, scan_depth: u64) -> Self {
+        self.scan_depth = scan_depth;
+        self
+    }
+
+    #[must_use]
+    pub fn on_alert(mut self, on_alert: impl Fn(&Alert) + Send + Sync + 'static) -> Self {
+        self.on_alert = Box::new(on_alert);
+        self
+    }
+
+    /// Compute the status of every watched validator and emit alerts for
+    /// the thresholds that are crossed.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - cosmos slashing or staking endpoints fail
+    /// - a watched validator has no signing info or staking validator
+    /// - the tendermint `commit` endpoint fails
+    pub async fn check(&self) -> Result<Vec<ValidatorStatus>, CosmosClient> {
+        let params = self
+            .slashing
+            .params()
+            .await?
+            .params
+            .ok_or(CosmosClient::RpcError("no slashing params".to_string()))?;
+
+        let latest = self.rpc.latest_commit().await?.signed_header.commit;
+        let mut commits = vec![latest.signatures.clone()];
+        let mut height = latest.height.value();
+        for _ in 1..self.scan_depth {
+            height -= 1;
+            if height == 0 {
+                break;
+            }
+            commits.push(
+                self.rpc
+                    .commit(Height::try_from(height)?)
+                    .await?
+                    .signed_header
+                    .commit
+                    .signatures,
+            );
+        }
+
+        let jailed = self.jailed().await?;
+        let mut statuses = vec![];
+        for validator in &self.validators {
+            let info = self
+                .slashing
+                .signing_info(validator.cons_address.as_str())
+                .await?
+                .val_signing_info
+                .ok_or_else(|| {
+                    CosmosClient::RpcError(format!(
+                        "no signing info for {}",
+                        validator.cons_address
+                    ))
+                })?;
+            let jailed = *jailed.get(&validator.address).ok_or_else(|| {
+                CosmosClient::RpcError(format!(
+                    "no staking validator for {}",
+                    validator.cons_address
+                ))
+            })?;
+            let status = status(
+                &validator.cons_address,
+                latest.height.value(),
+                &params,
+                &info,
+                missed_streak(&commits, validator.address),
+                jailed,
+            )?;
+
+            for alert in alerts(&status, &self.thresholds) {
+                (self.on_alert)(&alert);
+            }
+            statuses.push(status);
+        }
+
+        Ok(statuses)
+    }
+
+    /// `jailed` flag of every staking validator, by consensus address.
+    async fn jailed(&self) -> Result<HashMap<account::Id, bool>, CosmosClient> {
+        let mut jailed = HashMap::new();
+        let mut key = vec![];
+
+        loop {
+            let page = self
+                .staking
+                .validators(
+                    "",
+                    Some(PageRequest {
+                        key,
+                        ..PageRequest::default()
+                    }),
+                )
+                .await?;
+
+            for validator in page.validators {
+                if let Some(consensus_pubkey) = &validator.consensus_pubkey {
+                    let public_key: cosmrs::tendermint::PublicKey =
+                        PublicKey::try_from(consensus_pubkey)?.into();
+                    let address = cosmrs::tendermint::account::Id::from(public_key);
+                    jailed.insert(
+                        account::Id::try_from(address.as_bytes().to_vec())?,
+                        validator.jailed,
+                    );
+                }
+            }
+
+            key = page
+                .pagination
+                .map(|page| page.next_key)
+                .unwrap_or_default();
+            if key.is_empty() {
+                return Ok(jailed);
+            }
+        }
+    }
+}
+
+/// Count the consecutive commits, newest first, that `address` did not
+/// sign. A nil vote counts as signed, as in x/slashing.
+#[must_use]
+pub fn missed_streak(commits: &[Vec<CommitSig>], address: account::Id) -> u64 {
+    commits
+        .iter()
+        .take_while(|signatures| {
+            !signatures.iter().any(|signature| {
+                matches!(signature, CommitSig::BlockIdFlagCommit { validator_address, .. }
+                    | CommitSig::BlockIdFlagNil { validator_address, .. }
+                    if *validator_address == address)
+            })
+        })
+        .count() as u64
+}
+
+/// `jailed` is the staking flag of the validator : `jailed_until` only
+/// tells when it may unjail, and the missed blocks are reset on jailing.
+///
+/// # Errors
+///
+/// Will return `Err` if `min_signed_per_window` is not a valid decimal
+#[allow(clippy::cast_precision_loss)]
+pub fn status(
+    cons_address: &str,
+    height: u64,
+    params: &Params,
+    info: &ValidatorSigningInfo,
+    missed_streak: u64,
+    jailed: bool,
+) -> Result<ValidatorStatus, CosmosClient> {
+    let window = params.signed_blocks_window.max(1);
+    let min_signed = min_signed_blocks(window, &params.min_signed_per_window)?;
+    let max_missed = window - min_signed;
+
+    Ok(ValidatorStatus {
+        cons_address: cons_address.to_string(),
+        height,
+        uptime: (window - info.missed_blocks_counter).max(0) as f64 * 100.0 / window as f64,
+        missed_blocks: info.missed_blocks_counter,
+        missed_streak,
+        blocks_before_jail: (max_missed - info.missed_blocks_counter).max(0),
+        jailed,
+        tombstoned: info.tombstoned,
+    })
+}
+
+#[must_use]
+pub fn alerts(status: &ValidatorStatus, thresholds: &Thresholds) -> Vec<Alert> {
+    let cons_address = status.cons_address.clone();
+    let mut alerts = vec![];
+
+    if status.tombstoned {
+        alerts.push(Alert::Tombstoned { cons_address });
+        return alerts;
+    }
+    if status.jailed {
+        alerts.push(Alert::Jailed { cons_address });
+        return alerts;
+    }
+    if status.missed_streak >= thresholds.missed_streak {
+        alerts.push(Alert::MissedStreak {
+            cons_address: cons_address.clone(),
+            streak: status.missed_streak,
+        });
+    }
+    if status.uptime < thresholds.min_uptime {
+        alerts.push(Alert::LowUptime {
+            cons_address: cons_address.clone(),
+            uptime: status.uptime,
+        });
+    }
+    if status.blocks_before_jail <= thresholds.blocks_before_jail {
+        alerts.push(Alert::NearJailing {
+            cons_address,
+            blocks_before_jail: status.blocks_before_jail,
+        });
+    }
+
+    alerts
+}
+
+/// `window * min_signed_per_window` rounded like the sdk `RoundInt64`
+/// (half to even). The decimal is encoded as an 18 digits fixed point
+/// integer string.
+fn min_signed_blocks(window: i64, min_signed_per_window: &[u8]) -> Result<i64, CosmosClient> {
+    let dec = std::str::from_utf8(min_signed_per_window)?;
+    let dec = if let Some((int, frac)) = dec.split_once('.') {
+        int.parse::<u128>()? * DEC_PRECISION + format!("{frac:0<18}")[..18].parse::<u128>()?
+    } else {
+        dec.parse::<u128>()?
+    };
+
+    let product = u128::try_from(window)? * dec;
+    let mut blocks = product / DEC_PRECISION;
+    let remainder = (product % DEC_PRECISION) * 2;
+    if remainder > DEC_PRECISION || (remainder == DEC_PRECISION && blocks % 2 == 1) {
+        blocks += 1;
+    }
+
+    Ok(i64::try_from(blocks)?)
+}
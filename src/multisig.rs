@@ -0,0 +1,269 @@
+use crate::error::CosmosClient;
+use crate::signer::Signer;
+use crate::tx::amino::StdSignDoc;
+use crate::tx::offline::{SignedTx, UnsignedTx};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use cosmos_sdk_proto::cosmos::crypto::multisig::v1beta1::{CompactBitArray, MultiSignature};
+use cosmos_sdk_proto::cosmos::crypto::multisig::LegacyAminoPubKey;
+use cosmos_sdk_proto::cosmos::tx::signing::v1beta1::SignMode;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::{mode_info, AuthInfo, ModeInfo, SignerInfo, TxRaw};
+use cosmrs::crypto::secp256k1::{Signature, VerifyingKey};
+use cosmrs::crypto::PublicKey;
+use cosmrs::AccountId;
+use k256::ecdsa::signature::Verifier;
+use prost::Message;
+use prost_types::Any;
+use serde::{Deserialize, Serialize};
+use tendermint::crypto::default::Sha256;
+use tendermint::crypto::Sha256 as _;
+
+const MULTISIG_AMINO_PREFIX: [u8; 4] = [0x22, 0xc1, 0xf7, 0xe2];
+const SECP256K1_AMINO_PREFIX: [u8; 4] = [0xeb, 0x5a, 0xe9, 0x87];
+
+/// `threshold`-of-n `LegacyAminoPubKey` multisig of secp256k1 keys.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Multisig {
+    pub threshold: u32,
+    pub public_keys: Vec<PublicKey>,
+}
+
+/// Signature of one multisig member over the amino JSON sign doc of an
+/// [`UnsignedTx`], base64 encoded.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartialSignature {
+    /// Compressed secp256k1 public key of the member.
+    pub public_key: String,
+    pub signature: String,
+}
+
+impl Multisig {
+    /// Keys are kept in the given order, which is part of the address. The
+    /// SDK CLI `keys add --multisig` sorts them by address unless
+    /// `--nosort` is set, see [`Multisig::sorted`].
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - `threshold` is 0 or above the number of keys
+    /// - one of the keys is not a secp256k1 key
+    pub fn new(threshold: u32, public_keys: Vec<PublicKey>) -> Result<Self, CosmosClient> {
+        if threshold == 0 || usize::try_from(threshold)? > public_keys.len() {
+            return Err(CosmosClient::InvalidMultisig(format!(
+                "threshold {threshold} for {} keys",
+                public_keys.len()
+            )));
+        }
+        if let Some(key) = public_keys
+            .iter()
+            .find(|key| key.type_url() != PublicKey::SECP256K1_TYPE_URL)
+        {
+            return Err(CosmosClient::InvalidMultisig(format!(
+                "unsupported key type {}",
+                key.type_url()
+            )));
+        }
+
+        Ok(Multisig {
+            threshold,
+            public_keys,
+        })
+    }
+
+    /// Sort the keys by address like the SDK CLI does.
+    #[must_use]
+    pub fn sorted(mut self) -> Self {
+        self.public_keys.sort_by_key(|key| {
+            key.account_id("cosmos")
+                .map(|account| account.to_bytes())
+                .unwrap_or_default()
+        });
+        self
+    }
+
+    /// The multisig key as a protobuf `LegacyAminoPubKey`.
+    #[must_use]
+    pub fn to_any(&self) -> Any {
+        Any {
+            type_url: "/cosmos.crypto.multisig.LegacyAminoPubKey".to_string(),
+            value: LegacyAminoPubKey {
+                threshold: self.threshold,
+                public_keys: self.public_keys.iter().map(|key| (*key).into()).collect(),
+            }
+            .encode_to_vec(),
+        }
+    }
+
+    /// Amino binary encoding of the key, which the address is derived from.
+    #[must_use]
+    pub fn amino_bytes(&self) -> Vec<u8> {
+        let mut bytes = MULTISIG_AMINO_PREFIX.to_vec();
+        bytes.push(0x08);
+        prost::encoding::encode_varint(u64::from(self.threshold), &mut bytes);
+
+        for key in &self.public_keys {
+            let key = key.to_bytes();
+            let mut amino = SECP256K1_AMINO_PREFIX.to_vec();
+            prost::encoding::encode_varint(key.len() as u64, &mut amino);
+            amino.extend(key);
+
+            bytes.push(0x12);
+            prost::encoding::encode_varint(amino.len() as u64, &mut bytes);
+            bytes.extend(amino);
+        }
+
+        bytes
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the prefix is bad
+    pub fn account_id(&self, prefix: &str) -> Result<AccountId, CosmosClient> {
+        Ok(AccountId::new(
+            prefix,
+            &Sha256::digest(self.amino_bytes())[..20],
+        )?)
+    }
+
+    /// Combine the partial signatures of the members into the signed tx.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - a signature is not from a member, is duplicated or is invalid
+    /// - there are less signatures than the threshold
+    /// - `unsigned` bytes are not valid
+    /// - there is an encode error
+    pub fn combine(
+        &self,
+        unsigned: &UnsignedTx,
+        signatures: &[PartialSignature],
+    ) -> Result<SignedTx, CosmosClient> {
+        let sign_bytes = sign_doc(unsigned)?.to_bytes()?;
+        let mut signed = vec![None; self.public_keys.len()];
+
+        for partial in signatures {
+            let public_key = STANDARD.decode(&partial.public_key)?;
+            let index = self
+                .public_keys
+                .iter()
+                .position(|key| key.to_bytes() == public_key)
+                .ok_or(CosmosClient::InvalidMultisig(format!(
+                    "{} is not a member",
+                    partial.public_key
+                )))?;
+            if signed[index].is_some() {
+                return Err(CosmosClient::InvalidMultisig(format!(
+                    "duplicated signature of {}",
+                    partial.public_key
+                )));
+            }
+
+            let signature = STANDARD.decode(&partial.signature)?;
+            VerifyingKey::from_sec1_bytes(&public_key)
+                .and_then(|key| {
+                    key.verify(&sign_bytes, &Signature::try_from(signature.as_slice())?)
+                })
+                .map_err(|_| {
+                    CosmosClient::InvalidMultisig(format!(
+                        "invalid signature of {}",
+                        partial.public_key
+                    ))
+                })?;
+            signed[index] = Some(signature);
+        }
+
+        let count = signed.iter().flatten().count();
+        if count < usize::try_from(self.threshold)? {
+            return Err(CosmosClient::InvalidMultisig(format!(
+                "{count} signatures for a threshold of {}",
+                self.threshold
+            )));
+        }
+
+        let signer_info = SignerInfo {
+            public_key: Some(self.to_any()),
+            mode_info: Some(ModeInfo {
+                sum: Some(mode_info::Sum::Multi(mode_info::Multi {
+                    bitarray: Some(bit_array(
+                        &signed.iter().map(Option::is_some).collect::<Vec<bool>>(),
+                    )),
+                    mode_infos: vec![
+                        ModeInfo {
+                            sum: Some(mode_info::Sum::Single(mode_info::Single {
+                                mode: SignMode::LegacyAminoJson.into(),
+                            })),
+                        };
+                        count
+                    ],
+                })),
+            }),
+            sequence: unsigned.sequence,
+        };
+        let mut auth_info =
+            AuthInfo::decode(STANDARD.decode(&unsigned.auth_info_bytes)?.as_slice())?;
+        auth_info.signer_infos = vec![signer_info];
+
+        SignedTx::new(
+            &TxRaw {
+                body_bytes: STANDARD.decode(&unsigned.body_bytes)?,
+                auth_info_bytes: auth_info.encode_to_vec(),
+                signatures: vec![MultiSignature {
+                    signatures: signed.into_iter().flatten().collect(),
+                }
+                .encode_to_vec()],
+            }
+            .encode_to_vec(),
+        )
+    }
+}
+
+impl UnsignedTx {
+    /// Sign the tx as a member of the multisig sending it, without any
+    /// network access. Members sign in amino JSON mode, with the account
+    /// number and sequence of the multisig account.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - `body_bytes` or `auth_info_bytes` are not valid
+    /// - the body has no amino JSON encoding
+    /// - there is a sign error
+    pub fn sign_partial(&self, signer: &Signer) -> Result<PartialSignature, CosmosClient> {
+        let signature = signer.private_key.sign(&sign_doc(self)?.to_bytes()?)?;
+
+        Ok(PartialSignature {
+            public_key: STANDARD.encode(signer.public_key.to_bytes()),
+            signature: STANDARD.encode(signature.to_vec()),
+        })
+    }
+}
+
+fn sign_doc(unsigned: &UnsignedTx) -> Result<StdSignDoc, CosmosClient> {
+    let (body, fee) = unsigned.body_and_fee()?;
+
+    StdSignDoc::new(
+        &body,
+        &fee,
+        unsigned.chain_id.as_str(),
+        unsigned.account_number,
+        unsigned.sequence,
+    )
+}
+
+/// Cosmos SDK `CompactBitArray`, bits are stored from the most significant
+/// bit of each byte.
+#[must_use]
+pub fn bit_array(bits: &[bool]) -> CompactBitArray {
+    let mut elems = vec![0u8; bits.len().div_ceil(8)];
+    for (index, bit) in bits.iter().enumerate() {
+        if *bit {
+            elems[index / 8] |= 1 << (7 - index % 8);
+        }
+    }
+
+    CompactBitArray {
+        extra_bits_stored: u32::try_from(bits.len() % 8).unwrap_or_default(),
+        elems,
+    }
+}
@@ -0,0 +1,48 @@
+//! Protobuf messages that are not shipped by `cosmos-sdk-proto`.
+
+pub mod cosmos {
+    pub mod crypto {
+        pub mod hd {
+            pub mod v1;
+        }
+
+        pub mod keyring {
+            pub mod v1;
+        }
+    }
+
+    pub mod gov {
+        pub mod v1;
+    }
+}
+
+pub mod ethermint {
+    pub mod crypto {
+        pub mod v1 {
+            pub mod ethsecp256k1;
+        }
+    }
+
+    pub mod types {
+        pub mod v1;
+    }
+}
+
+pub mod feemarket {
+    #[allow(clippy::module_inception)]
+    pub mod feemarket {
+        pub mod v1;
+    }
+}
+
+pub mod injective {
+    pub mod types {
+        pub mod v1beta1;
+    }
+}
+
+pub mod osmosis {
+    pub mod txfees {
+        pub mod v1beta1;
+    }
+}
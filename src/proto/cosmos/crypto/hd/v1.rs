@@ -0,0 +1,13 @@
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Bip44Params {
+    #[prost(uint32, tag = "1")]
+    pub purpose: u32,
+    #[prost(uint32, tag = "2")]
+    pub coin_type: u32,
+    #[prost(uint32, tag = "3")]
+    pub account: u32,
+    #[prost(bool, tag = "4")]
+    pub change: bool,
+    #[prost(uint32, tag = "5")]
+    pub address_index: u32,
+}
@@ -0,0 +1,46 @@
+use prost_types::Any;
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Record {
+    #[prost(string, tag = "1")]
+    pub name: String,
+    #[prost(message, optional, tag = "2")]
+    pub pub_key: Option<Any>,
+    #[prost(oneof = "record::Item", tags = "3, 4, 5, 6")]
+    pub item: Option<record::Item>,
+}
+
+pub mod record {
+    use crate::proto::cosmos::crypto::hd::v1::Bip44Params;
+    use prost_types::Any;
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Local {
+        #[prost(message, optional, tag = "1")]
+        pub priv_key: Option<Any>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Ledger {
+        #[prost(message, optional, tag = "1")]
+        pub path: Option<Bip44Params>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Multi {}
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Offline {}
+
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Item {
+        #[prost(message, tag = "3")]
+        Local(Local),
+        #[prost(message, tag = "4")]
+        Ledger(Ledger),
+        #[prost(message, tag = "5")]
+        Multi(Multi),
+        #[prost(message, tag = "6")]
+        Offline(Offline),
+    }
+}
@@ -0,0 +1,99 @@
+use cosmos_sdk_proto::cosmos::base::v1beta1::Coin;
+use cosmos_sdk_proto::traits::TypeUrl;
+use cosmos_sdk_proto::Any;
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgSubmitProposal {
+    #[prost(message, repeated, tag = "1")]
+    pub messages: Vec<Any>,
+    #[prost(message, repeated, tag = "2")]
+    pub initial_deposit: Vec<Coin>,
+    #[prost(string, tag = "3")]
+    pub proposer: String,
+    #[prost(string, tag = "4")]
+    pub metadata: String,
+    #[prost(string, tag = "5")]
+    pub title: String,
+    #[prost(string, tag = "6")]
+    pub summary: String,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgExecLegacyContent {
+    #[prost(message, optional, tag = "1")]
+    pub content: Option<Any>,
+    #[prost(string, tag = "2")]
+    pub authority: String,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgVote {
+    #[prost(uint64, tag = "1")]
+    pub proposal_id: u64,
+    #[prost(string, tag = "2")]
+    pub voter: String,
+    #[prost(enumeration = "VoteOption", tag = "3")]
+    pub option: i32,
+    #[prost(string, tag = "4")]
+    pub metadata: String,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WeightedVoteOption {
+    #[prost(enumeration = "VoteOption", tag = "1")]
+    pub option: i32,
+    #[prost(string, tag = "2")]
+    pub weight: String,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgVoteWeighted {
+    #[prost(uint64, tag = "1")]
+    pub proposal_id: u64,
+    #[prost(string, tag = "2")]
+    pub voter: String,
+    #[prost(message, repeated, tag = "3")]
+    pub options: Vec<WeightedVoteOption>,
+    #[prost(string, tag = "4")]
+    pub metadata: String,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgDeposit {
+    #[prost(uint64, tag = "1")]
+    pub proposal_id: u64,
+    #[prost(string, tag = "2")]
+    pub depositor: String,
+    #[prost(message, repeated, tag = "3")]
+    pub amount: Vec<Coin>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum VoteOption {
+    Unspecified = 0,
+    Yes = 1,
+    Abstain = 2,
+    No = 3,
+    NoWithVeto = 4,
+}
+
+impl TypeUrl for MsgSubmitProposal {
+    const TYPE_URL: &'static str = "/cosmos.gov.v1.MsgSubmitProposal";
+}
+
+impl TypeUrl for MsgExecLegacyContent {
+    const TYPE_URL: &'static str = "/cosmos.gov.v1.MsgExecLegacyContent";
+}
+
+impl TypeUrl for MsgVote {
+    const TYPE_URL: &'static str = "/cosmos.gov.v1.MsgVote";
+}
+
+impl TypeUrl for MsgVoteWeighted {
+    const TYPE_URL: &'static str = "/cosmos.gov.v1.MsgVoteWeighted";
+}
+
+impl TypeUrl for MsgDeposit {
+    const TYPE_URL: &'static str = "/cosmos.gov.v1.MsgDeposit";
+}
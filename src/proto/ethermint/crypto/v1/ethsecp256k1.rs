@@ -0,0 +1,11 @@
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PubKey {
+    #[prost(bytes = "vec", tag = "1")]
+    pub key: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PrivKey {
+    #[prost(bytes = "vec", tag = "1")]
+    pub key: Vec<u8>,
+}
@@ -0,0 +1,19 @@
+use cosmos_sdk_proto::cosmos::auth::v1beta1::BaseAccount;
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EthAccount {
+    #[prost(message, optional, tag = "1")]
+    pub base_account: Option<BaseAccount>,
+    #[prost(string, tag = "2")]
+    pub code_hash: String,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExtensionOptionsWeb3Tx {
+    #[prost(uint64, tag = "1")]
+    pub typed_data_chain_id: u64,
+    #[prost(string, tag = "2")]
+    pub fee_payer: String,
+    #[prost(bytes = "vec", tag = "3")]
+    pub fee_payer_sig: Vec<u8>,
+}
@@ -0,0 +1,13 @@
+use cosmos_sdk_proto::cosmos::base::v1beta1::DecCoin;
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GasPriceRequest {
+    #[prost(string, tag = "1")]
+    pub denom: String,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GasPriceResponse {
+    #[prost(message, optional, tag = "1")]
+    pub price: Option<DecCoin>,
+}
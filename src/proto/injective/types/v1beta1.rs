@@ -0,0 +1,9 @@
+use cosmos_sdk_proto::cosmos::auth::v1beta1::BaseAccount;
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EthAccount {
+    #[prost(message, optional, tag = "1")]
+    pub base_account: Option<BaseAccount>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub code_hash: Vec<u8>,
+}
@@ -0,0 +1,8 @@
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryEipBaseFeeRequest {}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryEipBaseFeeResponse {
+    #[prost(string, tag = "1")]
+    pub base_fee: String,
+}
@@ -0,0 +1,156 @@
+use crate::client::auth;
+use crate::error::CosmosClient;
+use std::collections::BTreeSet;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+/// Hands out account sequences of one account to concurrent signing tasks.
+///
+/// Clones share the same state. Each tx takes a sequence with
+/// [`SequenceManager::acquire`] and reports the `CheckTx` result with
+/// [`SequenceManager::confirm`] or [`SequenceManager::fail`]. A failed
+/// sequence leaves a gap that is handed out again before any new one, and
+/// the txs already signed after it are expected to fail with a sequence
+/// mismatch and be retried.
+#[derive(Clone)]
+pub struct SequenceManager {
+    address: String,
+    account_number: u64,
+    auth: auth::Module,
+    state: Arc<Mutex<State>>,
+}
+
+struct State {
+    /// Lowest sequence that may be handed out.
+    next: u64,
+    /// Sequence after the last tx that passed `CheckTx`.
+    floor: u64,
+    in_flight: BTreeSet<u64>,
+}
+
+impl SequenceManager {
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - the account does not exist or is not a base, vesting or ethermint
+    ///   account
+    /// - cosmos `account` endpoint fails
+    pub async fn new(auth: auth::Module, address: &str) -> Result<Self, CosmosClient> {
+        let (account_number, sequence) = auth.account_sequence(address).await?;
+
+        Ok(SequenceManager::from_sequence(
+            auth,
+            address,
+            account_number,
+            sequence,
+        ))
+    }
+
+    /// Start from a known account number and sequence, without querying the
+    /// chain.
+    #[must_use]
+    pub fn from_sequence(
+        auth: auth::Module,
+        address: &str,
+        account_number: u64,
+        sequence: u64,
+    ) -> Self {
+        SequenceManager {
+            address: address.to_string(),
+            account_number,
+            auth,
+            state: Arc::new(Mutex::new(State {
+                next: sequence,
+                floor: sequence,
+                in_flight: BTreeSet::new(),
+            })),
+        }
+    }
+
+    #[must_use]
+    pub fn address(&self) -> &str {
+        self.address.as_str()
+    }
+
+    #[must_use]
+    pub fn account_number(&self) -> u64 {
+        self.account_number
+    }
+
+    /// Take the lowest free sequence and mark it in flight.
+    #[must_use]
+    pub fn acquire(&self) -> u64 {
+        let mut state = self.state();
+        let mut sequence = state.next;
+        while state.in_flight.contains(&sequence) {
+            sequence += 1;
+        }
+
+        state.in_flight.insert(sequence);
+        state.next = sequence + 1;
+        sequence
+    }
+
+    /// The tx signed with `sequence` passed `CheckTx`.
+    pub fn confirm(&self, sequence: u64) {
+        let mut state = self.state();
+        state.in_flight.remove(&sequence);
+        state.floor = state.floor.max(sequence + 1);
+        state.next = state.next.max(state.floor);
+    }
+
+    /// The tx signed with `sequence` was not accepted. `expected` is the
+    /// sequence the chain asked for on a sequence mismatch, if any.
+    pub fn fail(&self, sequence: u64, expected: Option<u64>) {
+        let mut state = self.state();
+        state.in_flight.remove(&sequence);
+
+        let next = match expected {
+            Some(expected) => expected,
+            None => state.next.min(sequence),
+        };
+        state.next = next.max(state.floor);
+    }
+
+    /// Sequences handed out and not yet confirmed or failed, in order.
+    #[must_use]
+    pub fn in_flight(&self) -> Vec<u64> {
+        self.state().in_flight.iter().copied().collect()
+    }
+
+    /// Sequence after the last confirmed tx, the one of the account in the
+    /// chain check state unless other txs passed `CheckTx` since. Txs are
+    /// simulated with it, as the chain rejects in flight sequences there.
+    #[must_use]
+    pub fn floor(&self) -> u64 {
+        self.state().floor
+    }
+
+    /// Sequence the next [`SequenceManager::acquire`] starts from.
+    #[must_use]
+    pub fn next_sequence(&self) -> u64 {
+        self.state().next
+    }
+
+    /// Reset the sequence to the one of the account on chain. Sequences still
+    /// in flight below it are dropped.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - the account does not exist or is not a base, vesting or ethermint
+    ///   account
+    /// - cosmos `account` endpoint fails
+    pub async fn resync(&self) -> Result<u64, CosmosClient> {
+        let (_, sequence) = self.auth.account_sequence(self.address.as_str()).await?;
+
+        let mut state = self.state();
+        state.in_flight.retain(|in_flight| *in_flight >= sequence);
+        state.next = sequence;
+        state.floor = sequence;
+        Ok(sequence)
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
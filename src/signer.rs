@@ -1,40 +1,292 @@
+pub mod armor;
+pub mod hd;
+pub mod keystore;
+pub mod remote;
+
 use crate::error::CosmosClient;
-use cosmrs::bip32::{Language, Mnemonic, XPrv};
-use cosmrs::crypto::secp256k1::SigningKey;
+use crate::fee::GasPrice;
+use crate::proto::ethermint::crypto::v1::ethsecp256k1;
+use crate::signer::armor::{encrypt_armor_priv_key, unarmor_decrypt_priv_key, BCRYPT_COST};
+use crate::signer::hd::HdWallet;
+use crate::signer::keystore::Keystore;
+use async_trait::async_trait;
+use cosmrs::bip32::{Language, Mnemonic};
+use cosmrs::crypto::secp256k1::{Signature, SigningKey, VerifyingKey};
 use cosmrs::crypto::PublicKey;
+use cosmrs::tx::SignerPublicKey;
 use cosmrs::AccountId;
 use hex::decode;
+use k256::ecdsa::signature::hazmat::{PrehashSigner, PrehashVerifier};
+use k256::ecdsa::signature::{Error, Keypair, Signer as _, Verifier};
+use prost::Message;
+use prost_types::Any;
 use rand_core::OsRng;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+
+/// Key signing txs, in process like [`Signer`] or held by an external
+/// service like [`remote::RemoteSigner`].
+#[async_trait]
+pub trait TxSigner: Send + Sync {
+    fn public_key(&self) -> PublicKey;
+
+    fn address(&self) -> AccountId;
+
+    /// Public key as set in the signer infos of a tx, a Cosmos SDK
+    /// secp256k1 key by default.
+    fn signer_public_key(&self) -> SignerPublicKey {
+        SignerPublicKey::Single(self.public_key())
+    }
+
+    /// secp256k1 signature of the hash of `bytes`, as the 64 bytes
+    /// `r || s` with a low `s`. The hash is sha256, or keccak256 for an
+    /// ethermint key.
+    async fn sign_bytes(&self, bytes: &[u8]) -> Result<Vec<u8>, CosmosClient>;
+}
+
+/// Type of a secp256k1 key, which sets its derivation path, address and
+/// how it signs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyType {
+    /// Cosmos SDK key : coin type 118, `ripemd160(sha256(key))` address,
+    /// signs the sha256 hash.
+    #[default]
+    Secp256k1,
+    /// Ethermint key used by Evmos, Cronos and other EVM chains : coin type
+    /// 60, Ethereum `keccak256` address, signs the keccak256 hash.
+    EthSecp256k1,
+    /// Injective flavor of [`KeyType::EthSecp256k1`], only its public key
+    /// type differs.
+    InjectiveEthSecp256k1,
+}
+
+impl KeyType {
+    #[must_use]
+    pub fn default_derivation(&self) -> &'static str {
+        match self {
+            KeyType::Secp256k1 => "m/44'/118'/0'/0/0",
+            KeyType::EthSecp256k1 | KeyType::InjectiveEthSecp256k1 => "m/44'/60'/0'/0/0",
+        }
+    }
+
+    /// Name of the key algorithm in the Cosmos SDK keyring and armor.
+    #[must_use]
+    pub fn algo(&self) -> &'static str {
+        match self {
+            KeyType::Secp256k1 => "secp256k1",
+            KeyType::EthSecp256k1 | KeyType::InjectiveEthSecp256k1 => "eth_secp256k1",
+        }
+    }
+
+    /// BIP44 coin type of the derivation path.
+    #[must_use]
+    pub fn coin_type(&self) -> u32 {
+        match self {
+            KeyType::Secp256k1 => 118,
+            KeyType::EthSecp256k1 | KeyType::InjectiveEthSecp256k1 => 60,
+        }
+    }
+
+    /// BIP44 path `m/44'/<coin type>'/<account>'/0/<index>`, as `--account`
+    /// and `--index` of `keys add`.
+    #[must_use]
+    pub fn derivation(&self, account: u32, index: u32) -> String {
+        format!("m/44'/{}'/{account}'/0/{index}", self.coin_type())
+    }
+
+    /// Key type of a public key protobuf type.
+    #[must_use]
+    pub fn from_type_url(type_url: &str) -> Option<KeyType> {
+        [
+            KeyType::Secp256k1,
+            KeyType::EthSecp256k1,
+            KeyType::InjectiveEthSecp256k1,
+        ]
+        .into_iter()
+        .find(|key_type| key_type.type_url() == type_url)
+    }
+
+    /// Protobuf type of the public key in the signer infos.
+    #[must_use]
+    pub fn type_url(&self) -> &'static str {
+        match self {
+            KeyType::Secp256k1 => PublicKey::SECP256K1_TYPE_URL,
+            KeyType::EthSecp256k1 => "/ethermint.crypto.v1.ethsecp256k1.PubKey",
+            KeyType::InjectiveEthSecp256k1 => "/injective.crypto.v1beta1.ethsecp256k1.PubKey",
+        }
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - `public_key` is not a secp256k1 key
+    /// - if the prefix is bad
+    pub fn address(&self, public_key: &PublicKey, prefix: &str) -> Result<AccountId, CosmosClient> {
+        match self {
+            KeyType::Secp256k1 => Ok(public_key.account_id(prefix)?),
+            KeyType::EthSecp256k1 | KeyType::InjectiveEthSecp256k1 => {
+                let uncompressed =
+                    VerifyingKey::from_sec1_bytes(&public_key.to_bytes())?.to_encoded_point(false);
+                let hash = Keccak256::digest(&uncompressed.as_bytes()[1..]);
+
+                Ok(AccountId::new(prefix, &hash[12..])?)
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn signer_public_key(&self, public_key: PublicKey) -> SignerPublicKey {
+        match self {
+            KeyType::Secp256k1 => SignerPublicKey::Single(public_key),
+            KeyType::EthSecp256k1 | KeyType::InjectiveEthSecp256k1 => SignerPublicKey::Any(Any {
+                type_url: self.type_url().to_string(),
+                value: ethsecp256k1::PubKey {
+                    key: public_key.to_bytes(),
+                }
+                .encode_to_vec(),
+            }),
+        }
+    }
+
+    /// Check `signature` of `msg` by `public_key`, over the hash this key
+    /// type signs.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if `public_key` or `signature` is invalid, or
+    /// `signature` does not match
+    pub fn verify(
+        &self,
+        public_key: &PublicKey,
+        msg: &[u8],
+        signature: &[u8],
+    ) -> Result<(), Error> {
+        let key = VerifyingKey::from_sec1_bytes(&public_key.to_bytes())?;
+        let signature = Signature::try_from(signature)?;
+
+        match self {
+            KeyType::Secp256k1 => key.verify(msg, &signature),
+            KeyType::EthSecp256k1 | KeyType::InjectiveEthSecp256k1 => {
+                key.verify_prehash(&Keccak256::digest(msg), &signature)
+            }
+        }
+    }
+
+    fn signing_key(self, key: k256::ecdsa::SigningKey) -> SigningKey {
+        match self {
+            KeyType::Secp256k1 => SigningKey::new(Box::new(key)),
+            KeyType::EthSecp256k1 | KeyType::InjectiveEthSecp256k1 => {
+                SigningKey::new(Box::new(EthSigningKey(key)))
+            }
+        }
+    }
+}
+
+/// Ethermint private key, signing the keccak256 hash of the message.
+struct EthSigningKey(k256::ecdsa::SigningKey);
+
+impl k256::ecdsa::signature::Signer<Signature> for EthSigningKey {
+    fn try_sign(&self, msg: &[u8]) -> Result<Signature, Error> {
+        self.0.sign_prehash(&Keccak256::digest(msg))
+    }
+}
+
+impl Keypair for EthSigningKey {
+    type VerifyingKey = VerifyingKey;
+
+    fn verifying_key(&self) -> VerifyingKey {
+        *self.0.verifying_key()
+    }
+}
+
+/// secp256k1 private key of a [`Signer`], hashing what it signs as its
+/// [`KeyType`] does.
+#[derive(Clone)]
+pub struct PrivateKey {
+    key_type: KeyType,
+    secret: k256::ecdsa::SigningKey,
+}
+
+impl PrivateKey {
+    #[must_use]
+    pub fn new(key_type: KeyType, secret: k256::ecdsa::SigningKey) -> Self {
+        PrivateKey { key_type, secret }
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the message cannot be signed
+    pub fn sign(&self, msg: &[u8]) -> Result<Signature, Error> {
+        match self.key_type {
+            KeyType::Secp256k1 => self.secret.try_sign(msg),
+            KeyType::EthSecp256k1 | KeyType::InjectiveEthSecp256k1 => {
+                self.secret.sign_prehash(&Keccak256::digest(msg))
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey::from(*self.secret.verifying_key())
+    }
+
+    /// The key as a cosmrs [`SigningKey`], e.g. for [`crate::tx::sign_body`].
+    #[must_use]
+    pub fn signing_key(&self) -> SigningKey {
+        self.key_type.signing_key(self.secret.clone())
+    }
+}
 
 pub struct Signer {
     pub mnemonic: Option<String>,
-    pub denom: String,
     pub public_address: AccountId,
-    pub private_key: SigningKey,
+    pub private_key: PrivateKey,
     pub public_key: PublicKey,
+    pub key_type: KeyType,
     pub gas_adjustment_percent: u8,
-    pub gas_price: u128,
+    pub gas_price: GasPrice,
+    pub(crate) secret: k256::ecdsa::SigningKey,
 }
 
 impl Signer {
     fn load_from_mnemonic(
+        key_type: KeyType,
         phrase: &str,
-        prefix: &str,
+        passphrase: &str,
         derivation: Option<&str>,
-    ) -> Result<(SigningKey, PublicKey, AccountId), CosmosClient> {
+    ) -> Result<k256::ecdsa::SigningKey, CosmosClient> {
         let derivation = if let Some(derivation) = derivation {
             derivation
         } else {
-            "m/44'/118'/0'/0/0"
+            key_type.default_derivation()
         };
 
-        let mnemonic = Mnemonic::new(phrase, Language::English)?;
-        let pri = XPrv::derive_from_path(&mnemonic.to_seed(""), &derivation.parse()?)?;
-        let private_key = SigningKey::from(pri);
+        HdWallet::new(key_type, phrase, passphrase)?.derive(derivation)
+    }
+
+    pub(crate) fn new(
+        key_type: KeyType,
+        secret: k256::ecdsa::SigningKey,
+        mnemonic: Option<String>,
+        prefix: &str,
+        gas_adjustment_percent: u8,
+        gas_price: GasPrice,
+    ) -> Result<Self, CosmosClient> {
+        let private_key = PrivateKey::new(key_type, secret.clone());
         let public_key = private_key.public_key();
-        let public_address = public_key.account_id(prefix)?;
+        let public_address = key_type.address(&public_key, prefix)?;
 
-        Ok((private_key, public_key, public_address))
+        Ok(Signer {
+            mnemonic,
+            public_address,
+            private_key,
+            public_key,
+            key_type,
+            gas_adjustment_percent,
+            gas_price,
+            secret,
+        })
     }
 
     /// # Errors
@@ -44,24 +296,44 @@ impl Signer {
     /// - if the prefix is bad
     pub fn generate(
         prefix: &str,
-        denom: &str,
         derivation: Option<&str>,
         gas_adjustment_percent: u8,
-        gas_price: u128,
+        gas_price: GasPrice,
+    ) -> Result<Self, CosmosClient> {
+        Signer::generate_with_key_type(
+            KeyType::Secp256k1,
+            prefix,
+            derivation,
+            gas_adjustment_percent,
+            gas_price,
+        )
+    }
+
+    /// Generate a key of `key_type`, derived on its default path when
+    /// `derivation` is `None`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - we cannot parse the derivation
+    /// - if the prefix is bad
+    pub fn generate_with_key_type(
+        key_type: KeyType,
+        prefix: &str,
+        derivation: Option<&str>,
+        gas_adjustment_percent: u8,
+        gas_price: GasPrice,
     ) -> Result<Self, CosmosClient> {
         let mnemonic = Mnemonic::random(OsRng, Language::English);
-        let (private_key, public_key, public_address) =
-            Signer::load_from_mnemonic(mnemonic.phrase(), prefix, derivation)?;
 
-        Ok(Signer {
-            mnemonic: Some(mnemonic.phrase().to_string()),
-            public_address,
+        Signer::new(
+            key_type,
+            Signer::load_from_mnemonic(key_type, mnemonic.phrase(), "", derivation)?,
+            Some(mnemonic.phrase().to_string()),
+            prefix,
             gas_adjustment_percent,
             gas_price,
-            denom: denom.to_string(),
-            private_key,
-            public_key,
-        })
+        )
     }
 
     /// # Errors
@@ -73,23 +345,38 @@ impl Signer {
     pub fn from_pkey(
         private_key: &str,
         prefix: &str,
-        denom: &str,
         gas_adjustment_percent: u8,
-        gas_price: u128,
+        gas_price: GasPrice,
     ) -> Result<Self, CosmosClient> {
-        let private_key = SigningKey::from_slice(decode(private_key)?.as_slice())?;
-        let public_key = private_key.public_key();
-        let public_address = public_key.account_id(prefix)?;
+        Signer::from_pkey_with_key_type(
+            KeyType::Secp256k1,
+            private_key,
+            prefix,
+            gas_adjustment_percent,
+            gas_price,
+        )
+    }
 
-        Ok(Signer {
-            mnemonic: None,
-            public_address,
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - the private key is invalid
+    /// - if the prefix is bad
+    pub fn from_pkey_with_key_type(
+        key_type: KeyType,
+        private_key: &str,
+        prefix: &str,
+        gas_adjustment_percent: u8,
+        gas_price: GasPrice,
+    ) -> Result<Self, CosmosClient> {
+        Signer::new(
+            key_type,
+            k256::ecdsa::SigningKey::from_slice(&decode(private_key)?)?,
+            None,
+            prefix,
             gas_adjustment_percent,
             gas_price,
-            denom: denom.to_string(),
-            private_key,
-            public_key,
-        })
+        )
     }
 
     /// # Errors
@@ -101,22 +388,181 @@ impl Signer {
     pub fn from_mnemonic(
         phrase: &str,
         prefix: &str,
-        denom: &str,
         derivation: Option<&str>,
         gas_adjustment_percent: u8,
-        gas_price: u128,
+        gas_price: GasPrice,
     ) -> Result<Self, CosmosClient> {
-        let (private_key, public_key, public_address) =
-            Signer::load_from_mnemonic(phrase, prefix, derivation)?;
+        Signer::from_mnemonic_with_key_type(
+            KeyType::Secp256k1,
+            phrase,
+            prefix,
+            derivation,
+            gas_adjustment_percent,
+            gas_price,
+        )
+    }
 
-        Ok(Signer {
-            mnemonic: Some(phrase.to_string()),
-            public_address,
+    /// Load a key of `key_type`, derived on its default path when
+    /// `derivation` is `None`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - mnemonic is invalid
+    /// - we cannot parse the derivation
+    /// - if the prefix is bad
+    pub fn from_mnemonic_with_key_type(
+        key_type: KeyType,
+        phrase: &str,
+        prefix: &str,
+        derivation: Option<&str>,
+        gas_adjustment_percent: u8,
+        gas_price: GasPrice,
+    ) -> Result<Self, CosmosClient> {
+        Signer::from_mnemonic_with_passphrase(
+            key_type,
+            phrase,
+            "",
+            prefix,
+            derivation,
             gas_adjustment_percent,
             gas_price,
-            denom: denom.to_string(),
-            private_key,
-            public_key,
-        })
+        )
+    }
+
+    /// Load a key of `key_type` from a mnemonic protected by a BIP39
+    /// `passphrase`, see [`KeyType::derivation`] for the path of an account
+    /// and index.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - mnemonic is invalid
+    /// - we cannot parse the derivation
+    /// - if the prefix is bad
+    pub fn from_mnemonic_with_passphrase(
+        key_type: KeyType,
+        phrase: &str,
+        passphrase: &str,
+        prefix: &str,
+        derivation: Option<&str>,
+        gas_adjustment_percent: u8,
+        gas_price: GasPrice,
+    ) -> Result<Self, CosmosClient> {
+        Signer::new(
+            key_type,
+            Signer::load_from_mnemonic(key_type, phrase, passphrase, derivation)?,
+            Some(phrase.to_string()),
+            prefix,
+            gas_adjustment_percent,
+            gas_price,
+        )
+    }
+
+    /// Import a key exported by `keys export` of a Cosmos SDK chain binary,
+    /// its type is read from the armor.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - the armor is invalid
+    /// - the passphrase is wrong
+    /// - if the prefix is bad
+    pub fn from_armor(
+        armored: &str,
+        passphrase: &str,
+        prefix: &str,
+        gas_adjustment_percent: u8,
+        gas_price: GasPrice,
+    ) -> Result<Self, CosmosClient> {
+        let (secret, key_type) = unarmor_decrypt_priv_key(armored, passphrase)?;
+
+        Signer::new(
+            key_type,
+            secret,
+            None,
+            prefix,
+            gas_adjustment_percent,
+            gas_price,
+        )
+    }
+
+    /// Export the key encrypted with `passphrase`, in the armor format of
+    /// `keys export` that `keys import` reads.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - the encryption fails
+    pub fn to_armor(&self, passphrase: &str) -> Result<String, CosmosClient> {
+        encrypt_armor_priv_key(&self.secret, self.key_type, passphrase)
+    }
+
+    /// Load a key from a [`Keystore`] JSON.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - the keystore is invalid
+    /// - the passphrase is wrong
+    /// - if the prefix is bad
+    pub fn from_keystore(
+        json: &str,
+        passphrase: &str,
+        prefix: &str,
+        gas_adjustment_percent: u8,
+        gas_price: GasPrice,
+    ) -> Result<Self, CosmosClient> {
+        let keystore: Keystore = serde_json::from_str(json)?;
+
+        Signer::new(
+            keystore.key_type,
+            keystore.decrypt(passphrase)?,
+            None,
+            prefix,
+            gas_adjustment_percent,
+            gas_price,
+        )
+    }
+
+    /// Export the key as a [`Keystore`] JSON encrypted with `passphrase`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - the encryption fails
+    pub fn to_keystore(&self, passphrase: &str) -> Result<String, CosmosClient> {
+        Ok(serde_json::to_string_pretty(&Keystore::encrypt(
+            &self.secret,
+            self.key_type,
+            &self.public_address,
+            passphrase,
+            BCRYPT_COST,
+        )?)?)
+    }
+
+    /// Public key as set in the signer infos of a tx.
+    #[must_use]
+    pub fn signer_public_key(&self) -> SignerPublicKey {
+        self.key_type.signer_public_key(self.public_key)
+    }
+}
+
+#[async_trait]
+impl TxSigner for Signer {
+    fn public_key(&self) -> PublicKey {
+        self.public_key
+    }
+
+    fn address(&self) -> AccountId {
+        self.public_address.clone()
+    }
+
+    fn signer_public_key(&self) -> SignerPublicKey {
+        Signer::signer_public_key(self)
+    }
+
+    async fn sign_bytes(&self, bytes: &[u8]) -> Result<Vec<u8>, CosmosClient> {
+        Ok(self.private_key.sign(bytes)?.to_vec())
     }
 }
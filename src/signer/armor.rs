@@ -0,0 +1,283 @@
+use crate::error::CosmosClient;
+use crate::signer::KeyType;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use bcrypt::Version;
+use crypto_secretbox::aead::{Aead, KeyInit};
+use crypto_secretbox::XSalsa20Poly1305;
+use rand_core::{OsRng, RngCore};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use zeroize::Zeroizing;
+
+/// Block type of a private key exported by `keys export`.
+pub const PRIVATE_KEY_BLOCK_TYPE: &str = "TENDERMINT PRIVATE KEY";
+
+/// bcrypt cost used by the Cosmos SDK to derive the cipher key.
+pub const BCRYPT_COST: u32 = 12;
+
+const NONCE_SIZE: usize = 24;
+const LINE_LENGTH: usize = 64;
+
+/// `OpenPGP` ASCII armor block, as written by the Cosmos SDK.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Armor {
+    pub block_type: String,
+    pub headers: BTreeMap<String, String>,
+    pub data: Vec<u8>,
+}
+
+impl Armor {
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - the begin or end line is missing
+    /// - the body is not base64
+    /// - the checksum does not match the body
+    pub fn decode(armored: &str) -> Result<Self, CosmosClient> {
+        let mut lines = armored.lines().map(str::trim);
+        let block_type = lines
+            .find_map(|line| {
+                line.strip_prefix("-----BEGIN ")
+                    .and_then(|line| line.strip_suffix("-----"))
+            })
+            .ok_or_else(|| CosmosClient::InvalidArmor("no begin line".to_string()))?
+            .to_string();
+        let end = format!("-----END {block_type}-----");
+
+        let mut headers = BTreeMap::new();
+        let mut body = String::new();
+        let mut checksum = None;
+        let mut in_headers = true;
+        let mut ended = false;
+
+        for line in lines {
+            if line == end {
+                ended = true;
+                break;
+            }
+            if in_headers {
+                if let Some((key, value)) = line.split_once(": ") {
+                    headers.insert(key.to_string(), value.to_string());
+                    continue;
+                }
+                in_headers = false;
+            }
+            if let Some(crc) = line.strip_prefix('=') {
+                checksum = Some(crc.to_string());
+            } else {
+                body.push_str(line);
+            }
+        }
+        if !ended {
+            return Err(CosmosClient::InvalidArmor(format!("no end line {end}")));
+        }
+
+        let data = STANDARD.decode(body)?;
+        if let Some(checksum) = checksum {
+            if STANDARD.decode(checksum)? != crc24(&data).to_be_bytes()[1..] {
+                return Err(CosmosClient::InvalidArmor("bad checksum".to_string()));
+            }
+        }
+
+        Ok(Armor {
+            block_type,
+            headers,
+            data,
+        })
+    }
+
+    #[must_use]
+    pub fn encode(&self) -> String {
+        let mut armored = format!("-----BEGIN {}-----\n", self.block_type);
+        for (key, value) in &self.headers {
+            armored.push_str(key);
+            armored.push_str(": ");
+            armored.push_str(value);
+            armored.push('\n');
+        }
+        armored.push('\n');
+
+        let body = STANDARD.encode(&self.data);
+        for line in body.as_bytes().chunks(LINE_LENGTH) {
+            armored.push_str(&String::from_utf8_lossy(line));
+            armored.push('\n');
+        }
+
+        format!(
+            "{armored}={}\n-----END {}-----",
+            STANDARD.encode(&crc24(&self.data).to_be_bytes()[1..]),
+            self.block_type
+        )
+    }
+}
+
+/// Encrypt `private_key` with `passphrase` the way `keys export` does : the
+/// amino encoded key is sealed with xsalsa20-poly1305, under the sha256 of
+/// the bcrypt hash of the passphrase.
+///
+/// # Errors
+///
+/// Will return `Err` if :
+/// - bcrypt fails
+/// - the encryption fails
+pub fn encrypt_armor_priv_key(
+    private_key: &k256::ecdsa::SigningKey,
+    key_type: KeyType,
+    passphrase: &str,
+) -> Result<String, CosmosClient> {
+    let mut salt = [0u8; 16];
+    OsRng.fill_bytes(&mut salt);
+
+    let mut amino = Zeroizing::new(amino_prefix(key_type).to_vec());
+    amino.push(32);
+    amino.extend_from_slice(&private_key.to_bytes());
+
+    Ok(Armor {
+        block_type: PRIVATE_KEY_BLOCK_TYPE.to_string(),
+        headers: BTreeMap::from([
+            ("kdf".to_string(), "bcrypt".to_string()),
+            ("salt".to_string(), hex::encode_upper(salt)),
+            ("type".to_string(), key_type.algo().to_string()),
+        ]),
+        data: seal(&bcrypt_key(passphrase, salt, BCRYPT_COST)?, &amino)?,
+    }
+    .encode())
+}
+
+/// Decrypt a private key exported by `keys export`, along with its type.
+///
+/// # Errors
+///
+/// Will return `Err` if :
+/// - the armor is not a bcrypt encrypted private key
+/// - the passphrase is wrong
+/// - the key is not an amino encoded secp256k1 key
+pub fn unarmor_decrypt_priv_key(
+    armored: &str,
+    passphrase: &str,
+) -> Result<(k256::ecdsa::SigningKey, KeyType), CosmosClient> {
+    let armor = Armor::decode(armored)?;
+    if armor.block_type != PRIVATE_KEY_BLOCK_TYPE {
+        return Err(CosmosClient::InvalidArmor(format!(
+            "unrecognized armor type {}",
+            armor.block_type
+        )));
+    }
+    match armor.headers.get("kdf").map(String::as_str) {
+        Some("bcrypt") => {}
+        kdf => {
+            return Err(CosmosClient::InvalidArmor(format!(
+                "unrecognized KDF type {kdf:?}"
+            )))
+        }
+    }
+    let salt: [u8; 16] = hex::decode(
+        armor
+            .headers
+            .get("salt")
+            .ok_or_else(|| CosmosClient::InvalidArmor("missing salt".to_string()))?,
+    )?
+    .try_into()
+    .map_err(|_| CosmosClient::InvalidArmor("salt is not 16 bytes".to_string()))?;
+
+    let amino = open(&bcrypt_key(passphrase, salt, BCRYPT_COST)?, &armor.data)?;
+    let key_type = [
+        KeyType::Secp256k1,
+        KeyType::EthSecp256k1,
+        KeyType::InjectiveEthSecp256k1,
+    ]
+    .into_iter()
+    .find(|key_type| amino.starts_with(&amino_prefix(*key_type)))
+    .ok_or_else(|| CosmosClient::InvalidArmor("unknown amino key type".to_string()))?;
+
+    // keys exported before the type header are secp256k1
+    let algo = armor
+        .headers
+        .get("type")
+        .map_or("secp256k1", String::as_str);
+    if algo != key_type.algo() {
+        return Err(CosmosClient::InvalidArmor(format!(
+            "type {algo} does not match the {} key",
+            key_type.algo()
+        )));
+    }
+
+    match &amino[4..] {
+        [32, key @ ..] if key.len() == 32 => {
+            Ok((k256::ecdsa::SigningKey::from_slice(key)?, key_type))
+        }
+        _ => Err(CosmosClient::InvalidArmor(
+            "private key is not 32 bytes".to_string(),
+        )),
+    }
+}
+
+/// xsalsa20-poly1305 key derived from `passphrase` : sha256 of its bcrypt
+/// hash string.
+pub(crate) fn bcrypt_key(
+    passphrase: &str,
+    salt: [u8; 16],
+    cost: u32,
+) -> Result<Zeroizing<[u8; 32]>, CosmosClient> {
+    let hash = Zeroizing::new(
+        bcrypt::hash_with_salt(passphrase, cost, salt)?.format_for_version(Version::TwoA),
+    );
+
+    Ok(Zeroizing::new(Sha256::digest(hash.as_bytes()).into()))
+}
+
+/// `NaCl` secretbox of `plaintext`, prefixed by its random nonce.
+pub(crate) fn seal(key: &Zeroizing<[u8; 32]>, plaintext: &[u8]) -> Result<Vec<u8>, CosmosClient> {
+    let mut nonce = [0u8; NONCE_SIZE];
+    OsRng.fill_bytes(&mut nonce);
+
+    let sealed = XSalsa20Poly1305::new((&**key).into())
+        .encrypt(&nonce.into(), plaintext)
+        .map_err(|_| CosmosClient::KeyEncryption)?;
+
+    Ok([nonce.as_slice(), &sealed].concat())
+}
+
+/// Open a box of [`seal`].
+pub(crate) fn open(
+    key: &Zeroizing<[u8; 32]>,
+    sealed: &[u8],
+) -> Result<Zeroizing<Vec<u8>>, CosmosClient> {
+    if sealed.len() < NONCE_SIZE {
+        return Err(CosmosClient::KeyDecryption);
+    }
+    let (nonce, sealed) = sealed.split_at(NONCE_SIZE);
+
+    XSalsa20Poly1305::new((&**key).into())
+        .decrypt(nonce.into(), sealed)
+        .map(Zeroizing::new)
+        .map_err(|_| CosmosClient::KeyDecryption)
+}
+
+/// Amino prefix of the registered private key type.
+fn amino_prefix(key_type: KeyType) -> [u8; 4] {
+    match key_type {
+        // tendermint/PrivKeySecp256k1
+        KeyType::Secp256k1 => [0xe1, 0xb0, 0xf7, 0x9b],
+        // ethermint/PrivKeyEthSecp256k1
+        KeyType::EthSecp256k1 => [0xfc, 0xd2, 0xef, 0xcc],
+        // injective/PrivKeyEthSecp256k1
+        KeyType::InjectiveEthSecp256k1 => [0x77, 0x3b, 0x38, 0x86],
+    }
+}
+
+/// CRC-24 checksum of `OpenPGP` armor.
+pub(crate) fn crc24(data: &[u8]) -> u32 {
+    let mut crc = 0x00B7_04CE_u32;
+    for byte in data {
+        crc ^= u32::from(*byte) << 16;
+        for _ in 0..8 {
+            crc <<= 1;
+            if crc & 0x0100_0000 != 0 {
+                crc ^= 0x0186_4CFB;
+            }
+        }
+    }
+    crc & 0x00FF_FFFF
+}
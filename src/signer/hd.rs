@@ -0,0 +1,139 @@
+use crate::error::CosmosClient;
+use crate::fee::GasPrice;
+use crate::signer::{KeyType, Signer};
+use cosmos_sdk_proto::cosmos::base::v1beta1::Coin;
+use cosmrs::bip32::{Language, Mnemonic, Seed, XPrv};
+use cosmrs::AccountId;
+use prost_types::Any;
+use std::future::Future;
+use zeroize::Zeroizing;
+
+/// Number of unused addresses in a row that ends a discovery, as set by
+/// BIP44.
+pub const DEFAULT_GAP_LIMIT: u32 = 20;
+
+/// HD wallet of a BIP39 mnemonic and its optional passphrase, deriving the
+/// keys of `key_type` on the BIP44 path
+/// `m/44'/<coin type>'/<account>'/0/<index>`.
+pub struct HdWallet {
+    pub key_type: KeyType,
+    phrase: Zeroizing<String>,
+    seed: Seed,
+}
+
+/// Address with history found by `Rpc::discover_accounts`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiscoveredAccount {
+    pub index: u32,
+    pub address: AccountId,
+    /// Account returned by the auth module, `None` if the address only
+    /// holds balances.
+    pub account: Option<Any>,
+    pub balances: Vec<Coin>,
+}
+
+impl HdWallet {
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - mnemonic is invalid
+    pub fn new(key_type: KeyType, phrase: &str, passphrase: &str) -> Result<Self, CosmosClient> {
+        let mnemonic = Mnemonic::new(phrase, Language::English)?;
+
+        Ok(HdWallet {
+            key_type,
+            phrase: Zeroizing::new(phrase.to_string()),
+            seed: mnemonic.to_seed(passphrase),
+        })
+    }
+
+    /// Private key at `derivation`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - we cannot parse the derivation
+    pub fn derive(&self, derivation: &str) -> Result<k256::ecdsa::SigningKey, CosmosClient> {
+        let pri = XPrv::derive_from_path(&self.seed, &derivation.parse()?)?;
+
+        Ok(pri.private_key().clone())
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - `index` is out of the non hardened range
+    /// - if the prefix is bad
+    pub fn address(
+        &self,
+        account: u32,
+        index: u32,
+        prefix: &str,
+    ) -> Result<AccountId, CosmosClient> {
+        let private_key = self.derive(&self.key_type.derivation(account, index))?;
+
+        self.key_type
+            .address(&(*private_key.verifying_key()).into(), prefix)
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - `index` is out of the non hardened range
+    /// - if the prefix is bad
+    pub fn signer(
+        &self,
+        account: u32,
+        index: u32,
+        prefix: &str,
+        gas_adjustment_percent: u8,
+        gas_price: GasPrice,
+    ) -> Result<Signer, CosmosClient> {
+        Signer::new(
+            self.key_type,
+            self.derive(&self.key_type.derivation(account, index))?,
+            Some(self.phrase.to_string()),
+            prefix,
+            gas_adjustment_percent,
+            gas_price,
+        )
+    }
+
+    /// Scan the indices of `account` from 0 with `lookup`, until
+    /// `gap_limit` addresses in a row are unused. An address is used when
+    /// `lookup` returns `Some`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - `lookup` fails
+    /// - if the prefix is bad
+    pub async fn discover<T, F, Fut>(
+        &self,
+        account: u32,
+        prefix: &str,
+        gap_limit: u32,
+        mut lookup: F,
+    ) -> Result<Vec<T>, CosmosClient>
+    where
+        F: FnMut(u32, AccountId) -> Fut,
+        Fut: Future<Output = Result<Option<T>, CosmosClient>>,
+    {
+        let mut found = vec![];
+        let mut gap = 0;
+        let mut index = 0;
+
+        while gap < gap_limit {
+            match lookup(index, self.address(account, index, prefix)?).await? {
+                Some(used) => {
+                    found.push(used);
+                    gap = 0;
+                }
+                None => gap += 1,
+            }
+            index += 1;
+        }
+
+        Ok(found)
+    }
+}
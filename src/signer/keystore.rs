@@ -0,0 +1,127 @@
+use crate::error::CosmosClient;
+use crate::signer::armor::{bcrypt_key, open, seal};
+use crate::signer::KeyType;
+use cosmrs::crypto::PublicKey;
+use cosmrs::AccountId;
+use rand_core::{OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+
+pub const KEYSTORE_VERSION: u32 = 1;
+
+/// Encrypted JSON keystore of a private key :
+///
+/// ```json
+/// {
+///   "version": 1,
+///   "key_type": "secp256k1",
+///   "address": "cosmos1...",
+///   "public_key": "<hex of the compressed public key>",
+///   "crypto": {
+///     "kdf": "bcrypt",
+///     "cost": 12,
+///     "salt": "<hex of 16 random bytes>",
+///     "cipher": "xsalsa20-poly1305",
+///     "ciphertext": "<hex of nonce || tag || encrypted key>"
+///   }
+/// }
+/// ```
+///
+/// The 32 bytes private key is sealed with the same primitives as the
+/// Cosmos SDK armor : the cipher key is the sha256 of the bcrypt hash string
+/// of the passphrase, and the ciphertext a `NaCl` secretbox prefixed by its 24
+/// bytes nonce. `key_type` is `secp256k1`, `eth_secp256k1` or
+/// `injective_eth_secp256k1`. `address` and `public_key` are left in clear to
+/// find a key without the passphrase.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Keystore {
+    pub version: u32,
+    pub key_type: KeyType,
+    pub address: String,
+    pub public_key: String,
+    pub crypto: KeystoreCrypto,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeystoreCrypto {
+    pub kdf: String,
+    pub cost: u32,
+    pub salt: String,
+    pub cipher: String,
+    pub ciphertext: String,
+}
+
+impl Keystore {
+    /// Encrypt `private_key` with `passphrase`, hashed with a bcrypt `cost`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - the cost is out of the bcrypt range
+    /// - the encryption fails
+    pub fn encrypt(
+        private_key: &k256::ecdsa::SigningKey,
+        key_type: KeyType,
+        address: &AccountId,
+        passphrase: &str,
+        cost: u32,
+    ) -> Result<Self, CosmosClient> {
+        let mut salt = [0u8; 16];
+        OsRng.fill_bytes(&mut salt);
+        let public_key = PublicKey::from(*private_key.verifying_key());
+
+        Ok(Keystore {
+            version: KEYSTORE_VERSION,
+            key_type,
+            address: address.to_string(),
+            public_key: hex::encode(public_key.to_bytes()),
+            crypto: KeystoreCrypto {
+                kdf: "bcrypt".to_string(),
+                cost,
+                salt: hex::encode(salt),
+                cipher: "xsalsa20-poly1305".to_string(),
+                ciphertext: hex::encode(seal(
+                    &bcrypt_key(passphrase, salt, cost)?,
+                    &private_key.to_bytes(),
+                )?),
+            },
+        })
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - the version, kdf or cipher is not supported
+    /// - the passphrase is wrong
+    /// - the decrypted key does not match `public_key`
+    pub fn decrypt(&self, passphrase: &str) -> Result<k256::ecdsa::SigningKey, CosmosClient> {
+        if self.version != KEYSTORE_VERSION {
+            return Err(CosmosClient::InvalidKeystore(format!(
+                "unsupported version {}",
+                self.version
+            )));
+        }
+        if self.crypto.kdf != "bcrypt" || self.crypto.cipher != "xsalsa20-poly1305" {
+            return Err(CosmosClient::InvalidKeystore(format!(
+                "unsupported {} / {} encryption",
+                self.crypto.kdf, self.crypto.cipher
+            )));
+        }
+        let salt: [u8; 16] = hex::decode(&self.crypto.salt)?
+            .try_into()
+            .map_err(|_| CosmosClient::InvalidKeystore("salt is not 16 bytes".to_string()))?;
+
+        let private_key = k256::ecdsa::SigningKey::from_slice(&open(
+            &bcrypt_key(passphrase, salt, self.crypto.cost)?,
+            &hex::decode(&self.crypto.ciphertext)?,
+        )?)?;
+        if PublicKey::from(*private_key.verifying_key()).to_bytes()
+            != hex::decode(&self.public_key)?
+        {
+            return Err(CosmosClient::InvalidKeystore(
+                "private key does not match the public key".to_string(),
+            ));
+        }
+
+        Ok(private_key)
+    }
+}
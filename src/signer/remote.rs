@@ -0,0 +1,251 @@
+use crate::error::CosmosClient;
+use crate::signer::TxSigner;
+use async_trait::async_trait;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use cosmrs::crypto::secp256k1::{Signature, VerifyingKey};
+use cosmrs::crypto::PublicKey;
+use cosmrs::AccountId;
+use k256::ecdsa::signature::Verifier;
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+/// Where the signing service listens.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Endpoint {
+    /// `http://host:port/base/path`, plain HTTP as the service is expected
+    /// to run on the same host or a private network.
+    Http { host: String, base_path: String },
+    /// `unix:///path/to/signer.sock`
+    Unix(PathBuf),
+}
+
+impl Endpoint {
+    /// # Errors
+    ///
+    /// Will return `Err` if `url` is neither an `http://` nor a `unix://` url
+    pub fn parse(url: &str) -> Result<Self, CosmosClient> {
+        if let Some(path) = url.strip_prefix("unix://") {
+            return Ok(Endpoint::Unix(PathBuf::from(path)));
+        }
+
+        let rest = url
+            .strip_prefix("http://")
+            .ok_or_else(|| CosmosClient::RemoteSigner(format!("unsupported endpoint {url}")))?;
+        let (host, base_path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+
+        Ok(Endpoint::Http {
+            host: host.to_string(),
+            base_path: base_path.trim_end_matches('/').to_string(),
+        })
+    }
+}
+
+/// Key held by a signing service, so the private key never enters the
+/// process. The service exposes a key under `name` with :
+/// - `GET /keys/{name}` returning `{"public_key": "<base64 compressed secp256k1>"}`
+/// - `POST /keys/{name}/sign` taking `{"sign_bytes": "<base64>"}` and
+///   returning `{"signature": "<base64 r || s>"}`
+///
+/// Every signature is checked against the public key before it is used.
+pub struct RemoteSigner {
+    pub endpoint: Endpoint,
+    pub name: String,
+    pub public_key: PublicKey,
+    pub public_address: AccountId,
+    /// Timeout of every request to the service, 30s by default.
+    pub timeout: Duration,
+}
+
+impl RemoteSigner {
+    /// Fetch the public key of the key `name` served at `url`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - `url` is not a valid endpoint
+    /// - the service is unreachable or answers with an error
+    /// - the public key is not a valid secp256k1 key
+    /// - if the prefix is bad
+    pub async fn connect(url: &str, name: &str, prefix: &str) -> Result<Self, CosmosClient> {
+        let endpoint = Endpoint::parse(url)?;
+        let timeout = Duration::from_secs(30);
+
+        let response = request(&endpoint, timeout, "GET", &format!("/keys/{name}"), None).await?;
+        let public_key = VerifyingKey::from_sec1_bytes(&decode_field(&response, "public_key")?)
+            .map(PublicKey::from)
+            .map_err(|_| CosmosClient::RemoteSigner(format!("invalid public key for {name}")))?;
+
+        Ok(RemoteSigner {
+            endpoint,
+            name: name.to_string(),
+            public_address: public_key.account_id(prefix)?,
+            public_key,
+            timeout,
+        })
+    }
+}
+
+#[async_trait]
+impl TxSigner for RemoteSigner {
+    fn public_key(&self) -> PublicKey {
+        self.public_key
+    }
+
+    fn address(&self) -> AccountId {
+        self.public_address.clone()
+    }
+
+    async fn sign_bytes(&self, bytes: &[u8]) -> Result<Vec<u8>, CosmosClient> {
+        let response = request(
+            &self.endpoint,
+            self.timeout,
+            "POST",
+            &format!("/keys/{}/sign", self.name),
+            Some(json!({ "sign_bytes": STANDARD.encode(bytes) })),
+        )
+        .await?;
+        let signature = decode_field(&response, "signature")?;
+
+        VerifyingKey::from_sec1_bytes(&self.public_key.to_bytes())
+            .and_then(|key| key.verify(bytes, &Signature::try_from(signature.as_slice())?))
+            .map_err(|_| {
+                CosmosClient::RemoteSigner(format!("invalid signature from {}", self.name))
+            })?;
+
+        Ok(signature)
+    }
+}
+
+fn decode_field(response: &Value, field: &str) -> Result<Vec<u8>, CosmosClient> {
+    let value = response[field]
+        .as_str()
+        .ok_or_else(|| CosmosClient::RemoteSigner(format!("no {field} in response")))?;
+
+    Ok(STANDARD.decode(value)?)
+}
+
+/// Send a JSON request with `Connection: close` and read the JSON answer.
+async fn request(
+    endpoint: &Endpoint,
+    timeout: Duration,
+    method: &str,
+    path: &str,
+    body: Option<Value>,
+) -> Result<Value, CosmosClient> {
+    let body = body.map(|body| body.to_string()).unwrap_or_default();
+    let (host, path) = match endpoint {
+        Endpoint::Http { host, base_path } => (host.as_str(), format!("{base_path}{path}")),
+        Endpoint::Unix(_) => ("localhost", path.to_string()),
+    };
+    let payload = format!(
+        "{method} {path} HTTP/1.1\r\nHost: {host}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+
+    let send = async {
+        match endpoint {
+            Endpoint::Http { host, .. } => {
+                exchange(TcpStream::connect(host).await?, &payload).await
+            }
+            #[cfg(unix)]
+            Endpoint::Unix(path) => {
+                exchange(tokio::net::UnixStream::connect(path).await?, &payload).await
+            }
+            #[cfg(not(unix))]
+            Endpoint::Unix(path) => Err(CosmosClient::RemoteSigner(format!(
+                "unix sockets are not supported : {}",
+                path.display()
+            ))),
+        }
+    };
+    let response = tokio::time::timeout(timeout, send)
+        .await
+        .map_err(|_| CosmosClient::RemoteSigner(format!("{method} {path} timed out")))??;
+
+    parse_response(&response)
+}
+
+async fn exchange<S: AsyncRead + AsyncWrite + Unpin>(
+    mut stream: S,
+    payload: &str,
+) -> Result<Vec<u8>, CosmosClient> {
+    stream.write_all(payload.as_bytes()).await?;
+    stream.flush().await?;
+
+    let mut response = vec![];
+    stream.read_to_end(&mut response).await?;
+    Ok(response)
+}
+
+fn parse_response(response: &[u8]) -> Result<Value, CosmosClient> {
+    let invalid = || CosmosClient::RemoteSigner("invalid HTTP response".to_string());
+
+    let split = response
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .ok_or_else(invalid)?;
+    let (head, body) = (
+        std::str::from_utf8(&response[..split])?,
+        &response[split + 4..],
+    );
+    let mut lines = head.lines();
+    let status = lines
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .ok_or_else(invalid)?
+        .parse::<u16>()?;
+    let headers = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim(), value.trim()))
+        .collect::<Vec<_>>();
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| *value)
+    };
+
+    let body = if header("transfer-encoding")
+        .is_some_and(|coding| coding.eq_ignore_ascii_case("chunked"))
+    {
+        decode_chunked(body).ok_or_else(invalid)?
+    } else {
+        match header("content-length") {
+            Some(length) => body.get(..length.parse::<usize>()?).ok_or_else(invalid)?,
+            None => body,
+        }
+        .to_vec()
+    };
+    let body = std::str::from_utf8(&body)?;
+
+    if !(200..300).contains(&status) {
+        return Err(CosmosClient::RemoteSigner(format!(
+            "status {status} : {}",
+            body.trim()
+        )));
+    }
+    Ok(serde_json::from_str(body)?)
+}
+
+/// Join the chunks of a `Transfer-Encoding: chunked` body, `None` if it is
+/// malformed or truncated. Chunk extensions and trailers are ignored.
+fn decode_chunked(mut body: &[u8]) -> Option<Vec<u8>> {
+    let mut decoded = vec![];
+
+    loop {
+        let end = body.windows(2).position(|window| window == b"\r\n")?;
+        let size = std::str::from_utf8(&body[..end]).ok()?;
+        let size = usize::from_str_radix(size.split(';').next()?.trim(), 16).ok()?;
+        body = &body[end + 2..];
+
+        if size == 0 {
+            return Some(decoded);
+        }
+        decoded.extend_from_slice(body.get(..size)?);
+        body = body.get(size..)?.strip_prefix(b"\r\n")?;
+    }
+}
@@ -1 +1,21 @@
 mod client;
+mod fee;
+mod keyring;
+mod light_client;
+mod monitor;
+mod multisig;
+mod sequence;
+mod signer;
+mod tx;
+
+#[cfg(test)]
+use cosmos_sdk_proto::cosmos::base::v1beta1::Coin;
+
+/// `amount` of `denom`, as found in msgs and fees.
+#[cfg(test)]
+pub(crate) fn coin(amount: &str, denom: &str) -> Coin {
+    Coin {
+        denom: denom.to_string(),
+        amount: amount.to_string(),
+    }
+}
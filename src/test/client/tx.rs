@@ -1 +1,114 @@
+#[cfg(test)]
+mod tx_tests {
+    use crate::client::tx::{
+        broadcast_error, expected_sequence, is_sequence_mismatch, tx_error, Confirmation, Module,
+        Response,
+    };
+    use crate::error::CosmosClient;
+    use cosmos_sdk_proto::cosmos::base::abci::v1beta1::TxResponse;
+    use cosmos_sdk_proto::cosmos::tx::v1beta1::GetTxResponse;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tendermint::abci::Code;
+    use tendermint::Hash;
+    use tendermint_rpc::endpoint::broadcast::tx_sync;
+    use tendermint_rpc::HttpClient;
 
+    const HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+    fn module() -> Result<Module, CosmosClient> {
+        Ok(Module::new(Arc::new(HttpClient::new(
+            "http://127.0.0.1:1",
+        )?)))
+    }
+
+    #[test]
+    fn sequence_mismatch() {
+        let log = "account sequence mismatch, expected 42, got 41: incorrect account sequence";
+
+        assert!(is_sequence_mismatch(log));
+        assert_eq!(expected_sequence(log), Some(42));
+        assert_eq!(
+            expected_sequence("account sequence mismatch: incorrect account sequence"),
+            None
+        );
+        assert!(!is_sequence_mismatch("insufficient fees"));
+        assert_eq!(expected_sequence("expected 3"), None);
+    }
+
+    #[tokio::test]
+    async fn confirm_timeout() -> Result<(), CosmosClient> {
+        let confirmation = Confirmation {
+            poll_interval: Duration::from_millis(10),
+            timeout: Duration::from_millis(100),
+            websocket: None,
+        };
+
+        let result = module()?.confirm(HASH, &confirmation).await;
+        assert!(matches!(result, Err(CosmosClient::TXPollingTimeout)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn confirm_cancelled() -> Result<(), CosmosClient> {
+        let cancel = tokio::time::sleep(Duration::from_millis(50));
+
+        let result = module()?
+            .confirm_or_cancel(HASH, &Confirmation::default(), cancel)
+            .await;
+        assert!(matches!(result, Err(CosmosClient::TXConfirmationCancelled)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_tx_failure() -> Result<(), CosmosClient> {
+        let response = |code: u32| {
+            Response::Sync(tx_sync::Response {
+                code: Code::from(code),
+                data: Vec::new().into(),
+                log: "insufficient fees".to_string(),
+                hash: Hash::None,
+            })
+        };
+
+        assert!(broadcast_error(&response(0)).is_none());
+        match broadcast_error(&response(13)) {
+            Some(CosmosClient::TxFailed { code, raw_log, .. }) => {
+                assert_eq!(code, 13);
+                assert_eq!(raw_log, "insufficient fees");
+            }
+            e => panic!("unexpected {e:?}"),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn deliver_tx_failure() {
+        let response = |code: u32| GetTxResponse {
+            tx: None,
+            tx_response: Some(TxResponse {
+                txhash: "ABCD".to_string(),
+                codespace: "wasm".to_string(),
+                code,
+                raw_log: "out of gas".to_string(),
+                gas_used: 1234,
+                ..TxResponse::default()
+            }),
+        };
+
+        assert!(tx_error(&response(0)).is_none());
+        assert!(matches!(
+            tx_error(&response(5)),
+            Some(CosmosClient::TxFailed {
+                code: 5,
+                gas_used: 1234,
+                ref hash,
+                ref codespace,
+                ..
+            }) if hash == "ABCD" && codespace == "wasm"
+        ));
+    }
+}
@@ -0,0 +1,161 @@
+#[cfg(test)]
+mod fee_tests {
+    use crate::error::CosmosClient;
+    use crate::fee::{Adjustment, FeeStrategy, GasPrice, Quote, SimulatedFee, StaticFee};
+    use crate::test::coin;
+    use crate::tx::Cosmos;
+    use cosmrs::AccountId;
+
+    #[test]
+    fn parse_gas_price() -> Result<(), CosmosClient> {
+        let price: GasPrice = "0.025uatom".parse()?;
+        assert_eq!(price.denom, "uatom");
+        assert_eq!(price.to_string(), "0.025uatom");
+
+        let price: GasPrice =
+            "3ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2".parse()?;
+        assert_eq!(
+            price.denom,
+            "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2"
+        );
+        assert_eq!(price.fee(10), coin("30", price.denom.as_str()));
+
+        assert!("uatom".parse::<GasPrice>().is_err());
+        assert!("0.025".parse::<GasPrice>().is_err());
+        assert!("0.0000000000000000001uatom".parse::<GasPrice>().is_err());
+        assert!(matches!(
+            "340282366920938463463.999999999999999999uatom".parse::<GasPrice>(),
+            Err(CosmosClient::InvalidGasPrice(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn fee_is_rounded_up() -> Result<(), CosmosClient> {
+        let price: GasPrice = "0.025uatom".parse()?;
+
+        assert_eq!(price.fee(200_000), coin("5000", "uatom"));
+        assert_eq!(price.fee(200_001), coin("5001", "uatom"));
+        assert_eq!(price.fee(0), coin("0", "uatom"));
+        Ok(())
+    }
+
+    #[test]
+    fn max_fee() -> Result<(), CosmosClient> {
+        let tx = Cosmos::build().max_fee(coin("5000", "uatom"));
+
+        tx.check_max_fee(&coin("5000", "uatom"))?;
+        assert!(matches!(
+            tx.check_max_fee(&coin("5001", "uatom")),
+            Err(CosmosClient::FeeAboveMax { .. })
+        ));
+        assert!(tx.check_max_fee(&coin("1", "uosmo")).is_err());
+        Cosmos::build().check_max_fee(&coin("1000000000", "uatom"))?;
+        Ok(())
+    }
+
+    #[test]
+    fn sdk_dec_gas_price() -> Result<(), CosmosClient> {
+        let price = GasPrice::from_sdk_dec("0.002500000000000000", "uosmo")?;
+        assert_eq!(price.to_string(), "0.0025uosmo");
+
+        let price = GasPrice::from_sdk_dec("2500000000000000", "uosmo")?;
+        assert_eq!(price.to_string(), "0.0025uosmo");
+
+        assert!(GasPrice::from_sdk_dec("abc", "uosmo").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn adjustment_clamps_fee() -> Result<(), CosmosClient> {
+        let adjustment = Adjustment {
+            gas_adjustment_percent: 30,
+            min_fee: Some(100),
+            max_fee: Some(1000),
+        };
+        assert_eq!(adjustment.gas_limit(100_000), 130_000);
+
+        let quote = |amount: &str| Quote {
+            gas_limit: 1,
+            amount: coin(amount, "uatom"),
+        };
+        assert_eq!(adjustment.clamp(quote("10"))?, quote("100"));
+        assert_eq!(adjustment.clamp(quote("500"))?, quote("500"));
+        assert_eq!(adjustment.clamp(quote("1000"))?, quote("1000"));
+        assert!(matches!(
+            adjustment.clamp(quote("5000")),
+            Err(CosmosClient::FeeAboveMax { fee, max_fee })
+                if fee == "5000uatom" && max_fee == "1000uatom"
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn static_and_simulated_quotes() -> Result<(), CosmosClient> {
+        let adjustment = Adjustment {
+            gas_adjustment_percent: 50,
+            ..Adjustment::default()
+        };
+
+        let fixed = StaticFee {
+            gas_limit: 200_000,
+            gas_price: "0.025uatom".parse()?,
+            adjustment: Adjustment::default(),
+        };
+        assert!(!fixed.simulate());
+        assert_eq!(
+            fixed.quote(0).await?,
+            Quote {
+                gas_limit: 200_000,
+                amount: coin("5000", "uatom"),
+            }
+        );
+
+        let simulated = SimulatedFee {
+            gas_price: "0.025uatom".parse()?,
+            adjustment,
+        };
+        assert!(simulated.simulate());
+        assert_eq!(
+            simulated.quote(100_000).await?,
+            Quote {
+                gas_limit: 150_000,
+                amount: coin("3750", "uatom"),
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn fee_granter_and_payer() -> Result<(), CosmosClient> {
+        let granter = AccountId::new("cosmos", &[1; 20])?.to_string();
+        let payer = AccountId::new("cosmos", &[2; 20])?.to_string();
+
+        let fee = Cosmos::build().build_fee(vec![], 100)?;
+        assert!(fee.granter.is_none() && fee.payer.is_none());
+
+        let fee = Cosmos::build()
+            .fee_granter(granter.as_str())
+            .fee_payer(payer.as_str())
+            .build_fee(vec![], 100)?;
+        assert_eq!(fee.granter.unwrap().to_string(), granter);
+        assert_eq!(fee.payer.unwrap().to_string(), payer);
+
+        assert!(Cosmos::build()
+            .fee_granter("not an address")
+            .build_fee(vec![], 100)
+            .is_err());
+
+        Cosmos::build().check_fee_payer(granter.as_str())?;
+        Cosmos::build()
+            .fee_payer(payer.as_str())
+            .check_fee_payer(payer.as_str())?;
+        assert!(matches!(
+            Cosmos::build()
+                .fee_payer(payer.as_str())
+                .check_fee_payer(granter.as_str()),
+            Err(CosmosClient::InvalidSigners(_))
+        ));
+        Ok(())
+    }
+}
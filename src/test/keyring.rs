@@ -0,0 +1,355 @@
+#[cfg(test)]
+mod keyring_tests {
+    use crate::error::CosmosClient;
+    use crate::keyring::{jose, KeyKind, Keyring, FILE_BACKEND_DIR, TEST_BACKEND_DIR};
+    use crate::proto::cosmos::crypto::keyring::v1::{record, Record};
+    use crate::signer::{KeyType, Signer};
+    use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+    use base64::Engine;
+    use prost::Message;
+    use serde_json::{json, Value};
+    use std::path::PathBuf;
+
+    /// Empty home directory of a chain binary.
+    fn home(name: &str) -> PathBuf {
+        let home =
+            std::env::temp_dir().join(format!("cosmos-client-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&home);
+        home
+    }
+
+    fn signer(key_type: KeyType) -> Result<Signer, CosmosClient> {
+        Signer::generate_with_key_type(key_type, "cosmos", None, 30, "0.025uatom".parse()?)
+    }
+
+    #[test]
+    fn pbes2_vector() -> Result<(), CosmosClient> {
+        // RFC 7517 appendix C
+        let mut cek = [0u8; 32];
+        jose::key_encryption_key(
+            "Thus from my lips, by yours, my sin is purged.",
+            &URL_SAFE_NO_PAD.decode("2WCTcJZ1Rvd_CJuJripQ1w")?,
+            4096,
+        )
+        .unwrap(
+            &URL_SAFE_NO_PAD.decode("TrqXOwuNUfDV9VPTNbyGvEJ9JMjefAVn-TR1uIxR9p6hsRQh9Tk7BA")?,
+            &mut cek,
+        )
+        .map_err(|_| CosmosClient::KeyDecryption)?;
+
+        assert_eq!(
+            cek,
+            [
+                111, 27, 25, 52, 66, 29, 20, 78, 92, 176, 56, 240, 65, 208, 82, 112, 161, 131, 36,
+                55, 202, 236, 185, 172, 129, 23, 153, 194, 195, 48, 253, 182
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn jwe() -> Result<(), CosmosClient> {
+        let token = jose::encrypt(b"payload", "test")?;
+        let parts: Vec<&str> = token.split('.').collect();
+        assert_eq!(parts.len(), 5);
+
+        let header: Value = serde_json::from_slice(&URL_SAFE_NO_PAD.decode(parts[0])?)?;
+        assert_eq!(header["alg"], "PBES2-HS256+A128KW");
+        assert_eq!(header["enc"], "A256GCM");
+        assert_eq!(header["p2c"], 8192);
+
+        assert_eq!(jose::decrypt(&token, "test")?.as_slice(), b"payload");
+        assert!(matches!(
+            jose::decrypt(&token, "other"),
+            Err(CosmosClient::KeyDecryption)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_backend() -> Result<(), CosmosClient> {
+        let home = home("keyring-test");
+        let keyring = Keyring::test(&home);
+        assert!(keyring.list()?.is_empty());
+
+        let alice = signer(KeyType::Secp256k1)?;
+        let bob = signer(KeyType::EthSecp256k1)?;
+        keyring.add("alice", &alice)?;
+        let info = keyring.add("bob", &bob)?;
+        assert_eq!(info.kind, KeyKind::Local);
+        assert_eq!(info.key_type(), Some(KeyType::EthSecp256k1));
+        assert_eq!(info.address("cosmos")?, bob.public_address);
+
+        // items readable by the Go keyring, with the `test` password
+        let dir = home.join(TEST_BACKEND_DIR);
+        let address_item: Value = serde_json::from_slice(&jose::decrypt(
+            &std::fs::read_to_string(dir.join(format!(
+                "{}.address",
+                hex::encode(alice.public_address.to_bytes())
+            )))?,
+            "test",
+        )?)?;
+        assert_eq!(
+            address_item["Key"],
+            format!("{}.address", hex::encode(alice.public_address.to_bytes()))
+        );
+        assert_eq!(address_item["Data"], STANDARD.encode("alice.info"));
+
+        let info_item: Value = serde_json::from_slice(&jose::decrypt(
+            &std::fs::read_to_string(dir.join("alice.info"))?,
+            "test",
+        )?)?;
+        let record = Record::decode(
+            STANDARD
+                .decode(info_item["Data"].as_str().unwrap_or_default())?
+                .as_slice(),
+        )?;
+        assert_eq!(record.name, "alice");
+        assert_eq!(
+            record.pub_key.unwrap_or_default().type_url,
+            "/cosmos.crypto.secp256k1.PubKey"
+        );
+        assert!(matches!(
+            record.item,
+            Some(record::Item::Local(record::Local { priv_key: Some(priv_key) }))
+                if priv_key.type_url == "/cosmos.crypto.secp256k1.PrivKey"
+        ));
+
+        assert!(matches!(
+            keyring.add("alice", &bob),
+            Err(CosmosClient::KeyAlreadyExists(_))
+        ));
+        assert!(matches!(
+            keyring.add("carol", &alice),
+            Err(CosmosClient::KeyAlreadyExists(_))
+        ));
+
+        let loaded = keyring.signer("bob", "cosmos", 30, "0.025uatom".parse()?)?;
+        assert_eq!(loaded.key_type, KeyType::EthSecp256k1);
+        assert_eq!(loaded.public_address, bob.public_address);
+
+        keyring.rename("alice", "carol")?;
+        assert_eq!(
+            keyring
+                .list()?
+                .into_iter()
+                .map(|key| key.name)
+                .collect::<Vec<_>>(),
+            vec!["bob", "carol"]
+        );
+        assert!(matches!(
+            keyring.key("alice"),
+            Err(CosmosClient::KeyNotFound(_))
+        ));
+        assert!(matches!(
+            keyring.rename("bob", "carol"),
+            Err(CosmosClient::KeyAlreadyExists(_))
+        ));
+
+        keyring.delete("carol")?;
+        keyring.add("alice", &alice)?;
+        keyring.delete("bob")?;
+        assert!(!dir
+            .join(format!(
+                "{}.address",
+                hex::encode(bob.public_address.to_bytes())
+            ))
+            .exists());
+        assert_eq!(keyring.list()?.len(), 1);
+
+        std::fs::remove_dir_all(home)?;
+        Ok(())
+    }
+
+    #[test]
+    fn escaped_name() -> Result<(), CosmosClient> {
+        let home = home("keyring-escaped");
+        let keyring = Keyring::test(&home);
+        keyring.add("ops/relayer 1%", &signer(KeyType::Secp256k1)?)?;
+
+        assert!(home
+            .join(TEST_BACKEND_DIR)
+            .join("ops%2Frelayer%201%25.info")
+            .exists());
+        assert_eq!(keyring.list()?[0].name, "ops/relayer 1%");
+
+        std::fs::remove_dir_all(home)?;
+        Ok(())
+    }
+
+    #[test]
+    fn file_backend() -> Result<(), CosmosClient> {
+        let home = home("keyring-file");
+        let keyring = Keyring::file(&home, "passphrase")?;
+        let keyhash = std::fs::read_to_string(home.join(FILE_BACKEND_DIR).join("keyhash"))?;
+        assert!(keyhash.starts_with("$2a$10$"));
+
+        let alice = signer(KeyType::InjectiveEthSecp256k1)?;
+        keyring.add("alice", &alice)?;
+        assert!(matches!(
+            Keyring::file(&home, "wrong"),
+            Err(CosmosClient::KeyDecryption)
+        ));
+
+        let keyring = Keyring::file(&home, "passphrase")?;
+        let loaded = keyring.signer("alice", "inj", 30, "500000000inj".parse()?)?;
+        assert_eq!(loaded.key_type, KeyType::InjectiveEthSecp256k1);
+        assert_eq!(loaded.public_key, alice.public_key);
+
+        // another backend does not share the password
+        assert!(Keyring::test(&home).list()?.is_empty());
+        std::fs::rename(home.join(FILE_BACKEND_DIR), home.join(TEST_BACKEND_DIR))?;
+        assert!(matches!(
+            Keyring::test(&home).key("alice"),
+            Err(CosmosClient::KeyDecryption)
+        ));
+
+        std::fs::remove_dir_all(home)?;
+        Ok(())
+    }
+
+    #[test]
+    fn offline_key() -> Result<(), CosmosClient> {
+        let home = home("keyring-offline");
+        let dir = home.join(TEST_BACKEND_DIR);
+        std::fs::create_dir_all(&dir)?;
+
+        let ledger = signer(KeyType::Secp256k1)?;
+        let record = Record {
+            name: "ledger".to_string(),
+            pub_key: Some(ledger.public_key.to_any()?),
+            item: Some(record::Item::Offline(record::Offline {})),
+        };
+        let item = json!({
+            "Key": "ledger.info",
+            "Data": STANDARD.encode(record.encode_to_vec()),
+            "Label": "",
+            "Description": "",
+            "KeychainNotTrustApplication": false,
+            "KeychainNotSynchronizable": false,
+        });
+        std::fs::write(
+            dir.join("ledger.info"),
+            jose::encrypt(item.to_string().as_bytes(), "test")?,
+        )?;
+
+        let keyring = Keyring::test(&home);
+        let info = keyring.key("ledger")?;
+        assert_eq!(info.kind, KeyKind::Offline);
+        assert_eq!(info.address("cosmos")?, ledger.public_address);
+        assert!(matches!(
+            keyring.signer("ledger", "cosmos", 30, "0.025uatom".parse()?),
+            Err(CosmosClient::InvalidKeyring(_))
+        ));
+
+        std::fs::remove_dir_all(home)?;
+        Ok(())
+    }
+
+    /// Copy of the `test` backend in `src/test/fixtures/gaia`, in the format
+    /// of `gaiad keys add --keyring-backend test` : keys `alice` and `bob`
+    /// and their 2-of-2 `multi`.
+    fn gaia_home(name: &str) -> Result<PathBuf, CosmosClient> {
+        let fixture = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src/test/fixtures/gaia")
+            .join(TEST_BACKEND_DIR);
+        let home = home(name);
+        std::fs::create_dir_all(home.join(TEST_BACKEND_DIR))?;
+        for entry in std::fs::read_dir(fixture)? {
+            let entry = entry?;
+            std::fs::copy(
+                entry.path(),
+                home.join(TEST_BACKEND_DIR).join(entry.file_name()),
+            )?;
+        }
+        Ok(home)
+    }
+
+    #[test]
+    fn gaia_backend() -> Result<(), CosmosClient> {
+        let home = gaia_home("keyring-gaia")?;
+        let keyring = Keyring::test(&home);
+
+        let keys = keyring.list()?;
+        let addresses = keys
+            .iter()
+            .map(|key| {
+                Ok((
+                    key.name.as_str(),
+                    key.kind,
+                    key.address("cosmos")?.to_string(),
+                ))
+            })
+            .collect::<Result<Vec<_>, CosmosClient>>()?;
+        assert_eq!(
+            addresses,
+            vec![
+                (
+                    "alice",
+                    KeyKind::Local,
+                    "cosmos1l3e9pgs3mmwuwrh95fecme0s0qtn2880f2jmfe".to_string()
+                ),
+                (
+                    "bob",
+                    KeyKind::Local,
+                    "cosmos12vfxp232rx0z9rzn0hay9jptagk8c86dn5t56m".to_string()
+                ),
+                (
+                    "multi",
+                    KeyKind::Multi,
+                    "cosmos176092gvewk54h34jjczrp55qwknnal3nyelenz".to_string()
+                ),
+            ]
+        );
+        assert_eq!(keys[2].key_type(), None);
+
+        let alice = keyring.signer("alice", "cosmos", 30, "0.025uatom".parse()?)?;
+        assert_eq!(
+            alice.public_address,
+            Signer::from_pkey(
+                &hex::encode([0x11u8; 32]),
+                "cosmos",
+                30,
+                "0.025uatom".parse()?
+            )?
+            .public_address
+        );
+        assert!(matches!(
+            keyring.signer("multi", "cosmos", 30, "0.025uatom".parse()?),
+            Err(CosmosClient::InvalidKeyring(_))
+        ));
+
+        std::fs::remove_dir_all(home)?;
+        Ok(())
+    }
+
+    #[test]
+    fn multisig_key() -> Result<(), CosmosClient> {
+        let home = gaia_home("keyring-multisig")?;
+        let dir = home.join(TEST_BACKEND_DIR);
+        let keyring = Keyring::test(&home);
+        let index = dir.join("f69e55219975a95bc6b2960430d28075a73efe33.address");
+        let index_data = |dir: &PathBuf| -> Result<Value, CosmosClient> {
+            let item: Value = serde_json::from_slice(&jose::decrypt(
+                &std::fs::read_to_string(
+                    dir.join("f69e55219975a95bc6b2960430d28075a73efe33.address"),
+                )?,
+                "test",
+            )?)?;
+            Ok(item["Data"].clone())
+        };
+        assert_eq!(index_data(&dir)?, STANDARD.encode("multi.info"));
+
+        keyring.rename("multi", "treasury")?;
+        assert_eq!(keyring.key("treasury")?.kind, KeyKind::Multi);
+        assert_eq!(index_data(&dir)?, STANDARD.encode("treasury.info"));
+
+        keyring.delete("treasury")?;
+        assert!(!index.exists());
+        assert!(!dir.join("treasury.info").exists());
+        assert_eq!(keyring.list()?.len(), 2);
+
+        std::fs::remove_dir_all(home)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,208 @@
+#[cfg(all(test, feature = "light-client"))]
+mod light_client_tests {
+    use crate::light_client::{verify, LightBlock, Options, Verdict};
+    use std::time::Duration;
+    use tendermint::block::header::Version;
+    use tendermint::block::signed_header::SignedHeader;
+    use tendermint::block::{parts, Commit, CommitSig, Header, Height, Id, Round};
+    use tendermint::vote::{Type, ValidatorIndex, Vote};
+    use tendermint::{validator, AppHash, Hash, PublicKey, Signature, Time};
+
+    const CHAIN_ID: &str = "testchain-1";
+
+    fn keys(seeds: &[u8]) -> Vec<ed25519_consensus::SigningKey> {
+        seeds
+            .iter()
+            .map(|seed| ed25519_consensus::SigningKey::from([*seed; 32]))
+            .collect()
+    }
+
+    fn validator_set(keys: &[ed25519_consensus::SigningKey]) -> validator::Set {
+        validator::Set::without_proposer(
+            keys.iter()
+                .map(|key| {
+                    validator::Info::new(
+                        PublicKey::from_raw_ed25519(&key.verification_key().to_bytes()).unwrap(),
+                        10u32.into(),
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    fn block_time(height: u64) -> Time {
+        Time::from_unix_timestamp(1_700_000_000 + i64::try_from(height).unwrap() * 6, 0).unwrap()
+    }
+
+    /// Build a light block at `height` signed by the first `signers` keys of
+    /// `keys`, with `next_keys` as the next validator set.
+    fn light_block(
+        height: u64,
+        keys: &[ed25519_consensus::SigningKey],
+        next_keys: &[ed25519_consensus::SigningKey],
+        signers: usize,
+    ) -> LightBlock {
+        let validators = validator_set(keys);
+        let next_validators = validator_set(next_keys);
+        let header = Header {
+            version: Version { block: 11, app: 0 },
+            chain_id: CHAIN_ID.parse().unwrap(),
+            height: Height::try_from(height).unwrap(),
+            time: block_time(height),
+            last_block_id: None,
+            last_commit_hash: None,
+            data_hash: None,
+            validators_hash: validators.hash(),
+            next_validators_hash: next_validators.hash(),
+            consensus_hash: Hash::None,
+            app_hash: AppHash::try_from(vec![7u8; 32]).unwrap(),
+            last_results_hash: None,
+            evidence_hash: None,
+            proposer_address: validators.validators()[0].address,
+        };
+        let block_id = Id {
+            hash: header.hash(),
+            part_set_header: parts::Header::new(1, header.hash()).unwrap(),
+        };
+
+        let signatures = validators
+            .validators()
+            .iter()
+            .enumerate()
+            .map(|(index, validator)| {
+                let Some(key) = keys[..signers].iter().find(|key| {
+                    key.verification_key().to_bytes().as_slice()
+                        == validator.pub_key.to_bytes().as_slice()
+                }) else {
+                    return CommitSig::BlockIdFlagAbsent;
+                };
+                let vote = Vote {
+                    vote_type: Type::Precommit,
+                    height: header.height,
+                    round: Round::default(),
+                    block_id: Some(block_id),
+                    timestamp: Some(header.time),
+                    validator_address: validator.address,
+                    validator_index: ValidatorIndex::try_from(u32::try_from(index).unwrap())
+                        .unwrap(),
+                    signature: None,
+                    extension: vec![],
+                    extension_signature: None,
+                };
+                let signature = key.sign(&vote.into_signable_vec(CHAIN_ID.parse().unwrap()));
+                CommitSig::BlockIdFlagCommit {
+                    validator_address: validator.address,
+                    timestamp: header.time,
+                    signature: Some(Signature::from(signature)),
+                }
+            })
+            .collect();
+
+        let commit = Commit {
+            height: header.height,
+            round: Round::default(),
+            block_id,
+            signatures,
+        };
+
+        LightBlock {
+            signed_header: SignedHeader::new(header, commit).unwrap(),
+            validators,
+            next_validators,
+        }
+    }
+
+    fn now() -> Time {
+        block_time(200)
+    }
+
+    #[test]
+    fn adjacent_header() {
+        let set = keys(&[1, 2, 3, 4]);
+        let trusted = light_block(10, &set, &set, 4);
+        let untrusted = light_block(11, &set, &set, 3);
+
+        assert_eq!(
+            verify(&trusted, &untrusted, &Options::default(), now()),
+            Verdict::Success
+        );
+    }
+
+    #[test]
+    fn skipping_with_overlapping_validators() {
+        let trusted = light_block(10, &keys(&[1, 2, 3, 4]), &keys(&[1, 2, 3, 4]), 4);
+        let untrusted = light_block(50, &keys(&[1, 2, 5, 6]), &keys(&[1, 2, 5, 6]), 4);
+
+        assert_eq!(
+            verify(&trusted, &untrusted, &Options::default(), now()),
+            Verdict::Success
+        );
+    }
+
+    #[test]
+    fn skipping_needs_bisection_when_validators_changed() {
+        let trusted = light_block(10, &keys(&[1, 2, 3, 4]), &keys(&[1, 2, 3, 4]), 4);
+        let untrusted = light_block(50, &keys(&[1, 5, 6, 7]), &keys(&[1, 5, 6, 7]), 4);
+
+        assert_eq!(
+            verify(&trusted, &untrusted, &Options::default(), now()),
+            Verdict::NotEnoughTrust
+        );
+    }
+
+    #[test]
+    fn not_enough_signatures() {
+        let set = keys(&[1, 2, 3, 4]);
+        let trusted = light_block(10, &set, &set, 4);
+        let untrusted = light_block(11, &set, &set, 2);
+
+        assert!(matches!(
+            verify(&trusted, &untrusted, &Options::default(), now()),
+            Verdict::Invalid(_)
+        ));
+    }
+
+    #[test]
+    fn forged_signature() {
+        let set = keys(&[1, 2, 3, 4]);
+        let trusted = light_block(10, &set, &set, 4);
+        let mut untrusted = light_block(11, &set, &set, 4);
+        if let CommitSig::BlockIdFlagCommit { signature, .. } =
+            &mut untrusted.signed_header.commit.signatures[0]
+        {
+            *signature = Some(Signature::try_from(vec![1u8; 64].as_slice()).unwrap());
+        }
+
+        assert!(matches!(
+            verify(&trusted, &untrusted, &Options::default(), now()),
+            Verdict::Invalid(_)
+        ));
+    }
+
+    #[test]
+    fn validator_set_not_following_trusted() {
+        let trusted = light_block(10, &keys(&[1, 2, 3, 4]), &keys(&[1, 2, 3, 4]), 4);
+        let untrusted = light_block(11, &keys(&[5, 6, 7, 8]), &keys(&[5, 6, 7, 8]), 4);
+
+        assert!(matches!(
+            verify(&trusted, &untrusted, &Options::default(), now()),
+            Verdict::Invalid(_)
+        ));
+    }
+
+    #[test]
+    fn expired_trusted_header() {
+        let set = keys(&[1, 2, 3, 4]);
+        let trusted = light_block(10, &set, &set, 4);
+        let untrusted = light_block(11, &set, &set, 4);
+        let options = Options {
+            trusting_period: Duration::from_secs(60),
+            ..Options::default()
+        };
+
+        assert!(matches!(
+            verify(&trusted, &untrusted, &options, now()),
+            Verdict::Invalid(_)
+        ));
+    }
+}
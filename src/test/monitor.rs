@@ -0,0 +1,167 @@
+#[cfg(test)]
+mod monitor_tests {
+    use crate::error::CosmosClient;
+    use crate::monitor::{alerts, missed_streak, status, Alert, Thresholds};
+    use cosmos_sdk_proto::cosmos::slashing::v1beta1::{Params, ValidatorSigningInfo};
+    use tendermint::account;
+    use tendermint::block::CommitSig;
+    use tendermint::Time;
+
+    fn params() -> Params {
+        Params {
+            signed_blocks_window: 10_000,
+            min_signed_per_window: b"50000000000000000".to_vec(),
+            ..Params::default()
+        }
+    }
+
+    fn signed(address: account::Id) -> CommitSig {
+        CommitSig::BlockIdFlagCommit {
+            validator_address: address,
+            timestamp: Time::unix_epoch(),
+            signature: None,
+        }
+    }
+
+    #[test]
+    fn streak() {
+        let ours = account::Id::new([1u8; 20]);
+        let other = account::Id::new([2u8; 20]);
+        let commits = vec![
+            vec![signed(other)],
+            vec![CommitSig::BlockIdFlagAbsent, signed(other)],
+            vec![signed(ours), signed(other)],
+            vec![signed(other)],
+        ];
+
+        assert_eq!(missed_streak(&commits, ours), 2);
+        assert_eq!(missed_streak(&commits, other), 0);
+    }
+
+    #[test]
+    fn nil_vote_is_signed() {
+        let ours = account::Id::new([1u8; 20]);
+        let other = account::Id::new([2u8; 20]);
+        let commits = vec![
+            vec![CommitSig::BlockIdFlagAbsent, signed(other)],
+            vec![
+                CommitSig::BlockIdFlagNil {
+                    validator_address: ours,
+                    timestamp: Time::unix_epoch(),
+                    signature: None,
+                },
+                signed(other),
+            ],
+            vec![CommitSig::BlockIdFlagAbsent, signed(other)],
+        ];
+
+        assert_eq!(missed_streak(&commits, ours), 1);
+    }
+
+    #[test]
+    fn uptime_and_jailing() -> Result<(), CosmosClient> {
+        let info = ValidatorSigningInfo {
+            missed_blocks_counter: 9_000,
+            ..ValidatorSigningInfo::default()
+        };
+
+        let status = status("cosmosvalcons1test", 100, &params(), &info, 3, false)?;
+
+        // 5% of 10_000 blocks must be signed, jailed after the 9_501st miss
+        assert!((status.uptime - 10.0).abs() < f64::EPSILON);
+        assert_eq!(status.blocks_before_jail, 500);
+        assert!(!status.jailed);
+        Ok(())
+    }
+
+    #[test]
+    fn decimal_with_point() -> Result<(), CosmosClient> {
+        let params = Params {
+            signed_blocks_window: 100,
+            min_signed_per_window: b"0.5".to_vec(),
+            ..Params::default()
+        };
+
+        let status = status(
+            "cosmosvalcons1test",
+            100,
+            &params,
+            &Default::default(),
+            0,
+            false,
+        )?;
+
+        assert_eq!(status.blocks_before_jail, 50);
+        Ok(())
+    }
+
+    #[test]
+    fn threshold_alerts() -> Result<(), CosmosClient> {
+        let info = ValidatorSigningInfo {
+            missed_blocks_counter: 9_400,
+            ..ValidatorSigningInfo::default()
+        };
+        let status = status("cosmosvalcons1test", 100, &params(), &info, 7, false)?;
+
+        let alerts = alerts(&status, &Thresholds::default());
+
+        assert_eq!(alerts.len(), 3);
+        assert_eq!(
+            alerts[0],
+            Alert::MissedStreak {
+                cons_address: "cosmosvalcons1test".to_string(),
+                streak: 7
+            }
+        );
+        assert!(matches!(alerts[1], Alert::LowUptime { .. }));
+        assert_eq!(
+            alerts[2],
+            Alert::NearJailing {
+                cons_address: "cosmosvalcons1test".to_string(),
+                blocks_before_jail: 100
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn jailed_after_jailed_until() -> Result<(), CosmosClient> {
+        // missed blocks are reset on jailing and `jailed_until` is long past
+        let info = ValidatorSigningInfo {
+            jailed_until: Some(prost_types::Timestamp {
+                seconds: 600,
+                nanos: 0,
+            }),
+            ..ValidatorSigningInfo::default()
+        };
+        let status = status("cosmosvalcons1test", 100, &params(), &info, 0, true)?;
+
+        assert!(status.jailed);
+        assert!((status.uptime - 100.0).abs() < f64::EPSILON);
+        assert_eq!(
+            alerts(&status, &Thresholds::default()),
+            vec![Alert::Jailed {
+                cons_address: "cosmosvalcons1test".to_string()
+            }]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn tombstoned_only_alert() -> Result<(), CosmosClient> {
+        let info = ValidatorSigningInfo {
+            tombstoned: true,
+            missed_blocks_counter: 9_400,
+            ..ValidatorSigningInfo::default()
+        };
+        let status = status("cosmosvalcons1test", 100, &params(), &info, 7, false)?;
+
+        assert_eq!(
+            alerts(&status, &Thresholds::default()),
+            vec![Alert::Tombstoned {
+                cons_address: "cosmosvalcons1test".to_string()
+            }]
+        );
+        Ok(())
+    }
+}
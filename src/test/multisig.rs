@@ -0,0 +1,179 @@
+#[cfg(test)]
+mod multisig_tests {
+    use crate::error::CosmosClient;
+    use crate::multisig::{bit_array, Multisig};
+    use crate::signer::Signer;
+    use crate::tx::decode::decode_tx;
+    use crate::tx::offline::UnsignedTx;
+    use crate::tx::Cosmos;
+    use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
+    use cosmos_sdk_proto::cosmos::base::v1beta1::Coin;
+    use cosmos_sdk_proto::cosmos::crypto::multisig::v1beta1::MultiSignature;
+    use cosmos_sdk_proto::cosmos::tx::v1beta1::mode_info;
+    use cosmos_sdk_proto::traits::MessageExt;
+    use cosmrs::tx::Fee;
+    use prost::Message;
+
+    fn signers() -> Result<Vec<Signer>, CosmosClient> {
+        (1..=3u8)
+            .map(|seed| {
+                Signer::from_pkey(
+                    hex::encode([seed; 32]).as_str(),
+                    "cosmos",
+                    30,
+                    "0.025uatom".parse()?,
+                )
+            })
+            .collect()
+    }
+
+    fn multisig(signers: &[Signer]) -> Result<Multisig, CosmosClient> {
+        Multisig::new(2, signers.iter().map(|signer| signer.public_key).collect())
+    }
+
+    fn unsigned(multisig: &Multisig) -> Result<UnsignedTx, CosmosClient> {
+        let address = multisig.account_id("cosmos")?.to_string();
+        let tx = Cosmos::build().add_msg(
+            MsgSend {
+                from_address: address.clone(),
+                to_address: address,
+                amount: vec![Coin {
+                    denom: "uatom".to_string(),
+                    amount: "1000".to_string(),
+                }],
+            }
+            .to_any()?,
+        );
+        let fee = Fee::from_amount_and_gas(
+            cosmrs::Coin {
+                amount: 5000,
+                denom: "uatom".parse()?,
+            },
+            200_000u64,
+        );
+
+        UnsignedTx::new(&tx.finish(), fee, "cosmoshub-4", 3, 9)
+    }
+
+    #[test]
+    fn compact_bit_array() {
+        let bits = bit_array(&[true, false, true, false, false]);
+        assert_eq!(bits.extra_bits_stored, 5);
+        assert_eq!(bits.elems, vec![0b1010_0000]);
+
+        let bits = bit_array(
+            &[false; 7]
+                .into_iter()
+                .chain([true; 3])
+                .collect::<Vec<bool>>(),
+        );
+        assert_eq!(bits.extra_bits_stored, 2);
+        assert_eq!(bits.elems, vec![0b0000_0001, 0b1100_0000]);
+    }
+
+    #[test]
+    fn multisig_key() -> Result<(), CosmosClient> {
+        let signers = signers()?;
+        let multisig = multisig(&signers)?;
+
+        let amino = multisig.amino_bytes();
+        assert_eq!(amino[..6], [0x22, 0xc1, 0xf7, 0xe2, 0x08, 0x02]);
+        assert_eq!(amino[6..13], [0x12, 0x26, 0xeb, 0x5a, 0xe9, 0x87, 0x21]);
+        assert_eq!(amino.len(), 6 + 3 * 40);
+
+        let address = multisig.account_id("cosmos")?;
+        assert_eq!(address.to_bytes().len(), 20);
+        assert_ne!(address, signers[0].public_address);
+
+        let reversed = Multisig::new(2, multisig.public_keys.iter().rev().copied().collect())?;
+        assert_ne!(reversed.account_id("cosmos")?, address);
+        assert_eq!(
+            reversed.sorted().account_id("cosmos")?,
+            multisig.clone().sorted().account_id("cosmos")?
+        );
+
+        assert!(Multisig::new(0, multisig.public_keys.clone()).is_err());
+        assert!(Multisig::new(4, multisig.public_keys).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn combine_partial_signatures() -> Result<(), CosmosClient> {
+        let signers = signers()?;
+        let multisig = multisig(&signers)?;
+        let unsigned = unsigned(&multisig)?;
+
+        let partials = vec![
+            unsigned.sign_partial(&signers[2])?,
+            unsigned.sign_partial(&signers[0])?,
+        ];
+        let signed = multisig.combine(&unsigned, &partials)?;
+        let decoded = decode_tx(&signed.to_bytes()?)?;
+
+        assert_eq!(decoded.signer_infos[0].sequence, 9);
+        let Some(mode_info::Sum::Multi(multi)) =
+            decoded.signer_infos[0].mode_info.clone().unwrap().sum
+        else {
+            unreachable!()
+        };
+        assert_eq!(multi.bitarray, Some(bit_array(&[true, false, true])));
+        assert_eq!(multi.mode_infos.len(), 2);
+
+        let signature = MultiSignature::decode(decoded.signatures[0].as_slice())?;
+        assert_eq!(
+            signature.signatures,
+            vec![
+                base64_decode(&partials[1].signature),
+                base64_decode(&partials[0].signature)
+            ]
+        );
+        assert_eq!(
+            signed.tx["auth_info"]["signer_infos"][0]["public_key"]["threshold"],
+            2
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn reject_bad_partial_signatures() -> Result<(), CosmosClient> {
+        let signers = signers()?;
+        let multisig = multisig(&signers)?;
+        let unsigned = unsigned(&multisig)?;
+        let first = unsigned.sign_partial(&signers[0])?;
+
+        assert!(multisig
+            .combine(&unsigned, std::slice::from_ref(&first))
+            .is_err());
+        assert!(multisig
+            .combine(&unsigned, &[first.clone(), first.clone()])
+            .is_err());
+
+        let outsider = Signer::from_pkey(
+            hex::encode([9u8; 32]).as_str(),
+            "cosmos",
+            30,
+            "0.025uatom".parse()?,
+        )?;
+        assert!(multisig
+            .combine(
+                &unsigned,
+                &[first.clone(), unsigned.sign_partial(&outsider)?]
+            )
+            .is_err());
+
+        let mut forged = unsigned.sign_partial(&signers[1])?;
+        forged.signature = first.signature.clone();
+        assert!(matches!(
+            multisig.combine(&unsigned, &[first, forged]),
+            Err(CosmosClient::InvalidMultisig(_))
+        ));
+        Ok(())
+    }
+
+    fn base64_decode(value: &str) -> Vec<u8> {
+        use base64::Engine;
+        base64::engine::general_purpose::STANDARD
+            .decode(value)
+            .unwrap()
+    }
+}
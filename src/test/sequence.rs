@@ -0,0 +1,304 @@
+#[cfg(test)]
+mod sequence_tests {
+    use crate::client::auth;
+    use crate::client::Rpc;
+    use crate::error::CosmosClient;
+    use crate::sequence::SequenceManager;
+    use crate::signer::Signer;
+    use crate::tx::Cosmos;
+    use base64::engine::general_purpose::STANDARD;
+    use base64::Engine;
+    use cosmos_sdk_proto::cosmos::auth::v1beta1::{BaseAccount, QueryAccountResponse};
+    use cosmos_sdk_proto::cosmos::base::abci::v1beta1::GasInfo;
+    use cosmos_sdk_proto::cosmos::tx::v1beta1::{
+        AuthInfo, BroadcastMode, SimulateRequest, SimulateResponse, TxRaw,
+    };
+    use prost::Message;
+    use prost_types::Any;
+    use serde_json::{json, Value};
+    use std::sync::{Arc, Mutex};
+    use tendermint_rpc::HttpClient;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    fn manager(sequence: u64) -> Result<SequenceManager, CosmosClient> {
+        let auth = auth::Module::new(Arc::new(HttpClient::new("http://localhost:26657")?));
+        Ok(SequenceManager::from_sequence(auth, "cosmos1", 7, sequence))
+    }
+
+    #[test]
+    fn acquire_and_confirm() -> Result<(), CosmosClient> {
+        let manager = manager(10)?;
+        let shared = manager.clone();
+
+        assert_eq!(manager.acquire(), 10);
+        assert_eq!(shared.acquire(), 11);
+        assert_eq!(manager.acquire(), 12);
+        assert_eq!(manager.in_flight(), vec![10, 11, 12]);
+        assert_eq!(manager.account_number(), 7);
+
+        manager.confirm(11);
+        manager.confirm(10);
+        assert_eq!(shared.in_flight(), vec![12]);
+        assert_eq!(shared.next_sequence(), 13);
+
+        Ok(())
+    }
+
+    #[test]
+    fn failed_sequence_is_reused() -> Result<(), CosmosClient> {
+        let manager = manager(0)?;
+        let sequences: Vec<u64> = (0..4).map(|_| manager.acquire()).collect();
+        assert_eq!(sequences, vec![0, 1, 2, 3]);
+
+        manager.confirm(0);
+        manager.fail(1, None);
+        assert_eq!(manager.next_sequence(), 1);
+        assert_eq!(manager.acquire(), 1);
+        assert_eq!(manager.acquire(), 4);
+
+        // txs after the gap fail with a mismatch and are signed again
+        manager.fail(2, Some(2));
+        manager.fail(3, Some(2));
+        assert_eq!(manager.acquire(), 2);
+        assert_eq!(manager.acquire(), 3);
+        assert_eq!(manager.in_flight(), vec![1, 2, 3, 4]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn never_below_confirmed() -> Result<(), CosmosClient> {
+        let manager = manager(5)?;
+        let first = manager.acquire();
+        let second = manager.acquire();
+
+        manager.confirm(second);
+        manager.fail(first, Some(3));
+        assert_eq!(manager.next_sequence(), 7);
+        assert_eq!(manager.acquire(), 7);
+        assert!(manager.in_flight().iter().all(|sequence| *sequence >= 7));
+
+        Ok(())
+    }
+
+    /// Account state of a local stand-in of a node : like a chain check
+    /// state, a tx is only simulated or accepted with the current sequence.
+    struct Node {
+        sequence: u64,
+        accepted: Vec<u64>,
+    }
+
+    impl Node {
+        fn check(&mut self, tx_bytes: &[u8], broadcast: bool) -> Result<(), String> {
+            let raw = TxRaw::decode(tx_bytes).unwrap();
+            let sequence = AuthInfo::decode(raw.auth_info_bytes.as_slice())
+                .unwrap()
+                .signer_infos[0]
+                .sequence;
+
+            if sequence != self.sequence {
+                return Err(format!(
+                    "account sequence mismatch, expected {}, got {sequence}: incorrect account sequence",
+                    self.sequence
+                ));
+            }
+            if broadcast {
+                self.sequence += 1;
+                self.accepted.push(sequence);
+            }
+            Ok(())
+        }
+
+        fn answer(&mut self, method: &str, params: &Value) -> Value {
+            let abci = |result: Result<Vec<u8>, String>| {
+                let (code, log, value) = match result {
+                    Ok(value) => (0, String::new(), value),
+                    Err(log) => (32, log, vec![]),
+                };
+                json!({ "response": {
+                    "code": code, "codespace": "", "height": "1", "index": "0", "info": "",
+                    "key": "", "log": log, "proofOps": null, "value": STANDARD.encode(value),
+                } })
+            };
+
+            match method {
+                "status" => serde_json::from_str(STATUS).unwrap(),
+                "abci_query" => {
+                    let data = hex::decode(params["data"].as_str().unwrap()).unwrap();
+                    match params["path"].as_str().unwrap() {
+                        "/cosmos.auth.v1beta1.Query/Account" => abci(Ok(QueryAccountResponse {
+                            account: Some(Any {
+                                type_url: "/cosmos.auth.v1beta1.BaseAccount".to_string(),
+                                value: BaseAccount {
+                                    account_number: 9,
+                                    sequence: self.sequence,
+                                    ..BaseAccount::default()
+                                }
+                                .encode_to_vec(),
+                            }),
+                        }
+                        .encode_to_vec())),
+                        "/cosmos.tx.v1beta1.Service/Simulate" => {
+                            let request = SimulateRequest::decode(data.as_slice()).unwrap();
+                            abci(self.check(&request.tx_bytes, false).map(|()| {
+                                SimulateResponse {
+                                    gas_info: Some(GasInfo {
+                                        gas_wanted: 0,
+                                        gas_used: 80_000,
+                                    }),
+                                    result: None,
+                                }
+                                .encode_to_vec()
+                            }))
+                        }
+                        path => panic!("unexpected query {path}"),
+                    }
+                }
+                "broadcast_tx_sync" => {
+                    let tx = STANDARD.decode(params["tx"].as_str().unwrap()).unwrap();
+                    let (code, log) = match self.check(&tx, true) {
+                        Ok(()) => (0, String::new()),
+                        Err(log) => (32, log),
+                    };
+                    json!({
+                        "code": code, "codespace": "", "data": "", "log": log,
+                        "hash": "57018296EE0919C9D351F2FFEA82A8D28DE223724D79965FC8D00A7477ED48BC",
+                    })
+                }
+                method => panic!("unexpected method {method}"),
+            }
+        }
+    }
+
+    const STATUS: &str = r#"{
+        "node_info": {
+            "channels": "40202122233038606100",
+            "id": "0b8e9b2fc583439e365a85f06a8909294f7657e8",
+            "listen_addr": "tcp://0.0.0.0:26656",
+            "moniker": "node",
+            "network": "testchain",
+            "other": { "rpc_address": "tcp://0.0.0.0:26657", "tx_index": "on" },
+            "protocol_version": { "app": "1", "block": "11", "p2p": "8" },
+            "version": "0.34.21"
+        },
+        "sync_info": {
+            "catching_up": false,
+            "earliest_app_hash": "",
+            "earliest_block_hash": "56527562E5142C279254641CE18DB0D845767F2933AAFB784D752905ABF410E8",
+            "earliest_block_height": "1",
+            "earliest_block_time": "2022-09-22T18:57:22.193215438Z",
+            "latest_app_hash": "0600000000000000",
+            "latest_block_hash": "030CFF86E5DC2007E3B88C675984F2E66E0E0E9DE5835116DBB65BA58C3C47F9",
+            "latest_block_height": "67",
+            "latest_block_time": "2022-09-22T18:57:57.686014484Z"
+        },
+        "validator_info": {
+            "address": "675F52E8FDA5F4047B8EAF498F946F551ED53DC2",
+            "pub_key": {
+                "type": "tendermint/PubKeyEd25519",
+                "value": "OKAnhjqSneoGRrC37lMmw13vpj3pge3Au8/5Q1YfGq0="
+            },
+            "voting_power": "10"
+        }
+    }"#;
+
+    /// Answer one JSON-RPC request, one connection at a time so the node
+    /// sees the requests in order.
+    async fn serve(mut stream: TcpStream, node: &Mutex<Node>) {
+        let mut request = vec![];
+        let mut buffer = [0u8; 4096];
+        let body = loop {
+            let read = stream.read(&mut buffer).await.unwrap();
+            request.extend_from_slice(&buffer[..read]);
+            let text = String::from_utf8_lossy(&request).to_string();
+
+            if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                let length = head
+                    .lines()
+                    .filter_map(|line| line.split_once(':'))
+                    .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+                    .map_or(0, |(_, length)| length.trim().parse::<usize>().unwrap());
+                if body.len() >= length {
+                    break body.to_string();
+                }
+            }
+        };
+
+        let request: Value = serde_json::from_str(&body).unwrap();
+        let result = node
+            .lock()
+            .unwrap()
+            .answer(request["method"].as_str().unwrap(), &request["params"]);
+        let response =
+            json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }).to_string();
+
+        stream
+            .write_all(
+                format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}",
+                    response.len()
+                )
+                .as_bytes(),
+            )
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn concurrent_managed_txs() -> Result<(), CosmosClient> {
+        let node = Arc::new(Mutex::new(Node {
+            sequence: 4,
+            accepted: vec![],
+        }));
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let url = format!("http://{}", listener.local_addr()?);
+        let served = node.clone();
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                serve(stream, &served).await;
+            }
+        });
+
+        let signer = Signer::from_pkey(
+            hex::encode([7u8; 32]).as_str(),
+            "cosmos",
+            30,
+            "0.025uatom".parse()?,
+        )?;
+        let address = signer.public_address.to_string();
+        let mut client = Rpc::new(&url).await?;
+        client.attach_signer(signer).await?;
+        // every tx must pass at the first try
+        client.set_sequence_retries(0);
+        let client = Arc::new(client);
+        let manager = SequenceManager::new(client.auth.clone(), &address).await?;
+
+        let tasks = (0..5)
+            .map(|index| {
+                let (client, manager) = (client.clone(), manager.clone());
+                tokio::spawn(async move {
+                    client
+                        .sign_and_broadcast_managed(
+                            &manager,
+                            &Cosmos::build().memo(&format!("tx {index}")),
+                            BroadcastMode::Sync,
+                        )
+                        .await
+                })
+            })
+            .collect::<Vec<_>>();
+        for task in tasks {
+            task.await.unwrap()?;
+        }
+
+        let mut accepted = node.lock().unwrap().accepted.clone();
+        accepted.sort_unstable();
+        assert_eq!(accepted, vec![4, 5, 6, 7, 8]);
+        assert_eq!(manager.floor(), 9);
+        assert!(manager.in_flight().is_empty());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,161 @@
+mod armor;
+mod hd;
+mod keystore;
+mod remote;
+
+#[cfg(test)]
+mod signer_tests {
+    use crate::client::any_helper::{any_to_cosmos, CosmosType};
+    use crate::error::CosmosClient;
+    use crate::proto::ethermint;
+    use crate::signer::{KeyType, Signer, TxSigner};
+    use crate::tx::{sign_body_with, Cosmos, SignMode};
+    use cosmos_sdk_proto::cosmos::auth::v1beta1::BaseAccount;
+    use cosmos_sdk_proto::cosmos::tx::v1beta1::{AuthInfo, TxRaw};
+    use cosmos_sdk_proto::traits::MessageExt;
+    use cosmrs::crypto::secp256k1::{Signature, VerifyingKey};
+    use cosmrs::tx::Fee;
+    use k256::ecdsa::signature::hazmat::PrehashVerifier;
+    use prost::Message;
+    use prost_types::Any;
+    use sha3::{Digest, Keccak256};
+
+    /// First Hardhat account.
+    const ETH_ADDRESS: &str = "f39fd6e51aad88f6f4ce6ab8827279cfffb92266";
+    const ETH_PRIVATE_KEY: &str =
+        "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    #[test]
+    fn eth_address() -> Result<(), CosmosClient> {
+        let signer = Signer::from_pkey_with_key_type(
+            KeyType::EthSecp256k1,
+            ETH_PRIVATE_KEY,
+            "evmos",
+            30,
+            "25000000000aevmos".parse()?,
+        )?;
+        assert_eq!(signer.public_address.prefix(), "evmos");
+        assert_eq!(hex::encode(signer.public_address.to_bytes()), ETH_ADDRESS);
+
+        let injective = Signer::from_pkey_with_key_type(
+            KeyType::InjectiveEthSecp256k1,
+            ETH_PRIVATE_KEY,
+            "inj",
+            30,
+            "500000000inj".parse()?,
+        )?;
+        assert_eq!(
+            hex::encode(injective.public_address.to_bytes()),
+            ETH_ADDRESS
+        );
+        assert_eq!(injective.public_key, signer.public_key);
+
+        let cosmos = Signer::from_pkey(ETH_PRIVATE_KEY, "cosmos", 30, "0.025uatom".parse()?)?;
+        assert_eq!(cosmos.key_type, KeyType::Secp256k1);
+        assert_eq!(cosmos.public_key, signer.public_key);
+        assert_ne!(
+            cosmos.public_address.to_bytes(),
+            signer.public_address.to_bytes()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn eth_derivation() -> Result<(), CosmosClient> {
+        let generated = Signer::generate_with_key_type(
+            KeyType::EthSecp256k1,
+            "evmos",
+            None,
+            30,
+            "25000000000aevmos".parse()?,
+        )?;
+        let phrase = generated.mnemonic.clone().unwrap_or_default();
+
+        let coin_type_60 = Signer::from_mnemonic_with_key_type(
+            KeyType::EthSecp256k1,
+            phrase.as_str(),
+            "evmos",
+            Some("m/44'/60'/0'/0/0"),
+            30,
+            "25000000000aevmos".parse()?,
+        )?;
+        assert_eq!(coin_type_60.public_address, generated.public_address);
+
+        let cosmos = Signer::from_mnemonic(phrase.as_str(), "evmos", None, 30, "1aevmos".parse()?)?;
+        assert_ne!(cosmos.public_key, generated.public_key);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn eth_signature() -> Result<(), CosmosClient> {
+        let signer = Signer::from_pkey_with_key_type(
+            KeyType::EthSecp256k1,
+            ETH_PRIVATE_KEY,
+            "evmos",
+            30,
+            "25000000000aevmos".parse()?,
+        )?;
+        let fee = Fee::from_amount_and_gas(
+            cosmrs::Coin {
+                amount: 5000,
+                denom: "aevmos".parse()?,
+            },
+            200_000u64,
+        );
+
+        let tx = sign_body_with(
+            &signer,
+            SignMode::Direct,
+            &Cosmos::build().memo("evm").finish(),
+            fee,
+            "evmos_9001-2",
+            1,
+            0,
+        )
+        .await?;
+        let raw = TxRaw::decode(tx.as_slice())?;
+        let public_key = AuthInfo::decode(raw.auth_info_bytes.as_slice())?.signer_infos[0]
+            .public_key
+            .clone()
+            .unwrap_or_default();
+        assert_eq!(
+            public_key.type_url,
+            "/ethermint.crypto.v1.ethsecp256k1.PubKey"
+        );
+        assert_eq!(
+            ethermint::crypto::v1::ethsecp256k1::PubKey::decode(public_key.value.as_slice())?.key,
+            signer.public_key.to_bytes()
+        );
+
+        let signature = signer.sign_bytes(b"sign bytes").await?;
+        VerifyingKey::from_sec1_bytes(&signer.public_key.to_bytes())?.verify_prehash(
+            &Keccak256::digest(b"sign bytes"),
+            &Signature::try_from(signature.as_slice())?,
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn eth_account() -> Result<(), CosmosClient> {
+        let base_account = BaseAccount {
+            address: "evmos1...".to_string(),
+            pub_key: None,
+            account_number: 12,
+            sequence: 3,
+        };
+        let account = Any {
+            type_url: "/ethermint.types.v1.EthAccount".to_string(),
+            value: ethermint::types::v1::EthAccount {
+                base_account: Some(base_account.clone()),
+                code_hash: "0xc5d2".to_string(),
+            }
+            .to_bytes()?,
+        };
+
+        assert!(matches!(
+            any_to_cosmos(&account)?,
+            CosmosType::EthAccount(account) if account.base_account == Some(base_account)
+        ));
+        Ok(())
+    }
+}
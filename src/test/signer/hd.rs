@@ -0,0 +1,107 @@
+#[cfg(test)]
+mod hd_tests {
+    use crate::error::CosmosClient;
+    use crate::signer::hd::{HdWallet, DEFAULT_GAP_LIMIT};
+    use crate::signer::{KeyType, Signer};
+    use cosmrs::bip32::XPrv;
+
+    /// BIP39 vector of the all zero entropy.
+    const PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon \
+        abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+        abandon abandon abandon abandon art";
+
+    #[test]
+    fn passphrase() -> Result<(), CosmosClient> {
+        let seed = hex::decode(
+            "bda85446c68413707090a52022edd26a1c9462295029f2e60cd7c4f2bbd30971\
+             70af7a4d73245cafa9c3cca8d561a7c3de6f5d4a10be8ed2a5e608d68f92fcc8",
+        )?;
+        let expected = XPrv::derive_from_path(&seed, &"m/44'/118'/0'/0/0".parse()?)?;
+
+        let signer = Signer::from_mnemonic_with_passphrase(
+            KeyType::Secp256k1,
+            PHRASE,
+            "TREZOR",
+            "cosmos",
+            None,
+            30,
+            "0.025uatom".parse()?,
+        )?;
+        assert_eq!(
+            signer.public_key.to_bytes(),
+            expected.public_key().to_bytes().to_vec()
+        );
+
+        let without = Signer::from_mnemonic(PHRASE, "cosmos", None, 30, "0.025uatom".parse()?)?;
+        assert_ne!(without.public_address, signer.public_address);
+        assert_eq!(
+            HdWallet::new(KeyType::Secp256k1, PHRASE, "")?.address(0, 0, "cosmos")?,
+            without.public_address
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn account_index() -> Result<(), CosmosClient> {
+        assert_eq!(KeyType::Secp256k1.derivation(2, 5), "m/44'/118'/2'/0/5");
+        for key_type in [KeyType::Secp256k1, KeyType::EthSecp256k1] {
+            assert_eq!(key_type.derivation(0, 0), key_type.default_derivation());
+        }
+
+        let wallet = HdWallet::new(KeyType::EthSecp256k1, PHRASE, "")?;
+        let signer = Signer::from_mnemonic_with_key_type(
+            KeyType::EthSecp256k1,
+            PHRASE,
+            "evmos",
+            Some("m/44'/60'/1'/0/3"),
+            30,
+            "1aevmos".parse()?,
+        )?;
+        assert_eq!(wallet.address(1, 3, "evmos")?, signer.public_address);
+
+        let derived = wallet.signer(1, 3, "evmos", 30, "1aevmos".parse()?)?;
+        assert_eq!(derived.key_type, KeyType::EthSecp256k1);
+        assert_eq!(derived.public_address, signer.public_address);
+        assert_eq!(derived.mnemonic.as_deref(), Some(PHRASE));
+
+        assert!(wallet.address(0, 1 << 31, "evmos").is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn discover() -> Result<(), CosmosClient> {
+        let wallet = HdWallet::new(KeyType::Secp256k1, PHRASE, "")?;
+        let used = [0, 3, 25];
+
+        let mut scanned = 0;
+        let found = wallet
+            .discover(0, "cosmos", DEFAULT_GAP_LIMIT, |index, address| {
+                scanned += 1;
+                let address_ok = wallet
+                    .address(0, index, "cosmos")
+                    .map(|expected| expected == address);
+                async move {
+                    assert!(address_ok?);
+                    Ok(used.contains(&index).then_some(index))
+                }
+            })
+            .await?;
+        assert_eq!(found, vec![0, 3]);
+        assert_eq!(scanned, 24);
+
+        let found = wallet
+            .discover(0, "cosmos", 25, |index, _| async move {
+                Ok(used.contains(&index).then_some(index))
+            })
+            .await?;
+        assert_eq!(found, vec![0, 3, 25]);
+
+        let found = wallet
+            .discover(0, "cosmos", 5, |index, _| async move {
+                Err::<Option<u32>, _>(CosmosClient::RpcError(format!("index {index}")))
+            })
+            .await;
+        assert!(matches!(found, Err(CosmosClient::RpcError(_))));
+        Ok(())
+    }
+}
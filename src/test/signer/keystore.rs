@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod keystore_tests {
+    use crate::error::CosmosClient;
+    use crate::signer::keystore::Keystore;
+    use crate::signer::{KeyType, Signer};
+    use serde_json::Value;
+
+    fn signer() -> Result<Signer, CosmosClient> {
+        Signer::from_pkey_with_key_type(
+            KeyType::InjectiveEthSecp256k1,
+            "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+            "inj",
+            30,
+            "500000000inj".parse()?,
+        )
+    }
+
+    #[test]
+    fn signer_keystore() -> Result<(), CosmosClient> {
+        let signer = signer()?;
+        let json = signer.to_keystore("passphrase")?;
+
+        let keystore: Value = serde_json::from_str(&json)?;
+        assert_eq!(keystore["version"], 1);
+        assert_eq!(keystore["key_type"], "injective_eth_secp256k1");
+        assert_eq!(keystore["address"], signer.public_address.to_string());
+        assert_eq!(keystore["crypto"]["kdf"], "bcrypt");
+        assert_eq!(keystore["crypto"]["cost"], 12);
+        assert_eq!(keystore["crypto"]["cipher"], "xsalsa20-poly1305");
+
+        let imported =
+            Signer::from_keystore(&json, "passphrase", "inj", 30, "500000000inj".parse()?)?;
+        assert_eq!(imported.key_type, KeyType::InjectiveEthSecp256k1);
+        assert_eq!(imported.public_address, signer.public_address);
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_keystore() -> Result<(), CosmosClient> {
+        let signer = signer()?;
+        let keystore = Keystore::encrypt(
+            &k256::ecdsa::SigningKey::from_slice(&hex::decode(
+                "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+            )?)?,
+            signer.key_type,
+            &signer.public_address,
+            "passphrase",
+            4,
+        )?;
+        assert!(keystore.decrypt("passphrase").is_ok());
+
+        assert!(matches!(
+            keystore.decrypt("wrong"),
+            Err(CosmosClient::KeyDecryption)
+        ));
+
+        let mut other = keystore.clone();
+        other.public_key = hex::encode(
+            Signer::from_pkey("01".repeat(32).as_str(), "inj", 30, "1inj".parse()?)?
+                .public_key
+                .to_bytes(),
+        );
+        assert!(matches!(
+            other.decrypt("passphrase"),
+            Err(CosmosClient::InvalidKeystore(_))
+        ));
+
+        let mut other = keystore;
+        other.version = 2;
+        assert!(matches!(
+            other.decrypt("passphrase"),
+            Err(CosmosClient::InvalidKeystore(_))
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,275 @@
+#[cfg(test)]
+mod remote_tests {
+    use crate::error::CosmosClient;
+    use crate::signer::remote::{Endpoint, RemoteSigner};
+    use crate::signer::{Signer, TxSigner};
+    use crate::tx::{sign_body, sign_body_with, Cosmos, SignMode};
+    use base64::engine::general_purpose::STANDARD;
+    use base64::Engine;
+    use cosmrs::tx::Fee;
+    use serde_json::{json, Value};
+    use std::path::PathBuf;
+    use std::sync::Arc;
+    use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    fn key(byte: u8) -> Result<Signer, CosmosClient> {
+        Signer::from_pkey(
+            hex::encode([byte; 32]).as_str(),
+            "cosmos",
+            30,
+            "0.025uatom".parse()?,
+        )
+    }
+
+    /// Local stand-in of the signing service, serving `key` as `bot`, with
+    /// a `Transfer-Encoding: chunked` answer when `chunked`.
+    async fn serve<S: AsyncRead + AsyncWrite + Unpin>(mut stream: S, key: &Signer, chunked: bool) {
+        let mut request = vec![];
+        let mut buffer = [0u8; 1024];
+        let (head, body) = loop {
+            let read = stream.read(&mut buffer).await.unwrap();
+            request.extend_from_slice(&buffer[..read]);
+            let text = String::from_utf8_lossy(&request).to_string();
+
+            if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                let length = head
+                    .lines()
+                    .find_map(|line| line.strip_prefix("Content-Length: "))
+                    .map_or(0, |length| length.parse::<usize>().unwrap());
+                if body.len() >= length {
+                    break (head.to_string(), body.to_string());
+                }
+            }
+        };
+
+        let (status, response) = match head.lines().next().unwrap_or_default() {
+            "GET /keys/bot HTTP/1.1" => (
+                "200 OK",
+                json!({ "public_key": STANDARD.encode(key.public_key.to_bytes()) }),
+            ),
+            "POST /keys/bot/sign HTTP/1.1" => {
+                let body: Value = serde_json::from_str(&body).unwrap();
+                let bytes = STANDARD
+                    .decode(body["sign_bytes"].as_str().unwrap())
+                    .unwrap();
+                let signature = key.sign_bytes(&bytes).await.unwrap();
+                ("200 OK", json!({ "signature": STANDARD.encode(signature) }))
+            }
+            _ => ("404 Not Found", json!({ "error": "unknown key" })),
+        };
+        let response = response.to_string();
+
+        let payload = if chunked {
+            let chunks = response
+                .as_bytes()
+                .chunks(7)
+                .map(|chunk| {
+                    format!(
+                        "{:x};ext=1\r\n{}\r\n",
+                        chunk.len(),
+                        String::from_utf8_lossy(chunk)
+                    )
+                })
+                .collect::<String>();
+            format!(
+                "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nTransfer-Encoding: chunked\r\n\r\n{chunks}0\r\n\r\n"
+            )
+        } else {
+            format!(
+                "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{response}",
+                response.len()
+            )
+        };
+        stream.write_all(payload.as_bytes()).await.unwrap();
+    }
+
+    /// Serve `key` over TCP and return the service url.
+    async fn spawn_http(key: Signer, chunked: bool) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                serve(stream, &key, chunked).await;
+            }
+        });
+        url
+    }
+
+    fn payload() -> Result<(cosmrs::tx::Body, Fee), CosmosClient> {
+        let body = Cosmos::build().memo("remote").finish();
+        let fee = Fee::from_amount_and_gas(
+            cosmrs::Coin {
+                amount: 5000,
+                denom: "uatom".parse()?,
+            },
+            200_000u64,
+        );
+
+        Ok((body, fee))
+    }
+
+    #[test]
+    fn endpoints() -> Result<(), CosmosClient> {
+        assert_eq!(
+            Endpoint::parse("http://127.0.0.1:8080/signer/")?,
+            Endpoint::Http {
+                host: "127.0.0.1:8080".to_string(),
+                base_path: "/signer".to_string(),
+            }
+        );
+        assert_eq!(
+            Endpoint::parse("unix:///run/signer.sock")?,
+            Endpoint::Unix(PathBuf::from("/run/signer.sock"))
+        );
+        assert!(Endpoint::parse("https://signer").is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn http_signer() -> Result<(), CosmosClient> {
+        let url = spawn_http(key(1)?, false).await;
+        let remote = RemoteSigner::connect(&url, "bot", "cosmos").await?;
+        let local = key(1)?;
+        assert_eq!(remote.address(), local.public_address);
+
+        let (body, fee) = payload()?;
+        for sign_mode in [SignMode::Direct, SignMode::AminoJson] {
+            assert_eq!(
+                sign_body_with(&remote, sign_mode, &body, fee.clone(), "cosmoshub-4", 7, 3).await?,
+                sign_body(
+                    &local.private_key.signing_key(),
+                    sign_mode,
+                    &body,
+                    fee.clone(),
+                    "cosmoshub-4",
+                    7,
+                    3
+                )?
+            );
+        }
+
+        assert!(matches!(
+            RemoteSigner::connect(&url, "other", "cosmos").await,
+            Err(CosmosClient::RemoteSigner(_))
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn chunked_signer() -> Result<(), CosmosClient> {
+        let url = spawn_http(key(6)?, true).await;
+        let remote = RemoteSigner::connect(&url, "bot", "cosmos").await?;
+        assert_eq!(remote.public_key, key(6)?.public_key);
+
+        let (body, fee) = payload()?;
+        assert_eq!(
+            sign_body_with(&remote, SignMode::Direct, &body, fee.clone(), "test", 1, 0).await?,
+            sign_body(
+                &key(6)?.private_key.signing_key(),
+                SignMode::Direct,
+                &body,
+                fee,
+                "test",
+                1,
+                0
+            )?
+        );
+        assert!(matches!(
+            RemoteSigner::connect(&url, "other", "cosmos").await,
+            Err(CosmosClient::RemoteSigner(error)) if error.contains("unknown key")
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn spawned_signer() -> Result<(), CosmosClient> {
+        let url = spawn_http(key(5)?, false).await;
+        let remote: Arc<dyn TxSigner> =
+            Arc::new(RemoteSigner::connect(&url, "bot", "cosmos").await?);
+        let (body, fee) = payload()?;
+
+        let tasks = (0..4u64).map(|sequence| {
+            let (remote, body, fee) = (remote.clone(), body.clone(), fee.clone());
+            tokio::spawn(async move {
+                sign_body_with(
+                    remote.as_ref(),
+                    SignMode::Direct,
+                    &body,
+                    fee,
+                    "cosmoshub-4",
+                    7,
+                    sequence,
+                )
+                .await
+            })
+        });
+        for (sequence, task) in (0..4u64).zip(tasks.collect::<Vec<_>>()) {
+            assert_eq!(
+                task.await.unwrap()?,
+                sign_body(
+                    &key(5)?.private_key.signing_key(),
+                    SignMode::Direct,
+                    &body,
+                    fee.clone(),
+                    "cosmoshub-4",
+                    7,
+                    sequence
+                )?
+            );
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn wrong_key() -> Result<(), CosmosClient> {
+        let url = spawn_http(key(2)?, false).await;
+        let mut remote = RemoteSigner::connect(&url, "bot", "cosmos").await?;
+        remote.public_key = key(3)?.public_key;
+
+        assert!(matches!(
+            remote.sign_bytes(b"payload").await,
+            Err(CosmosClient::RemoteSigner(_))
+        ));
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn unix_signer() -> Result<(), CosmosClient> {
+        let path = std::env::temp_dir().join(format!("cosmos-client-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = tokio::net::UnixListener::bind(&path)?;
+
+        let server_key = key(4)?;
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                serve(stream, &server_key, false).await;
+            }
+        });
+
+        let url = format!("unix://{}", path.display());
+        let remote = RemoteSigner::connect(&url, "bot", "cosmos").await?;
+        let local = key(4)?;
+
+        let (body, fee) = payload()?;
+        assert_eq!(
+            sign_body_with(&remote, SignMode::Direct, &body, fee.clone(), "test", 1, 0).await?,
+            sign_body(
+                &local.private_key.signing_key(),
+                SignMode::Direct,
+                &body,
+                fee,
+                "test",
+                1,
+                0
+            )?
+        );
+
+        std::fs::remove_file(&path)?;
+        Ok(())
+    }
+}
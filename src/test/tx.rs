@@ -0,0 +1,116 @@
+mod amino;
+mod balance;
+mod decode;
+mod eip712;
+mod multi;
+mod offline;
+mod validate;
+
+#[cfg(test)]
+mod tx_tests {
+    use crate::error::CosmosClient;
+    use crate::tx::decode::decode_tx;
+    use crate::tx::{simulation_tx, Cosmos, SignMode, SIMULATION_PUBLIC_KEY};
+    use cosmos_sdk_proto::cosmos::crypto::secp256k1::PubKey;
+    use cosmos_sdk_proto::cosmos::tx::signing::v1beta1;
+    use cosmos_sdk_proto::cosmos::tx::v1beta1::mode_info;
+    use cosmos_sdk_proto::Any;
+    use prost::Message;
+
+    fn msg(value: u8) -> Any {
+        Any {
+            type_url: "/cosmos.bank.v1beta1.MsgSend".to_string(),
+            value: vec![value],
+        }
+    }
+
+    #[test]
+    fn timeout_height() -> Result<(), CosmosClient> {
+        let tx = Cosmos::build().timeout_height(1000u32);
+        assert_eq!(tx.finish().timeout_height.value(), 1000);
+        assert_eq!(tx.finish_at(5000)?.timeout_height.value(), 1000);
+
+        let tx = Cosmos::build().timeout_blocks(20);
+        assert_eq!(tx.finish().timeout_height.value(), 0);
+        assert_eq!(tx.finish_at(5000)?.timeout_height.value(), 5020);
+
+        let tx = Cosmos::build().timeout_blocks(20).timeout_height(10u32);
+        assert_eq!(tx.finish_at(5000)?.timeout_height.value(), 10);
+        Ok(())
+    }
+
+    #[test]
+    fn extension_options() {
+        let body = Cosmos::build()
+            .extension_option(msg(1))
+            .non_critical_extension_option(msg(2))
+            .finish();
+
+        assert_eq!(body.extension_options, vec![msg(1)]);
+        assert_eq!(body.non_critical_extension_options, vec![msg(2)]);
+    }
+
+    #[test]
+    fn inspect_and_clear_msgs() -> Result<(), CosmosClient> {
+        let empty = Cosmos::build().body_size();
+        let tx = Cosmos::build().add_msg(msg(1)).add_msg(msg(2));
+
+        assert_eq!(tx.msgs(), &[msg(1), msg(2)]);
+        assert_eq!(tx.body_size(), tx.finish().into_bytes()?.len());
+        assert!(tx.body_size() > empty);
+
+        let tx = tx.clear_msgs();
+        assert!(tx.msgs().is_empty());
+        assert_eq!(tx.body_size(), empty);
+        Ok(())
+    }
+
+    #[test]
+    fn simulation_without_key() -> Result<(), CosmosClient> {
+        let tx = Cosmos::build().add_msg(Any {
+            type_url: "/cosmos.bank.v1beta1.MsgSend".to_string(),
+            value: vec![],
+        });
+        let bytes = simulation_tx(
+            &tx.finish(),
+            tx.build_fee(vec![], 100)?,
+            None,
+            7,
+            SignMode::AminoJson,
+        )?;
+
+        let decoded = decode_tx(&bytes)?;
+        assert_eq!(decoded.signatures, vec![Vec::<u8>::new()]);
+        let signer_info = &decoded.signer_infos[0];
+        assert_eq!(signer_info.sequence, 7);
+        let public_key = signer_info.public_key.clone().unwrap_or_default();
+        assert_eq!(public_key.type_url, "/cosmos.crypto.secp256k1.PubKey");
+        assert_eq!(
+            hex::encode_upper(PubKey::decode(public_key.value.as_slice())?.key),
+            SIMULATION_PUBLIC_KEY
+        );
+        assert_eq!(
+            signer_info.mode_info.clone().and_then(|mode| mode.sum),
+            Some(mode_info::Sum::Single(mode_info::Single {
+                mode: v1beta1::SignMode::LegacyAminoJson.into()
+            }))
+        );
+
+        let public_key = Any {
+            type_url: "/cosmos.crypto.secp256k1.PubKey".to_string(),
+            value: vec![1, 2, 3],
+        };
+        let bytes = simulation_tx(
+            &tx.finish(),
+            tx.build_fee(vec![], 100)?,
+            Some(public_key.clone()),
+            0,
+            SignMode::Direct,
+        )?;
+        assert_eq!(
+            decode_tx(&bytes)?.signer_infos[0].public_key,
+            Some(public_key)
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,213 @@
+#[cfg(test)]
+mod amino_tests {
+    use crate::error::CosmosClient;
+    use crate::test::coin;
+    use crate::tx::amino::{msg_to_amino, StdSignDoc};
+    use crate::tx::decode::decode_tx;
+    use crate::tx::Cosmos;
+    use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
+    use cosmos_sdk_proto::cosmos::distribution::v1beta1::MsgWithdrawValidatorCommission;
+    use cosmos_sdk_proto::cosmos::tx::signing::v1beta1::SignMode;
+    use cosmos_sdk_proto::cosmos::tx::v1beta1::mode_info;
+    use cosmos_sdk_proto::cosmwasm::wasm::v1::{
+        AccessConfig, AccessType, MsgExecuteContract, MsgStoreCode,
+    };
+    use cosmos_sdk_proto::ibc::applications::transfer::v1::MsgTransfer;
+    use cosmos_sdk_proto::traits::MessageExt;
+    use cosmos_sdk_proto::Any;
+    use cosmrs::crypto::secp256k1::SigningKey;
+    use cosmrs::tx::Fee;
+    use serde_json::json;
+
+    const FROM: &str = "cosmos1pkptre7fdkl6gfrzlesjjvhxhlc3r4gmmk8rs6";
+    const TO: &str = "cosmos10dyr9899g6t0pelew4nvf4j5c3jcgv0r73qga5";
+
+    fn fee() -> Result<Fee, CosmosClient> {
+        Ok(Fee::from_amount_and_gas(
+            cosmrs::Coin {
+                amount: 2000,
+                denom: "ucosm".parse()?,
+            },
+            180_000u64,
+        ))
+    }
+
+    fn send() -> MsgSend {
+        MsgSend {
+            from_address: FROM.to_string(),
+            to_address: TO.to_string(),
+            amount: vec![coin("1234567", "ucosm")],
+        }
+    }
+
+    #[test]
+    fn std_sign_doc_bytes() -> Result<(), CosmosClient> {
+        let body = Cosmos::build()
+            .memo("Use your power wisely")
+            .add_msg(send().to_any()?)
+            .finish();
+
+        let doc = StdSignDoc::new(&body, &fee()?, "simd-testing", 1, 0)?;
+
+        assert_eq!(
+            String::from_utf8(doc.to_bytes()?).unwrap(),
+            format!(
+                r#"{{"account_number":"1","chain_id":"simd-testing","fee":{{"amount":[{{"amount":"2000","denom":"ucosm"}}],"gas":"180000"}},"memo":"Use your power wisely","msgs":[{{"type":"cosmos-sdk/MsgSend","value":{{"amount":[{{"amount":"1234567","denom":"ucosm"}}],"from_address":"{FROM}","to_address":"{TO}"}}}}],"sequence":"0"}}"#
+            )
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn withdraw_validator_commission_sign_bytes() -> Result<(), CosmosClient> {
+        // amino names are capped at 39 characters, the SDK and cosmjs
+        // register `cosmos-sdk/MsgWithdrawValCommission`
+        let validator = "cosmosvaloper1sjllsnramtg3ewxqwwrwjxfgc4n4ef9u2lcnj0";
+        let body = Cosmos::build()
+            .add_msg(
+                MsgWithdrawValidatorCommission {
+                    validator_address: validator.to_string(),
+                }
+                .to_any()?,
+            )
+            .finish();
+
+        let doc = StdSignDoc::new(&body, &fee()?, "cosmoshub-4", 42, 7)?;
+
+        assert_eq!(
+            String::from_utf8(doc.to_bytes()?).unwrap(),
+            format!(
+                r#"{{"account_number":"42","chain_id":"cosmoshub-4","fee":{{"amount":[{{"amount":"2000","denom":"ucosm"}}],"gas":"180000"}},"memo":"","msgs":[{{"type":"cosmos-sdk/MsgWithdrawValCommission","value":{{"validator_address":"{validator}"}}}}],"sequence":"7"}}"#
+            )
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn escaping_and_timeout_height() -> Result<(), CosmosClient> {
+        let body = Cosmos::build()
+            .memo("<b>&</b>")
+            .timeout_height(1234u32)
+            .finish();
+
+        let doc = StdSignDoc::new(&body, &fee()?, "simd-testing", 1, 0)?;
+        let bytes = String::from_utf8(doc.to_bytes()?).unwrap();
+
+        assert!(bytes.contains(r#""memo":"\u003cb\u003e\u0026\u003c/b\u003e""#));
+        assert!(bytes.ends_with(r#""sequence":"0","timeout_height":"1234"}"#));
+        Ok(())
+    }
+
+    #[test]
+    fn amino_msgs() -> Result<(), CosmosClient> {
+        let execute = MsgExecuteContract {
+            sender: FROM.to_string(),
+            contract: TO.to_string(),
+            msg: br#"{"release":{}}"#.to_vec(),
+            funds: vec![],
+        };
+        assert_eq!(
+            msg_to_amino(&execute.to_any()?)?,
+            json!({
+                "type": "wasm/MsgExecuteContract",
+                "value": {
+                    "sender": FROM,
+                    "contract": TO,
+                    "msg": { "release": {} },
+                    "funds": [],
+                },
+            })
+        );
+
+        let store = MsgStoreCode {
+            sender: FROM.to_string(),
+            wasm_byte_code: vec![0, 97, 115, 109],
+            instantiate_permission: Some(AccessConfig {
+                permission: AccessType::AnyOfAddresses as i32,
+                address: String::new(),
+                addresses: vec![FROM.to_string(), TO.to_string()],
+            }),
+        };
+        assert_eq!(
+            msg_to_amino(&store.to_any()?)?,
+            json!({
+                "type": "wasm/MsgStoreCode",
+                "value": {
+                    "sender": FROM,
+                    "wasm_byte_code": "AGFzbQ==",
+                    "instantiate_permission": {
+                        "permission": "AnyOfAddresses",
+                        "addresses": [FROM, TO],
+                    },
+                },
+            })
+        );
+        let store = MsgStoreCode {
+            instantiate_permission: None,
+            ..store
+        };
+        assert_eq!(
+            msg_to_amino(&store.to_any()?)?,
+            json!({
+                "type": "wasm/MsgStoreCode",
+                "value": { "sender": FROM, "wasm_byte_code": "AGFzbQ==" },
+            })
+        );
+
+        let transfer = MsgTransfer {
+            source_port: "transfer".to_string(),
+            source_channel: "channel-0".to_string(),
+            token: Some(coin("1", "ucosm")),
+            sender: FROM.to_string(),
+            receiver: TO.to_string(),
+            timeout_height: None,
+            timeout_timestamp: 1_700_000_000_000_000_000,
+        };
+        assert_eq!(
+            msg_to_amino(&transfer.to_any()?)?,
+            json!({
+                "type": "cosmos-sdk/MsgTransfer",
+                "value": {
+                    "source_port": "transfer",
+                    "source_channel": "channel-0",
+                    "token": { "amount": "1", "denom": "ucosm" },
+                    "sender": FROM,
+                    "receiver": TO,
+                    "timeout_height": {},
+                    "timeout_timestamp": "1700000000000000000",
+                },
+            })
+        );
+
+        assert!(matches!(
+            msg_to_amino(&Any {
+                type_url: "/cosmos.staking.v1beta1.MsgCreateValidator".to_string(),
+                value: vec![],
+            }),
+            Err(CosmosClient::UnsupportedAminoMsg(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn sign_amino_json() -> Result<(), CosmosClient> {
+        let key = SigningKey::from_slice(&[1u8; 32])?;
+        let body = Cosmos::build().add_msg(send().to_any()?).finish();
+        let doc = StdSignDoc::new(&body, &fee()?, "simd-testing", 1, 3)?;
+
+        let decoded = decode_tx(&doc.sign(&body, &key)?)?;
+
+        assert_eq!(
+            decoded.signatures,
+            vec![key.sign(&doc.to_bytes()?)?.to_vec()]
+        );
+        assert_eq!(decoded.signer_infos[0].sequence, 3);
+        assert_eq!(
+            decoded.signer_infos[0].mode_info.clone().unwrap().sum,
+            Some(mode_info::Sum::Single(mode_info::Single {
+                mode: SignMode::LegacyAminoJson.into()
+            }))
+        );
+        Ok(())
+    }
+}
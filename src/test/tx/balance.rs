@@ -0,0 +1,95 @@
+#[cfg(test)]
+mod balance_tests {
+    use crate::error::CosmosClient;
+    use crate::test::coin;
+    use crate::tx::balance::BalanceChanges;
+    use cosmos_sdk_proto::tendermint::abci::{Event, EventAttribute};
+
+    fn event(kind: &str, attributes: &[(&str, &str)]) -> Event {
+        Event {
+            r#type: kind.to_string(),
+            attributes: attributes
+                .iter()
+                .map(|(key, value)| EventAttribute {
+                    key: (*key).to_string(),
+                    value: (*value).to_string(),
+                    index: true,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn coin_events() -> Result<(), CosmosClient> {
+        let events = vec![
+            event("message", &[("action", "/cosmos.bank.v1beta1.MsgSend")]),
+            event(
+                "coin_spent",
+                &[("spender", "alice"), ("amount", "100uatom,5ibc/ABC")],
+            ),
+            event(
+                "coin_received",
+                &[("receiver", "bob"), ("amount", "100uatom")],
+            ),
+            event(
+                "coin_received",
+                &[("receiver", "carol"), ("amount", "5ibc/ABC")],
+            ),
+            event("coin_spent", &[("spender", "bob"), ("amount", "30uatom")]),
+            event(
+                "coin_received",
+                &[("receiver", "alice"), ("amount", "30uatom")],
+            ),
+            event(
+                "transfer",
+                &[
+                    ("sender", "alice"),
+                    ("recipient", "bob"),
+                    ("amount", "100uatom"),
+                ],
+            ),
+        ];
+
+        let mut changes = BalanceChanges::from_events(&events)?;
+        assert_eq!(changes.get("alice", "uatom"), -70);
+        assert_eq!(changes.get("bob", "uatom"), 70);
+        assert_eq!(changes.get("carol", "ibc/ABC"), 5);
+
+        changes.add("alice", "uatom", -5000);
+        assert_eq!(
+            changes.sent("alice"),
+            vec![coin("5", "ibc/ABC"), coin("5070", "uatom")]
+        );
+        assert!(changes.received("alice").is_empty());
+        assert_eq!(changes.received("bob"), vec![coin("70", "uatom")]);
+
+        changes.add("carol", "ibc/ABC", -5);
+        assert!(!changes.0.contains_key("carol"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn transfer_events() -> Result<(), CosmosClient> {
+        let events = vec![event(
+            "transfer",
+            &[
+                ("sender", "alice"),
+                ("recipient", "bob"),
+                ("amount", "7uosmo"),
+            ],
+        )];
+
+        let changes = BalanceChanges::from_events(&events)?;
+        assert_eq!(changes.get("alice", "uosmo"), -7);
+        assert_eq!(changes.get("bob", "uosmo"), 7);
+
+        let invalid = vec![event(
+            "coin_spent",
+            &[("spender", "alice"), ("amount", "uatom")],
+        )];
+        assert!(BalanceChanges::from_events(&invalid).is_err());
+
+        Ok(())
+    }
+}
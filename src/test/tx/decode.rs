@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod decode_tests {
+    use crate::error::CosmosClient;
+    use crate::proto::cosmos::gov::v1::MsgVote;
+    use crate::tx::decode::{decode_tx, Msg};
+    use crate::tx::Cosmos;
+    use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
+    use cosmos_sdk_proto::cosmos::base::v1beta1::Coin;
+    use cosmos_sdk_proto::cosmwasm::wasm::v1::MsgExecuteContract;
+    use cosmos_sdk_proto::traits::MessageExt;
+    use cosmos_sdk_proto::Any;
+    use cosmrs::crypto::secp256k1::SigningKey;
+    use cosmrs::tx::{Fee, SignDoc, SignerInfo};
+
+    fn sign(tx: &Cosmos) -> Result<Vec<u8>, CosmosClient> {
+        let key = SigningKey::from_slice(&[1u8; 32])?;
+        let auth_info = SignerInfo::single_direct(Some(key.public_key()), 7).auth_info(
+            Fee::from_amount_and_gas(
+                cosmrs::Coin {
+                    amount: 2500,
+                    denom: "uatom".parse()?,
+                },
+                100_000u64,
+            ),
+        );
+        let sign_doc = SignDoc::new(&tx.finish(), &auth_info, &"cosmoshub-4".parse()?, 42)?;
+
+        Ok(sign_doc.sign(&key)?.to_bytes()?)
+    }
+
+    #[test]
+    fn decode_known_msgs() -> Result<(), CosmosClient> {
+        let send = MsgSend {
+            from_address: "cosmos1from".to_string(),
+            to_address: "cosmos1to".to_string(),
+            amount: vec![Coin {
+                denom: "uatom".to_string(),
+                amount: "1000".to_string(),
+            }],
+        };
+        let vote = MsgVote {
+            proposal_id: 12,
+            voter: "cosmos1from".to_string(),
+            option: 1,
+            metadata: String::new(),
+        };
+        let execute = MsgExecuteContract {
+            sender: "cosmos1from".to_string(),
+            contract: "cosmos1contract".to_string(),
+            msg: b"{}".to_vec(),
+            funds: vec![],
+        };
+        let tx = Cosmos::build()
+            .memo("decode me")
+            .add_msg(send.clone().to_any()?)
+            .add_msg(vote.to_any()?)
+            .add_msg(execute.to_any()?);
+
+        let decoded = decode_tx(&sign(&tx)?)?;
+
+        assert_eq!(decoded.body.memo, "decode me");
+        assert_eq!(decoded.msgs.len(), 3);
+        assert_eq!(decoded.msgs[0], Msg::MsgSend(send));
+        match &decoded.msgs[1] {
+            Msg::MsgVoteV1(vote) => assert_eq!(vote.proposal_id, 12),
+            _ => unreachable!(),
+        }
+        match &decoded.msgs[2] {
+            Msg::MsgExecuteContract(execute) => assert_eq!(execute.contract, "cosmos1contract"),
+            _ => unreachable!(),
+        }
+
+        let fee = decoded.fee.unwrap();
+        assert_eq!(fee.gas_limit, 100_000);
+        assert_eq!(fee.amount[0].amount, "2500");
+        assert_eq!(decoded.signer_infos.len(), 1);
+        assert_eq!(decoded.signer_infos[0].sequence, 7);
+        assert_eq!(decoded.signatures.len(), 1);
+        assert_eq!(decoded.hash.len(), 64);
+        Ok(())
+    }
+
+    #[test]
+    fn decode_unknown_msg() -> Result<(), CosmosClient> {
+        let unknown = Any {
+            type_url: "/osmosis.gamm.v1beta1.MsgSwapExactAmountIn".to_string(),
+            value: vec![1, 2, 3],
+        };
+        let tx = Cosmos::build().add_msg(unknown.clone());
+
+        let decoded = decode_tx(&sign(&tx)?)?;
+
+        assert_eq!(decoded.msgs, vec![Msg::Unknown(unknown)]);
+        Ok(())
+    }
+
+    #[test]
+    fn decode_invalid_bytes() {
+        assert!(decode_tx(&[0xff, 0xff, 0xff]).is_err());
+    }
+}
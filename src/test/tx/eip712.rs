@@ -0,0 +1,365 @@
+#[cfg(test)]
+mod eip712_tests {
+    use crate::error::CosmosClient;
+    use crate::proto::ethermint::types::v1::ExtensionOptionsWeb3Tx;
+    use crate::signer::{KeyType, Signer};
+    use crate::tx::eip712::{
+        eip155_chain_id, sign_web3_tx, tx_typed_data, TypedData, Web3Tx, WEB3_TX_TYPE_URL,
+    };
+    use crate::tx::Cosmos;
+    use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
+    use cosmos_sdk_proto::cosmos::base::v1beta1::Coin;
+    use cosmos_sdk_proto::cosmos::staking::v1beta1::MsgDelegate;
+    use cosmos_sdk_proto::cosmos::tx::signing::v1beta1::SignMode;
+    use cosmos_sdk_proto::cosmos::tx::v1beta1::{mode_info, AuthInfo, TxBody, TxRaw};
+    use cosmos_sdk_proto::traits::MessageExt;
+    use cosmrs::crypto::secp256k1::{Signature, VerifyingKey};
+    use cosmrs::tx::Fee;
+    use k256::ecdsa::RecoveryId;
+    use prost::Message;
+    use serde_json::json;
+
+    /// Private key of `Cow` in the EIP-712 example, `keccak256("cow")`.
+    const COW_KEY: &str = "c85ef7d79691fe79573b1a7064c19c1a9819ebdbd1faaab1a8ec92344438aaf4";
+
+    /// `Mail` example of the EIP-712 specification.
+    fn mail() -> Result<TypedData, CosmosClient> {
+        Ok(serde_json::from_value(json!({
+            "types": {
+                "EIP712Domain": [
+                    { "name": "name", "type": "string" },
+                    { "name": "version", "type": "string" },
+                    { "name": "chainId", "type": "uint256" },
+                    { "name": "verifyingContract", "type": "address" },
+                ],
+                "Person": [
+                    { "name": "name", "type": "string" },
+                    { "name": "wallet", "type": "address" },
+                ],
+                "Mail": [
+                    { "name": "from", "type": "Person" },
+                    { "name": "to", "type": "Person" },
+                    { "name": "contents", "type": "string" },
+                ],
+            },
+            "primaryType": "Mail",
+            "domain": {
+                "name": "Ether Mail",
+                "version": "1",
+                "chainId": 1,
+                "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC",
+            },
+            "message": {
+                "from": { "name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826" },
+                "to": { "name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB" },
+                "contents": "Hello, Bob!",
+            },
+        }))?)
+    }
+
+    fn cow() -> Result<Signer, CosmosClient> {
+        Signer::from_pkey_with_key_type(
+            KeyType::EthSecp256k1,
+            COW_KEY,
+            "evmos",
+            30,
+            "25000000000aevmos".parse()?,
+        )
+    }
+
+    fn fee() -> Result<Fee, CosmosClient> {
+        Ok(Fee::from_amount_and_gas(
+            cosmrs::Coin {
+                amount: 4_000_000_000_000_000,
+                denom: "aevmos".parse()?,
+            },
+            200_000u64,
+        ))
+    }
+
+    fn send(signer: &Signer) -> Result<Cosmos, CosmosClient> {
+        Ok(Cosmos::build().memo("web3").add_msg(
+            MsgSend {
+                from_address: signer.public_address.to_string(),
+                to_address: signer.public_address.to_string(),
+                amount: vec![Coin {
+                    denom: "aevmos".to_string(),
+                    amount: "1".to_string(),
+                }],
+            }
+            .to_any()?,
+        ))
+    }
+
+    #[test]
+    fn mail_vector() -> Result<(), CosmosClient> {
+        let mail = mail()?;
+
+        assert_eq!(
+            mail.encode_type("Mail")?,
+            "Mail(Person from,Person to,string contents)Person(string name,address wallet)"
+        );
+        assert_eq!(
+            hex::encode(mail.hash_struct("EIP712Domain", &mail.domain)?),
+            "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+        );
+        assert_eq!(
+            hex::encode(mail.hash_struct("Mail", &mail.message)?),
+            "c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e"
+        );
+        assert_eq!(
+            hex::encode(mail.hash()?),
+            "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+        );
+
+        let cow = cow()?;
+        assert_eq!(
+            hex::encode(cow.public_address.to_bytes()),
+            "cd2a3d9f938e13cd947ec05abc7fe734df8dd826"
+        );
+        let signature = cow.private_key.sign(&mail.sign_bytes()?)?;
+        assert_eq!(
+            hex::encode(signature.to_bytes()),
+            "4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d\
+             07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b91562"
+        );
+
+        let mut extra = mail;
+        extra.message["cc"] = "Alice".into();
+        assert!(matches!(
+            extra.hash(),
+            Err(CosmosClient::InvalidTypedData(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn cosmos_typed_data() -> Result<(), CosmosClient> {
+        let cow = cow()?;
+        let address = cow.public_address.to_string();
+        let typed_data = tx_typed_data(
+            &send(&cow)?.finish(),
+            &fee()?,
+            "evmos_9001-2",
+            7,
+            3,
+            address.as_str(),
+        )?;
+
+        assert_eq!(eip155_chain_id("evmos_9001-2")?, 9001);
+        assert!(eip155_chain_id("cosmoshub-4").is_err());
+        assert_eq!(
+            serde_json::to_value(&typed_data)?,
+            json!({
+                "types": {
+                    "Coin": [
+                        { "name": "denom", "type": "string" },
+                        { "name": "amount", "type": "string" },
+                    ],
+                    "EIP712Domain": [
+                        { "name": "name", "type": "string" },
+                        { "name": "version", "type": "string" },
+                        { "name": "chainId", "type": "uint256" },
+                        { "name": "verifyingContract", "type": "string" },
+                        { "name": "salt", "type": "string" },
+                    ],
+                    "Fee": [
+                        { "name": "feePayer", "type": "string" },
+                        { "name": "amount", "type": "Coin[]" },
+                        { "name": "gas", "type": "string" },
+                    ],
+                    "Msg": [
+                        { "name": "type", "type": "string" },
+                        { "name": "value", "type": "MsgValue" },
+                    ],
+                    "MsgValue": [
+                        { "name": "from_address", "type": "string" },
+                        { "name": "to_address", "type": "string" },
+                        { "name": "amount", "type": "TypeAmount[]" },
+                    ],
+                    "Tx": [
+                        { "name": "account_number", "type": "string" },
+                        { "name": "chain_id", "type": "string" },
+                        { "name": "fee", "type": "Fee" },
+                        { "name": "memo", "type": "string" },
+                        { "name": "msgs", "type": "Msg[]" },
+                        { "name": "sequence", "type": "string" },
+                    ],
+                    "TypeAmount": [
+                        { "name": "denom", "type": "string" },
+                        { "name": "amount", "type": "string" },
+                    ],
+                },
+                "primaryType": "Tx",
+                "domain": {
+                    "name": "Cosmos Web3",
+                    "version": "1.0.0",
+                    "chainId": 9001,
+                    "verifyingContract": "cosmos",
+                    "salt": "0",
+                },
+                "message": {
+                    "account_number": "7",
+                    "chain_id": "evmos_9001-2",
+                    "fee": {
+                        "amount": [{ "amount": "4000000000000000", "denom": "aevmos" }],
+                        "feePayer": address,
+                        "gas": "200000",
+                    },
+                    "memo": "web3",
+                    "msgs": [{
+                        "type": "cosmos-sdk/MsgSend",
+                        "value": {
+                            "amount": [{ "amount": "1", "denom": "aevmos" }],
+                            "from_address": address,
+                            "to_address": address,
+                        },
+                    }],
+                    "sequence": "3",
+                },
+            })
+        );
+        assert_eq!(
+            typed_data.encode_type("Tx")?,
+            "Tx(string account_number,string chain_id,Fee fee,string memo,Msg[] msgs,string sequence)\
+             Coin(string denom,string amount)\
+             Fee(string feePayer,Coin[] amount,string gas)\
+             Msg(string type,MsgValue value)\
+             MsgValue(string from_address,string to_address,TypeAmount[] amount)\
+             TypeAmount(string denom,string amount)"
+        );
+
+        // computed outside this crate from the typed data above, as a
+        // wallet signing it with `eth_signTypedData_v4` would
+        assert_eq!(address, "evmos1e54rm8un3cfum9r7cpdtcll8xn0cmkpxl730px");
+        assert_eq!(
+            hex::encode(typed_data.hash_struct("EIP712Domain", &typed_data.domain)?),
+            "1633cfb1cf33b8790808cb60604cfd1f261c59a367ccb3aaff568a000149ace9"
+        );
+        assert_eq!(
+            hex::encode(typed_data.hash_struct("Tx", &typed_data.message)?),
+            "2d93ac08a926045271d80695c81a6db8aaf59722daaa684aabdd5fea7987a160"
+        );
+        assert_eq!(
+            hex::encode(typed_data.hash()?),
+            "c0fd83d5fecfab37e9dbbd7cbbb17cc6bd3241647e995033b67a27121e7b11c1"
+        );
+        assert_eq!(
+            hex::encode(cow.private_key.sign(&typed_data.sign_bytes()?)?.to_bytes()),
+            "19eb5581058ffc966744ac44b6c48b084a772a97b9f6cf7387c07394979fb916\
+             0bb9c9bf69a18062035ae8cf354b239dc89d9353ce2ffec21ef4bc163b4a0bef"
+        );
+
+        let mixed = send(&cow)?.add_msg(
+            MsgDelegate {
+                delegator_address: address.clone(),
+                validator_address: "evmosvaloper1".to_string(),
+                amount: None,
+            }
+            .to_any()?,
+        );
+        assert!(matches!(
+            tx_typed_data(&mixed.finish(), &fee()?, "evmos_9001-2", 7, 3, &address),
+            Err(CosmosClient::InvalidTypedData(_))
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn web3_tx() -> Result<(), CosmosClient> {
+        let cow = cow()?;
+        let body = send(&cow)?.finish();
+
+        let raw = TxRaw::decode(
+            sign_web3_tx(&cow, &body, fee()?, "evmos_9001-2", 7, 3)
+                .await?
+                .as_slice(),
+        )?;
+        assert_eq!(raw.signatures, vec![Vec::<u8>::new()]);
+
+        let signer_info = AuthInfo::decode(raw.auth_info_bytes.as_slice())?.signer_infos[0].clone();
+        assert_eq!(
+            signer_info.public_key.unwrap_or_default().type_url,
+            "/ethermint.crypto.v1.ethsecp256k1.PubKey"
+        );
+        assert!(matches!(
+            signer_info.mode_info.and_then(|mode_info| mode_info.sum),
+            Some(mode_info::Sum::Single(mode_info::Single { mode })) if mode == SignMode::LegacyAminoJson as i32
+        ));
+
+        let body = TxBody::decode(raw.body_bytes.as_slice())?;
+        assert_eq!(body.extension_options[0].type_url, WEB3_TX_TYPE_URL);
+        let extension = ExtensionOptionsWeb3Tx::decode(body.extension_options[0].value.as_slice())?;
+        assert_eq!(extension.typed_data_chain_id, 9001);
+        assert_eq!(extension.fee_payer, cow.public_address.to_string());
+
+        let typed_data = tx_typed_data(
+            &send(&cow)?.finish(),
+            &fee()?,
+            "evmos_9001-2",
+            7,
+            3,
+            cow.public_address.as_ref(),
+        )?;
+        let recovered = VerifyingKey::recover_from_prehash(
+            &typed_data.hash()?,
+            &Signature::try_from(&extension.fee_payer_sig[..64])?,
+            RecoveryId::from_byte(extension.fee_payer_sig[64] - 27)
+                .unwrap_or(RecoveryId::new(false, false)),
+        )?;
+        assert_eq!(
+            recovered.to_sec1_bytes().to_vec(),
+            cow.public_key.to_bytes()
+        );
+
+        let cosmos = Signer::from_pkey(COW_KEY, "evmos", 30, "1aevmos".parse()?)?;
+        assert!(
+            sign_web3_tx(&cosmos, &send(&cow)?.finish(), fee()?, "evmos_9001-2", 7, 3)
+                .await
+                .is_err()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn web_wallet_signature() -> Result<(), CosmosClient> {
+        let cow = cow()?;
+        let tx = Web3Tx::new(
+            cow.public_key,
+            &cow.public_address,
+            &send(&cow)?.finish(),
+            fee()?,
+            "evmos_9001-2",
+            7,
+            3,
+        )?;
+
+        let signature = cow.private_key.sign(&tx.typed_data.sign_bytes()?)?;
+        let recovery_id = RecoveryId::trial_recovery_from_prehash(
+            &VerifyingKey::from_sec1_bytes(&cow.public_key.to_bytes())?,
+            &tx.typed_data.hash()?,
+            &signature,
+        )?;
+        let mut wallet_signature = signature.to_vec();
+        wallet_signature.push(recovery_id.to_byte() + 27);
+
+        assert_eq!(
+            tx.clone().into_tx(&wallet_signature)?,
+            tx.clone().into_tx(&signature.to_vec())?
+        );
+
+        let other = Signer::from_pkey_with_key_type(
+            KeyType::EthSecp256k1,
+            "0101010101010101010101010101010101010101010101010101010101010101",
+            "evmos",
+            30,
+            "1aevmos".parse()?,
+        )?;
+        let forged = other.private_key.sign(&tx.typed_data.sign_bytes()?)?;
+        assert!(matches!(
+            tx.into_tx(&forged.to_vec()),
+            Err(CosmosClient::InvalidTypedData(_))
+        ));
+        Ok(())
+    }
+}
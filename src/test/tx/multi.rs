@@ -0,0 +1,284 @@
+#[cfg(test)]
+mod multi_tests {
+    use crate::error::CosmosClient;
+    use crate::signer::{KeyType, Signer};
+    use crate::tx::decode::decode_tx;
+    use crate::tx::multi::{msg_signers, tx_signers, MultiSignerTx, SignerData};
+    use crate::tx::Cosmos;
+    use base64::engine::general_purpose::STANDARD;
+    use base64::Engine;
+    use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
+    use cosmos_sdk_proto::cosmos::base::v1beta1::Coin;
+    use cosmos_sdk_proto::cosmos::staking::v1beta1::MsgCreateValidator;
+    use cosmos_sdk_proto::traits::MessageExt;
+    use cosmrs::tx::Fee;
+    use prost::Message;
+
+    fn signers() -> Result<Vec<Signer>, CosmosClient> {
+        (1..=3u8)
+            .map(|seed| {
+                Signer::from_pkey(
+                    hex::encode([seed; 32]).as_str(),
+                    "cosmos",
+                    30,
+                    "0.025uatom".parse()?,
+                )
+            })
+            .collect()
+    }
+
+    fn send(from: &Signer, to: &Signer) -> Result<prost_types::Any, CosmosClient> {
+        Ok(MsgSend {
+            from_address: from.public_address.to_string(),
+            to_address: to.public_address.to_string(),
+            amount: vec![Coin {
+                denom: "uatom".to_string(),
+                amount: "1000".to_string(),
+            }],
+        }
+        .to_any()?)
+    }
+
+    fn data(signer: &Signer, account_number: u64) -> SignerData {
+        SignerData::new(
+            &signer.public_key,
+            signer.public_address.to_string().as_str(),
+            account_number,
+            account_number * 10,
+        )
+    }
+
+    fn fee() -> Result<Fee, CosmosClient> {
+        Ok(Fee::from_amount_and_gas(
+            cosmrs::Coin {
+                amount: 5000,
+                denom: "uatom".parse()?,
+            },
+            200_000u64,
+        ))
+    }
+
+    #[test]
+    fn sign_on_several_machines() -> Result<(), CosmosClient> {
+        let signers = signers()?;
+        let tx = Cosmos::build()
+            .add_msg(send(&signers[1], &signers[0])?)
+            .add_msg(send(&signers[0], &signers[1])?);
+        let mut unsigned = MultiSignerTx::new(
+            &tx.finish(),
+            fee()?,
+            "cosmoshub-4",
+            vec![data(&signers[0], 1), data(&signers[1], 2)],
+        )?;
+        assert_eq!(
+            unsigned.missing_signers(),
+            vec![
+                signers[1].public_address.to_string(),
+                signers[0].public_address.to_string()
+            ]
+        );
+
+        let json = serde_json::to_string(&unsigned)?;
+        let mut remote: MultiSignerTx = serde_json::from_str(&json)?;
+        remote.sign(&signers[0])?;
+        unsigned.sign(&signers[1])?;
+        assert!(unsigned.signed().is_err());
+        unsigned.merge(&remote)?;
+        assert!(unsigned.missing_signers().is_empty());
+
+        let decoded = decode_tx(&unsigned.signed()?.to_bytes()?)?;
+        let public_keys: Vec<prost_types::Any> =
+            vec![signers[1].public_key.into(), signers[0].public_key.into()];
+        assert_eq!(
+            decoded
+                .signer_infos
+                .iter()
+                .map(|info| info.public_key.clone().unwrap_or_default())
+                .collect::<Vec<prost_types::Any>>(),
+            public_keys
+        );
+        assert_eq!(
+            decoded
+                .signer_infos
+                .iter()
+                .map(|info| info.sequence)
+                .collect::<Vec<u64>>(),
+            vec![20, 10]
+        );
+        assert_eq!(decoded.signatures.len(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn reject_bad_signatures() -> Result<(), CosmosClient> {
+        let signers = signers()?;
+        let tx = Cosmos::build()
+            .add_msg(send(&signers[0], &signers[1])?)
+            .add_msg(send(&signers[1], &signers[0])?);
+        let mut unsigned = MultiSignerTx::new(
+            &tx.finish(),
+            fee()?,
+            "cosmoshub-4",
+            vec![data(&signers[0], 1), data(&signers[1], 2)],
+        )?;
+
+        assert!(unsigned.sign(&signers[2]).is_err());
+
+        let mut other = unsigned.clone();
+        other.sign(&signers[0])?;
+        let signature = other.signatures[0].clone().unwrap_or_default();
+        assert!(unsigned
+            .add_signature(signers[1].public_address.to_string().as_str(), &signature)
+            .is_err());
+        unsigned.add_signature(signers[0].public_address.to_string().as_str(), &signature)?;
+        assert_eq!(
+            unsigned.missing_signers(),
+            vec![signers[1].public_address.to_string()]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn signers_order() -> Result<(), CosmosClient> {
+        let signers = signers()?;
+        let addresses: Vec<String> = signers
+            .iter()
+            .map(|signer| signer.public_address.to_string())
+            .collect();
+        let msgs = vec![
+            send(&signers[1], &signers[0])?,
+            send(&signers[0], &signers[1])?,
+            send(&signers[1], &signers[2])?,
+        ];
+
+        assert_eq!(
+            tx_signers(&msgs, "")?,
+            vec![addresses[1].clone(), addresses[0].clone()]
+        );
+        assert_eq!(
+            tx_signers(&msgs, addresses[2].as_str())?,
+            vec![
+                addresses[1].clone(),
+                addresses[0].clone(),
+                addresses[2].clone()
+            ]
+        );
+        assert_eq!(
+            tx_signers(&msgs, addresses[0].as_str())?,
+            vec![addresses[1].clone(), addresses[0].clone()]
+        );
+
+        let missing = MultiSignerTx::new(
+            &msgs
+                .into_iter()
+                .fold(Cosmos::build(), Cosmos::add_msg)
+                .finish(),
+            fee()?,
+            "cosmoshub-4",
+            vec![data(&signers[0], 1)],
+        );
+        assert!(matches!(missing, Err(CosmosClient::InvalidSigners(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn create_validator_signers() -> Result<(), CosmosClient> {
+        let signers = signers()?;
+        let create = |delegator: &Signer, validator: &Signer| {
+            Ok::<_, CosmosClient>(prost_types::Any {
+                type_url: "/cosmos.staking.v1beta1.MsgCreateValidator".to_string(),
+                value: MsgCreateValidator {
+                    delegator_address: delegator.public_address.to_string(),
+                    validator_address: cosmrs::AccountId::new(
+                        "cosmosvaloper",
+                        &validator.public_address.to_bytes(),
+                    )?
+                    .to_string(),
+                    ..MsgCreateValidator::default()
+                }
+                .encode_to_vec(),
+            })
+        };
+
+        assert_eq!(
+            msg_signers(&create(&signers[0], &signers[0])?)?,
+            vec![signers[0].public_address.to_string()]
+        );
+        assert_eq!(
+            msg_signers(&create(&signers[0], &signers[1])?)?,
+            vec![
+                signers[0].public_address.to_string(),
+                signers[1].public_address.to_string()
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn ethermint_signer() -> Result<(), CosmosClient> {
+        let cosmos = signers()?.remove(0);
+        let eth = Signer::from_pkey_with_key_type(
+            KeyType::EthSecp256k1,
+            hex::encode([9u8; 32]).as_str(),
+            "cosmos",
+            30,
+            "0.025uatom".parse()?,
+        )?;
+        let tx = Cosmos::build()
+            .add_msg(send(&eth, &cosmos)?)
+            .add_msg(send(&cosmos, &eth)?);
+        let mut unsigned = MultiSignerTx::new(
+            &tx.finish(),
+            fee()?,
+            "cosmoshub-4",
+            vec![
+                data(&cosmos, 1),
+                SignerData::new_with_key_type(
+                    KeyType::EthSecp256k1,
+                    &eth.public_key,
+                    eth.public_address.to_string().as_str(),
+                    2,
+                    0,
+                ),
+            ],
+        )?;
+
+        // same key signing the sha256 hash like a Cosmos SDK key
+        let sha256 = Signer::from_pkey(
+            hex::encode([9u8; 32]).as_str(),
+            "cosmos",
+            30,
+            "0.025uatom".parse()?,
+        )?;
+        assert!(unsigned.sign(&sha256).is_err());
+        let signature = sha256.private_key.sign(&unsigned.sign_bytes(0)?)?;
+        assert!(unsigned
+            .add_signature(
+                eth.public_address.to_string().as_str(),
+                &STANDARD.encode(signature.to_vec())
+            )
+            .is_err());
+
+        let mut remote: MultiSignerTx = serde_json::from_str(&serde_json::to_string(&unsigned)?)?;
+        remote.sign(&eth)?;
+        unsigned.sign(&cosmos)?;
+        unsigned.merge(&remote)?;
+
+        let decoded = decode_tx(&unsigned.signed()?.to_bytes()?)?;
+        assert_eq!(
+            decoded
+                .signer_infos
+                .iter()
+                .map(|info| info.public_key.clone().unwrap_or_default().type_url)
+                .collect::<Vec<String>>(),
+            vec![
+                "/ethermint.crypto.v1.ethsecp256k1.PubKey",
+                "/cosmos.crypto.secp256k1.PubKey"
+            ]
+        );
+        Ok(())
+    }
+}
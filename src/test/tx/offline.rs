@@ -0,0 +1,207 @@
+#[cfg(test)]
+mod offline_tests {
+    use crate::error::CosmosClient;
+    use crate::proto::cosmos::gov::v1::MsgVote;
+    use crate::signer::Signer;
+    use crate::tx::decode::decode_tx;
+    use crate::tx::offline::{SignedTx, UnsignedTx};
+    use crate::tx::{Cosmos, SignMode};
+    use base64::engine::general_purpose::STANDARD;
+    use base64::Engine;
+    use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
+    use cosmos_sdk_proto::cosmos::base::v1beta1::Coin;
+    use cosmos_sdk_proto::cosmwasm::wasm::v1::{AccessConfig, AccessType, MsgStoreCode};
+    use cosmos_sdk_proto::traits::MessageExt;
+    use cosmrs::tx::Fee;
+    use prost::Message;
+    use serde_json::json;
+
+    fn signer() -> Result<Signer, CosmosClient> {
+        Signer::from_pkey(
+            "0101010101010101010101010101010101010101010101010101010101010101",
+            "cosmos",
+            30,
+            "0.025uatom".parse()?,
+        )
+    }
+
+    fn unsigned(signer: &Signer) -> Result<UnsignedTx, CosmosClient> {
+        let tx = Cosmos::build()
+            .memo("cold storage")
+            .add_msg(
+                MsgSend {
+                    from_address: signer.public_address.to_string(),
+                    to_address: signer.public_address.to_string(),
+                    amount: vec![Coin {
+                        denom: "uatom".to_string(),
+                        amount: "1000".to_string(),
+                    }],
+                }
+                .to_any()?,
+            )
+            .add_msg(
+                MsgVote {
+                    proposal_id: 42,
+                    voter: signer.public_address.to_string(),
+                    option: 1,
+                    metadata: String::new(),
+                }
+                .to_any()?,
+            );
+        let fee = Fee::from_amount_and_gas(
+            cosmrs::Coin {
+                amount: 5000,
+                denom: "uatom".parse()?,
+            },
+            200_000u64,
+        );
+
+        UnsignedTx::new(&tx.finish(), fee, "cosmoshub-4", 12, 7)
+    }
+
+    #[test]
+    fn export_cli_json() -> Result<(), CosmosClient> {
+        let signer = signer()?;
+        let unsigned = unsigned(&signer)?;
+        let address = signer.public_address.to_string();
+
+        assert_eq!(
+            unsigned.tx,
+            json!({
+                "body": {
+                    "messages": [
+                        {
+                            "@type": "/cosmos.bank.v1beta1.MsgSend",
+                            "from_address": address,
+                            "to_address": address,
+                            "amount": [{ "denom": "uatom", "amount": "1000" }],
+                        },
+                        {
+                            "@type": "/cosmos.gov.v1.MsgVote",
+                            "proposal_id": "42",
+                            "voter": address,
+                            "option": "VOTE_OPTION_YES",
+                            "metadata": "",
+                        },
+                    ],
+                    "memo": "cold storage",
+                    "timeout_height": "0",
+                    "extension_options": [],
+                    "non_critical_extension_options": [],
+                },
+                "auth_info": {
+                    "signer_infos": [],
+                    "fee": {
+                        "amount": [{ "denom": "uatom", "amount": "5000" }],
+                        "gas_limit": "200000",
+                        "payer": "",
+                        "granter": "",
+                    },
+                },
+                "signatures": [],
+            })
+        );
+
+        let file = serde_json::to_string(&unsigned)?;
+        assert_eq!(serde_json::from_str::<UnsignedTx>(&file)?, unsigned);
+        Ok(())
+    }
+
+    #[test]
+    fn sign_offline() -> Result<(), CosmosClient> {
+        let signer = signer()?;
+        let unsigned = unsigned(&signer)?;
+
+        for sign_mode in [SignMode::Direct, SignMode::AminoJson] {
+            let signed = unsigned.sign(&signer, sign_mode)?;
+            let decoded = decode_tx(&signed.to_bytes()?)?;
+
+            assert_eq!(signed.hash, decoded.hash);
+            assert_eq!(decoded.body.memo, "cold storage");
+            assert_eq!(decoded.signer_infos[0].sequence, 7);
+            assert_eq!(decoded.signatures.len(), 1);
+            assert_eq!(signed.tx["auth_info"]["signer_infos"][0]["sequence"], "7");
+            assert_eq!(
+                signed.tx["auth_info"]["signer_infos"][0]["public_key"]["@type"],
+                "/cosmos.crypto.secp256k1.PubKey"
+            );
+            assert_eq!(signed.tx["signatures"].as_array().unwrap().len(), 1);
+
+            let file = serde_json::to_string(&signed)?;
+            assert_eq!(serde_json::from_str::<SignedTx>(&file)?, signed);
+        }
+
+        let direct = unsigned.sign(&signer, SignMode::Direct)?;
+        assert_eq!(
+            direct.tx["auth_info"]["signer_infos"][0]["mode_info"]["single"]["mode"],
+            "SIGN_MODE_DIRECT"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn tampered_file() -> Result<(), CosmosClient> {
+        let signer = signer()?;
+        let unsigned = unsigned(&signer)?;
+
+        // harmless `tx` shown to the operator, other bytes signed
+        let mut tampered = unsigned.clone();
+        tampered.tx["body"]["messages"][0]["amount"][0]["amount"] = json!("1");
+        assert!(matches!(
+            tampered.sign(&signer, SignMode::Direct),
+            Err(CosmosClient::UnsignedTxMismatch)
+        ));
+
+        let mut tampered = unsigned;
+        tampered.body_bytes = STANDARD.encode(
+            Cosmos::build()
+                .memo("drained")
+                .finish()
+                .into_proto()
+                .encode_to_vec(),
+        );
+        assert!(matches!(
+            tampered.sign(&signer, SignMode::AminoJson),
+            Err(CosmosClient::UnsignedTxMismatch)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn store_code_permission() -> Result<(), CosmosClient> {
+        let signer = signer()?;
+        let address = signer.public_address.to_string();
+        let body = Cosmos::build()
+            .add_msg(
+                MsgStoreCode {
+                    sender: address.clone(),
+                    wasm_byte_code: vec![0, 97, 115, 109],
+                    instantiate_permission: Some(AccessConfig {
+                        permission: AccessType::AnyOfAddresses as i32,
+                        address: String::new(),
+                        addresses: vec![address.clone()],
+                    }),
+                }
+                .to_any()?,
+            )
+            .finish();
+        let fee = Fee::from_amount_and_gas(
+            cosmrs::Coin {
+                amount: 5000,
+                denom: "uatom".parse()?,
+            },
+            2_000_000u64,
+        );
+        let unsigned = UnsignedTx::new(&body, fee, "cosmoshub-4", 12, 7)?;
+
+        assert_eq!(
+            unsigned.tx["body"]["messages"][0]["instantiate_permission"],
+            json!({
+                "permission": "ACCESS_TYPE_ANY_OF_ADDRESSES",
+                "address": "",
+                "addresses": [address],
+            })
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,147 @@
+#[cfg(test)]
+mod validate_tests {
+    use crate::error::CosmosClient;
+    use crate::proto::cosmos::gov::v1::MsgSubmitProposal;
+    use crate::test::coin;
+    use crate::tx::validate::{is_valid_denom, validate, violations, Limits, Violation};
+    use crate::tx::{Cosmos, SignMode};
+    use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
+    use cosmos_sdk_proto::cosmos::base::v1beta1::Coin;
+    use cosmos_sdk_proto::traits::MessageExt;
+    use cosmrs::tx::Fee;
+
+    fn limits() -> Limits {
+        Limits {
+            max_memo_characters: 10,
+            tx_sig_limit: 7,
+            max_tx_bytes: 1_000,
+            min_deposit: vec![coin("1000", "uatom")],
+        }
+    }
+
+    fn fee() -> Result<Fee, CosmosClient> {
+        Ok(Fee::from_amount_and_gas(
+            cosmrs::Coin {
+                amount: 5000,
+                denom: "uatom".parse()?,
+            },
+            200_000u64,
+        ))
+    }
+
+    fn send(amount: Vec<Coin>) -> Result<prost_types::Any, CosmosClient> {
+        Ok(MsgSend {
+            from_address: "cosmos1a".to_string(),
+            to_address: "cosmos1b".to_string(),
+            amount,
+        }
+        .to_any()?)
+    }
+
+    #[test]
+    fn valid_tx() -> Result<(), CosmosClient> {
+        let tx = Cosmos::build()
+            .memo("payout")
+            .add_msg(send(vec![coin("10", "ibc/ABC"), coin("10", "uatom")])?);
+
+        validate(&tx.finish(), &fee()?, 1, SignMode::Direct, &limits())?;
+        validate(
+            &tx.finish(),
+            &fee()?,
+            1,
+            SignMode::Direct,
+            &Limits::default(),
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn chain_limits() -> Result<(), CosmosClient> {
+        let tx = Cosmos::build()
+            .memo("a memo longer than 10 characters")
+            .add_msg(send(vec![coin("10", "uatom")])?);
+
+        let found = violations(&tx.finish(), &fee()?, 8, SignMode::Direct, &limits())?;
+        assert_eq!(found.len(), 2);
+        assert_eq!(
+            found[0],
+            Violation::MemoTooLong {
+                length: 32,
+                max: 10
+            }
+        );
+        assert_eq!(found[1], Violation::TooManySignatures { count: 8, max: 7 });
+
+        let tx = Cosmos::build().memo(&"x".repeat(2_000));
+        let found = violations(
+            &tx.finish(),
+            &fee()?,
+            1,
+            SignMode::Direct,
+            &Limits {
+                max_tx_bytes: 1_000,
+                ..Limits::default()
+            },
+        )?;
+        assert!(matches!(
+            found.as_slice(),
+            [Violation::TxTooLarge { size, max: 1_000 }] if *size > 2_000
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn coins_and_deposit() -> Result<(), CosmosClient> {
+        let tx = Cosmos::build()
+            .add_msg(send(vec![coin("10", "uatom"), coin("0", "ibc/ABC")])?)
+            .add_msg(send(vec![coin("abc", "1atom")])?)
+            .add_msg(
+                MsgSubmitProposal {
+                    messages: vec![],
+                    initial_deposit: vec![coin("999", "uatom")],
+                    proposer: "cosmos1a".to_string(),
+                    metadata: String::new(),
+                    title: String::new(),
+                    summary: String::new(),
+                }
+                .to_any()?,
+            );
+
+        let found = violations(&tx.finish(), &fee()?, 1, SignMode::Direct, &limits())?;
+        assert_eq!(
+            found,
+            vec![
+                Violation::InvalidAmount {
+                    denom: "ibc/ABC".to_string(),
+                    amount: "0".to_string(),
+                },
+                Violation::UnsortedCoins(vec![coin("10", "uatom"), coin("0", "ibc/ABC")]),
+                Violation::InvalidDenom("1atom".to_string()),
+                Violation::InvalidAmount {
+                    denom: "1atom".to_string(),
+                    amount: "abc".to_string(),
+                },
+                Violation::DepositTooLow {
+                    deposit: vec![coin("999", "uatom")],
+                    min_deposit: vec![coin("1000", "uatom")],
+                },
+            ]
+        );
+
+        let result = validate(&tx.finish(), &fee()?, 1, SignMode::Direct, &limits());
+        assert!(matches!(result, Err(CosmosClient::InvalidTx(found)) if found.len() == 5));
+        Ok(())
+    }
+
+    #[test]
+    fn denoms() {
+        assert!(is_valid_denom("uatom"));
+        assert!(is_valid_denom(
+            "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2"
+        ));
+        assert!(is_valid_denom("factory/osmo1abc/token.v2"));
+        assert!(!is_valid_denom("ab"));
+        assert!(!is_valid_denom("1atom"));
+        assert!(!is_valid_denom("u atom"));
+    }
+}
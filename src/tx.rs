@@ -1,32 +1,433 @@
-use cosmrs::tx::{Body, BodyBuilder};
+pub mod amino;
+pub mod balance;
+pub mod decode;
+pub mod eip712;
+pub mod json;
+pub mod multi;
+pub mod offline;
+pub mod validate;
+
+use crate::error::CosmosClient;
+use crate::signer::TxSigner;
+use crate::tx::amino::StdSignDoc;
+use cosmos_sdk_proto::cosmos::base::v1beta1::Coin;
+use cosmos_sdk_proto::cosmos::crypto::secp256k1;
+use cosmos_sdk_proto::cosmos::tx::signing;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::{mode_info, AuthInfo, ModeInfo, TxRaw};
+use cosmrs::crypto::secp256k1::SigningKey;
+use cosmrs::crypto::PublicKey;
+use cosmrs::tendermint::block::Height;
+use cosmrs::tendermint::chain;
+use cosmrs::tx::{Body, Fee, SignDoc, SignerInfo, SignerPublicKey};
+use cosmrs::AccountId;
+use prost::Message;
 use prost_types::Any;
+use std::str::FromStr;
+
+/// How the signer signs the tx.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SignMode {
+    /// `SIGN_MODE_DIRECT`, over the protobuf `SignDoc`.
+    #[default]
+    Direct,
+    /// `SIGN_MODE_LEGACY_AMINO_JSON`, over the amino JSON `StdSignDoc`.
+    AminoJson,
+}
 
 pub struct Cosmos {
-    tx: BodyBuilder,
+    body: Body,
+    pub(crate) timeout_blocks: Option<u64>,
+    pub(crate) fee: Option<Coin>,
+    pub(crate) max_fee: Option<Coin>,
+    pub(crate) fee_granter: Option<String>,
+    pub(crate) fee_payer: Option<String>,
+    pub(crate) sign_mode: SignMode,
 }
 
 impl Cosmos {
     #[must_use]
     pub fn build() -> Self {
         Cosmos {
-            tx: BodyBuilder::new(),
+            body: Body::default(),
+            timeout_blocks: None,
+            fee: None,
+            max_fee: None,
+            fee_granter: None,
+            fee_payer: None,
+            sign_mode: SignMode::Direct,
         }
     }
 
     #[must_use]
     pub fn memo(mut self, memo: &str) -> Self {
-        self.tx.memo(memo.to_string());
+        self.body.memo = memo.to_string();
         self
     }
 
     #[must_use]
     pub fn add_msg(mut self, msg: Any) -> Self {
-        self.tx.msg(msg);
+        self.body.messages.push(msg);
+        self
+    }
+
+    /// Pay exactly `fee` instead of computing it from the signer gas price.
+    #[must_use]
+    pub fn fee(mut self, fee: Coin) -> Self {
+        self.fee = Some(fee);
+        self
+    }
+
+    /// Refuse to sign if the fee would be above `max_fee`.
+    #[must_use]
+    pub fn max_fee(mut self, max_fee: Coin) -> Self {
+        self.max_fee = Some(max_fee);
+        self
+    }
+
+    /// Have the fee paid by `granter` through a feegrant allowance given to
+    /// the signer.
+    #[must_use]
+    pub fn fee_granter(mut self, granter: &str) -> Self {
+        self.fee_granter = Some(granter.to_string());
+        self
+    }
+
+    /// Have the fee paid by `payer`, who must also sign the tx. `Rpc` only
+    /// signs for its attached signer, a tx paid by another account is signed
+    /// with `MultiSignerTx`.
+    #[must_use]
+    pub fn fee_payer(mut self, payer: &str) -> Self {
+        self.fee_payer = Some(payer.to_string());
+        self
+    }
+
+    #[must_use]
+    pub fn sign_mode(mut self, sign_mode: SignMode) -> Self {
+        self.sign_mode = sign_mode;
+        self
+    }
+
+    /// Messages added to this tx so far.
+    #[must_use]
+    pub fn msgs(&self) -> &[Any] {
+        &self.body.messages
+    }
+
+    #[must_use]
+    pub fn clear_msgs(mut self) -> Self {
+        self.body.messages.clear();
+        self
+    }
+
+    /// The tx will be rejected once the chain is past `height`.
+    #[must_use]
+    pub fn timeout_height(mut self, height: impl Into<Height>) -> Self {
+        self.body.timeout_height = height.into();
+        self.timeout_blocks = None;
+        self
+    }
+
+    /// The tx will be rejected once the chain is more than `blocks` blocks
+    /// past the height at signing time.
+    #[must_use]
+    pub fn timeout_blocks(mut self, blocks: u64) -> Self {
+        self.body.timeout_height = Height::from(0u8);
+        self.timeout_blocks = Some(blocks);
         self
     }
 
+    /// Add an extension option, the tx is rejected by chains that cannot
+    /// handle it.
+    #[must_use]
+    pub fn extension_option(mut self, option: Any) -> Self {
+        self.body.extension_options.push(option);
+        self
+    }
+
+    /// Add an extension option that is ignored by chains that cannot handle
+    /// it.
+    #[must_use]
+    pub fn non_critical_extension_option(mut self, option: Any) -> Self {
+        self.body.non_critical_extension_options.push(option);
+        self
+    }
+
+    /// The tx body. A timeout set with `timeout_blocks` is not applied, use
+    /// `finish_at` for it.
     #[must_use]
     pub fn finish(&self) -> Body {
-        self.tx.finish()
+        self.body.clone()
+    }
+
+    /// The tx body with a `timeout_blocks` timeout resolved against
+    /// `current_height`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the timeout height overflows
+    pub fn finish_at(&self, current_height: u64) -> Result<Body, CosmosClient> {
+        let mut body = self.body.clone();
+        if let Some(blocks) = self.timeout_blocks {
+            body.timeout_height = Height::try_from(current_height.saturating_add(blocks))?;
+        }
+
+        Ok(body)
+    }
+
+    /// Size in bytes of the protobuf encoded tx body.
+    #[must_use]
+    pub fn body_size(&self) -> usize {
+        self.body.clone().into_proto().encoded_len()
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if `fee` is above the max fee set on this tx, or
+    /// if it is not in the same denom
+    pub fn check_max_fee(&self, fee: &Coin) -> Result<(), CosmosClient> {
+        let Some(max_fee) = &self.max_fee else {
+            return Ok(());
+        };
+
+        if fee.denom != max_fee.denom || fee.amount.parse::<u128>()? > max_fee.amount.parse()? {
+            return Err(CosmosClient::FeeAboveMax {
+                fee: format!("{}{}", fee.amount, fee.denom),
+                max_fee: format!("{}{}", max_fee.amount, max_fee.denom),
+            });
+        }
+        Ok(())
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the fee payer set on this tx is not `signer`, the
+    /// only one signing it
+    pub fn check_fee_payer(&self, signer: &str) -> Result<(), CosmosClient> {
+        match &self.fee_payer {
+            Some(payer) if payer != signer => Err(CosmosClient::InvalidSigners(format!(
+                "fee payer {payer} must sign the tx along with {signer}, use `MultiSignerTx`"
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Build the tx `Fee` with the granter and payer set on this tx.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the granter or the payer is not a bech32 address
+    pub fn build_fee(
+        &self,
+        amount: Vec<cosmrs::Coin>,
+        gas_limit: u64,
+    ) -> Result<Fee, CosmosClient> {
+        let account = |address: &Option<String>| {
+            address
+                .as_deref()
+                .map(AccountId::from_str)
+                .transpose()
+                .map_err(CosmosClient::ErrorReport)
+        };
+
+        Ok(Fee {
+            amount,
+            gas_limit,
+            payer: account(&self.fee_payer)?,
+            granter: account(&self.fee_granter)?,
+        })
+    }
+}
+
+/// Sign `body` with `key` and build the raw tx.
+///
+/// # Errors
+///
+/// Will return `Err` if :
+/// - `chain_id` is invalid
+/// - the body has no amino JSON encoding for `SignMode::AminoJson`
+/// - there is a sign or encode error
+pub fn sign_body(
+    key: &SigningKey,
+    sign_mode: SignMode,
+    body: &Body,
+    fee: Fee,
+    chain_id: &str,
+    account_number: u64,
+    sequence: u64,
+) -> Result<Vec<u8>, CosmosClient> {
+    let payload = SignPayload::new(
+        SignerPublicKey::Single(key.public_key()),
+        sign_mode,
+        body,
+        fee,
+        chain_id,
+        account_number,
+        sequence,
+    )?;
+    let signature = key.sign(&payload.sign_bytes)?;
+
+    Ok(payload.into_tx(signature.to_vec()))
+}
+
+/// Sign `body` with `signer`, which may hold its key outside the process,
+/// and build the raw tx.
+///
+/// # Errors
+///
+/// Will return `Err` if :
+/// - `chain_id` is invalid
+/// - the body has no amino JSON encoding for `SignMode::AminoJson`
+/// - the signer fails to sign
+/// - there is an encode error
+pub async fn sign_body_with(
+    signer: &dyn TxSigner,
+    sign_mode: SignMode,
+    body: &Body,
+    fee: Fee,
+    chain_id: &str,
+    account_number: u64,
+    sequence: u64,
+) -> Result<Vec<u8>, CosmosClient> {
+    let payload = SignPayload::new(
+        signer.signer_public_key(),
+        sign_mode,
+        body,
+        fee,
+        chain_id,
+        account_number,
+        sequence,
+    )?;
+    let signature = signer.sign_bytes(&payload.sign_bytes).await?;
+
+    Ok(payload.into_tx(signature))
+}
+
+/// Bytes a signer signs for a tx with a single signer, and the raw tx the
+/// signature goes in.
+pub struct SignPayload {
+    /// `SignDoc` or amino JSON `StdSignDoc` bytes, signed with secp256k1
+    /// over their sha256 hash.
+    pub sign_bytes: Vec<u8>,
+    body_bytes: Vec<u8>,
+    auth_info_bytes: Vec<u8>,
+}
+
+impl SignPayload {
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - `chain_id` is invalid
+    /// - the body has no amino JSON encoding for `SignMode::AminoJson`
+    /// - there is an encode error
+    pub fn new(
+        public_key: SignerPublicKey,
+        sign_mode: SignMode,
+        body: &Body,
+        fee: Fee,
+        chain_id: &str,
+        account_number: u64,
+        sequence: u64,
+    ) -> Result<Self, CosmosClient> {
+        let mode = match sign_mode {
+            SignMode::Direct => signing::v1beta1::SignMode::Direct,
+            SignMode::AminoJson => signing::v1beta1::SignMode::LegacyAminoJson,
+        };
+        let signer_info = SignerInfo {
+            public_key: Some(public_key),
+            mode_info: cosmrs::tx::ModeInfo::single(mode),
+            sequence,
+        };
+
+        match sign_mode {
+            SignMode::Direct => {
+                let sign_doc = SignDoc::new(
+                    body,
+                    &signer_info.auth_info(fee),
+                    &chain::Id::from_str(chain_id)?,
+                    account_number,
+                )?;
+
+                Ok(SignPayload {
+                    sign_bytes: sign_doc.clone().into_bytes()?,
+                    body_bytes: sign_doc.body_bytes,
+                    auth_info_bytes: sign_doc.auth_info_bytes,
+                })
+            }
+            SignMode::AminoJson => {
+                let sign_doc = StdSignDoc::new(body, &fee, chain_id, account_number, sequence)?;
+
+                Ok(SignPayload {
+                    sign_bytes: sign_doc.to_bytes()?,
+                    body_bytes: body.clone().into_bytes()?,
+                    auth_info_bytes: signer_info.auth_info(fee).into_bytes()?,
+                })
+            }
+        }
+    }
+
+    /// Raw tx signed with `signature`.
+    #[must_use]
+    pub fn into_tx(self, signature: Vec<u8>) -> Vec<u8> {
+        TxRaw {
+            body_bytes: self.body_bytes,
+            auth_info_bytes: self.auth_info_bytes,
+            signatures: vec![signature],
+        }
+        .encode_to_vec()
+    }
+}
+
+/// Compressed secp256k1 key the Cosmos SDK stands in for accounts without a
+/// public key when simulating.
+pub const SIMULATION_PUBLIC_KEY: &str =
+    "035AD6810A47F073553FF30D2FCC7E0D3B1C0B74B61A1AAA2582344037151E143A";
+
+/// Raw tx to simulate `body` for an account with `public_key` and
+/// `sequence`, with an empty signature. The placeholder
+/// [`SIMULATION_PUBLIC_KEY`] is used when the account has no public key yet.
+///
+/// # Errors
+///
+/// Will return `Err` if there is an encode error
+pub fn simulation_tx(
+    body: &Body,
+    fee: Fee,
+    public_key: Option<Any>,
+    sequence: u64,
+    sign_mode: SignMode,
+) -> Result<Vec<u8>, CosmosClient> {
+    let public_key = match public_key {
+        Some(public_key) => public_key,
+        None => Any {
+            type_url: PublicKey::SECP256K1_TYPE_URL.to_string(),
+            value: secp256k1::PubKey {
+                key: hex::decode(SIMULATION_PUBLIC_KEY)?,
+            }
+            .encode_to_vec(),
+        },
+    };
+    let mode = match sign_mode {
+        SignMode::Direct => signing::v1beta1::SignMode::Direct,
+        SignMode::AminoJson => signing::v1beta1::SignMode::LegacyAminoJson,
+    };
+    let auth_info = AuthInfo {
+        signer_infos: vec![cosmos_sdk_proto::cosmos::tx::v1beta1::SignerInfo {
+            public_key: Some(public_key),
+            mode_info: Some(ModeInfo {
+                sum: Some(mode_info::Sum::Single(mode_info::Single {
+                    mode: mode.into(),
+                })),
+            }),
+            sequence,
+        }],
+        fee: Some(fee.into()),
+        ..AuthInfo::default()
+    };
+
+    Ok(TxRaw {
+        body_bytes: body.clone().into_bytes()?,
+        auth_info_bytes: auth_info.encode_to_vec(),
+        signatures: vec![vec![]],
     }
+    .encode_to_vec())
 }
@@ -0,0 +1,426 @@
+use crate::error::CosmosClient;
+use crate::tx::decode::{decode_msg, Msg};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use cosmos_sdk_proto::cosmos::base::v1beta1::Coin;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::TextProposal;
+use cosmos_sdk_proto::cosmos::tx::signing::v1beta1::SignMode;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::TxRaw;
+use cosmos_sdk_proto::cosmwasm::wasm::v1::{AccessConfig, AccessType};
+use cosmrs::crypto::secp256k1::SigningKey;
+use cosmrs::tx::{Body, Fee, ModeInfo, SignerInfo, SignerPublicKey};
+use prost::Message;
+use prost_types::Any;
+use serde_json::{json, Map, Value};
+
+/// Legacy amino JSON sign doc, signed by `SIGN_MODE_LEGACY_AMINO_JSON`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StdSignDoc {
+    pub account_number: u64,
+    pub chain_id: String,
+    pub fee: Fee,
+    pub memo: String,
+    /// Messages in their amino JSON form, see [`msg_to_amino`].
+    pub msgs: Vec<Value>,
+    pub sequence: u64,
+    pub timeout_height: u64,
+}
+
+impl StdSignDoc {
+    /// # Errors
+    ///
+    /// Will return `Err` if one of the body messages has no amino JSON
+    /// encoding
+    pub fn new(
+        body: &Body,
+        fee: &Fee,
+        chain_id: &str,
+        account_number: u64,
+        sequence: u64,
+    ) -> Result<Self, CosmosClient> {
+        Ok(StdSignDoc {
+            account_number,
+            chain_id: chain_id.to_string(),
+            fee: fee.clone(),
+            memo: body.memo.clone(),
+            msgs: body
+                .messages
+                .iter()
+                .map(msg_to_amino)
+                .collect::<Result<Vec<Value>, CosmosClient>>()?,
+            sequence,
+            timeout_height: body.timeout_height.value(),
+        })
+    }
+
+    #[must_use]
+    pub fn to_json(&self) -> Value {
+        let mut fee = json!({
+            "amount": self.fee.amount.iter().map(|coin| json!({
+                "amount": coin.amount.to_string(),
+                "denom": coin.denom.to_string(),
+            })).collect::<Vec<Value>>(),
+            "gas": self.fee.gas_limit.to_string(),
+        });
+        if let Some(granter) = &self.fee.granter {
+            fee["granter"] = granter.to_string().into();
+        }
+        if let Some(payer) = &self.fee.payer {
+            fee["payer"] = payer.to_string().into();
+        }
+
+        let mut doc = json!({
+            "account_number": self.account_number.to_string(),
+            "chain_id": self.chain_id,
+            "fee": fee,
+            "memo": self.memo,
+            "msgs": self.msgs,
+            "sequence": self.sequence.to_string(),
+        });
+        if self.timeout_height != 0 {
+            doc["timeout_height"] = self.timeout_height.to_string().into();
+        }
+
+        doc
+    }
+
+    /// Bytes to sign : the JSON with sorted keys, no whitespace and `&`,
+    /// `<`, `>` escaped, like cosmjs `serializeSignDoc`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the JSON cannot be serialized
+    pub fn to_bytes(&self) -> Result<Vec<u8>, CosmosClient> {
+        let json = serde_json::to_string(&sorted(self.to_json()))?;
+
+        Ok(json
+            .replace('&', "\\u0026")
+            .replace('<', "\\u003c")
+            .replace('>', "\\u003e")
+            .into_bytes())
+    }
+
+    /// Sign the doc with `key` and build the raw tx.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if there is a sign or encode error
+    pub fn sign(&self, body: &Body, key: &SigningKey) -> Result<Vec<u8>, CosmosClient> {
+        let auth_info = SignerInfo {
+            public_key: Some(SignerPublicKey::Single(key.public_key())),
+            mode_info: ModeInfo::single(SignMode::LegacyAminoJson),
+            sequence: self.sequence,
+        }
+        .auth_info(self.fee.clone());
+        let signature = key.sign(&self.to_bytes()?)?;
+
+        Ok(TxRaw {
+            body_bytes: body.clone().into_bytes()?,
+            auth_info_bytes: auth_info.into_bytes()?,
+            signatures: vec![signature.to_vec()],
+        }
+        .encode_to_vec())
+    }
+}
+
+/// Amino JSON `{"type": ..., "value": ...}` form of `msg`, as produced by
+/// cosmjs amino converters.
+///
+/// # Errors
+///
+/// Will return `Err` if `msg` fails to decode or has no amino JSON encoding
+/// in this crate
+#[allow(clippy::too_many_lines)]
+pub fn msg_to_amino(msg: &Any) -> Result<Value, CosmosClient> {
+    let unsupported = || CosmosClient::UnsupportedAminoMsg(msg.type_url.clone());
+
+    let (kind, value) = match decode_msg(msg)? {
+        Msg::MsgSend(msg) => (
+            "cosmos-sdk/MsgSend",
+            json!({
+                "from_address": msg.from_address,
+                "to_address": msg.to_address,
+                "amount": coins(&msg.amount),
+            }),
+        ),
+        Msg::MsgMultiSend(msg) => (
+            "cosmos-sdk/MsgMultiSend",
+            json!({
+                "inputs": msg.inputs.iter().map(|input| json!({
+                    "address": input.address,
+                    "coins": coins(&input.coins),
+                })).collect::<Vec<Value>>(),
+                "outputs": msg.outputs.iter().map(|output| json!({
+                    "address": output.address,
+                    "coins": coins(&output.coins),
+                })).collect::<Vec<Value>>(),
+            }),
+        ),
+        Msg::MsgDelegate(msg) => (
+            "cosmos-sdk/MsgDelegate",
+            json!({
+                "delegator_address": msg.delegator_address,
+                "validator_address": msg.validator_address,
+                "amount": msg.amount.as_ref().map(coin),
+            }),
+        ),
+        Msg::MsgUndelegate(msg) => (
+            "cosmos-sdk/MsgUndelegate",
+            json!({
+                "delegator_address": msg.delegator_address,
+                "validator_address": msg.validator_address,
+                "amount": msg.amount.as_ref().map(coin),
+            }),
+        ),
+        Msg::MsgBeginRedelegate(msg) => (
+            "cosmos-sdk/MsgBeginRedelegate",
+            json!({
+                "delegator_address": msg.delegator_address,
+                "validator_src_address": msg.validator_src_address,
+                "validator_dst_address": msg.validator_dst_address,
+                "amount": msg.amount.as_ref().map(coin),
+            }),
+        ),
+        Msg::MsgCancelUnbondingDelegation(msg) => (
+            "cosmos-sdk/MsgCancelUnbondingDelegation",
+            json!({
+                "delegator_address": msg.delegator_address,
+                "validator_address": msg.validator_address,
+                "amount": msg.amount.as_ref().map(coin),
+                "creation_height": msg.creation_height.to_string(),
+            }),
+        ),
+        Msg::MsgSetWithdrawAddress(msg) => (
+            "cosmos-sdk/MsgModifyWithdrawAddress",
+            json!({
+                "delegator_address": msg.delegator_address,
+                "withdraw_address": msg.withdraw_address,
+            }),
+        ),
+        Msg::MsgWithdrawDelegatorReward(msg) => (
+            "cosmos-sdk/MsgWithdrawDelegationReward",
+            json!({
+                "delegator_address": msg.delegator_address,
+                "validator_address": msg.validator_address,
+            }),
+        ),
+        Msg::MsgWithdrawValidatorCommission(msg) => (
+            "cosmos-sdk/MsgWithdrawValCommission",
+            json!({ "validator_address": msg.validator_address }),
+        ),
+        Msg::MsgFundCommunityPool(msg) => (
+            "cosmos-sdk/MsgFundCommunityPool",
+            json!({
+                "amount": coins(&msg.amount),
+                "depositor": msg.depositor,
+            }),
+        ),
+        Msg::MsgSubmitProposalV1beta1(msg) => {
+            let content = msg.content.as_ref().ok_or_else(unsupported)?;
+            if content.type_url != "/cosmos.gov.v1beta1.TextProposal" {
+                return Err(CosmosClient::UnsupportedAminoMsg(content.type_url.clone()));
+            }
+            let text = TextProposal::decode(content.value.as_slice())?;
+            (
+                "cosmos-sdk/MsgSubmitProposal",
+                json!({
+                    "content": {
+                        "type": "cosmos-sdk/TextProposal",
+                        "value": { "title": text.title, "description": text.description },
+                    },
+                    "initial_deposit": coins(&msg.initial_deposit),
+                    "proposer": msg.proposer,
+                }),
+            )
+        }
+        Msg::MsgVoteV1beta1(msg) => (
+            "cosmos-sdk/MsgVote",
+            omit_default(
+                json!({
+                    "option": msg.option,
+                    "proposal_id": msg.proposal_id.to_string(),
+                    "voter": msg.voter,
+                }),
+                &["proposal_id"],
+            ),
+        ),
+        Msg::MsgDepositV1beta1(msg) => (
+            "cosmos-sdk/MsgDeposit",
+            omit_default(
+                json!({
+                    "amount": coins(&msg.amount),
+                    "depositor": msg.depositor,
+                    "proposal_id": msg.proposal_id.to_string(),
+                }),
+                &["proposal_id"],
+            ),
+        ),
+        Msg::MsgVoteV1(msg) => (
+            "cosmos-sdk/v1/MsgVote",
+            omit_default(
+                json!({
+                    "metadata": msg.metadata,
+                    "option": msg.option,
+                    "proposal_id": msg.proposal_id.to_string(),
+                    "voter": msg.voter,
+                }),
+                &["metadata", "proposal_id"],
+            ),
+        ),
+        Msg::MsgDepositV1(msg) => (
+            "cosmos-sdk/v1/MsgDeposit",
+            omit_default(
+                json!({
+                    "amount": coins(&msg.amount),
+                    "depositor": msg.depositor,
+                    "proposal_id": msg.proposal_id.to_string(),
+                }),
+                &["proposal_id"],
+            ),
+        ),
+        Msg::MsgTransfer(msg) => {
+            let height = msg.timeout_height.unwrap_or_default();
+            (
+                "cosmos-sdk/MsgTransfer",
+                omit_default(
+                    json!({
+                        "source_port": msg.source_port,
+                        "source_channel": msg.source_channel,
+                        "token": msg.token.as_ref().map(coin),
+                        "sender": msg.sender,
+                        "receiver": msg.receiver,
+                        "timeout_height": omit_default(json!({
+                            "revision_height": height.revision_height.to_string(),
+                            "revision_number": height.revision_number.to_string(),
+                        }), &["revision_height", "revision_number"]),
+                        "timeout_timestamp": msg.timeout_timestamp.to_string(),
+                    }),
+                    &["timeout_timestamp"],
+                ),
+            )
+        }
+        Msg::MsgStoreCode(msg) => {
+            let mut value = json!({
+                "sender": msg.sender,
+                "wasm_byte_code": STANDARD.encode(&msg.wasm_byte_code),
+            });
+            if let Some(config) = &msg.instantiate_permission {
+                value["instantiate_permission"] = access_config(config);
+            }
+            ("wasm/MsgStoreCode", value)
+        }
+        Msg::MsgInstantiateContract(msg) => {
+            let mut value = json!({
+                "sender": msg.sender,
+                "code_id": msg.code_id.to_string(),
+                "label": msg.label,
+                "msg": serde_json::from_slice::<Value>(&msg.msg)?,
+                "funds": coins(&msg.funds),
+            });
+            if !msg.admin.is_empty() {
+                value["admin"] = msg.admin.into();
+            }
+            ("wasm/MsgInstantiateContract", value)
+        }
+        Msg::MsgExecuteContract(msg) => (
+            "wasm/MsgExecuteContract",
+            json!({
+                "sender": msg.sender,
+                "contract": msg.contract,
+                "msg": serde_json::from_slice::<Value>(&msg.msg)?,
+                "funds": coins(&msg.funds),
+            }),
+        ),
+        Msg::MsgMigrateContract(msg) => (
+            "wasm/MsgMigrateContract",
+            json!({
+                "sender": msg.sender,
+                "contract": msg.contract,
+                "code_id": msg.code_id.to_string(),
+                "msg": serde_json::from_slice::<Value>(&msg.msg)?,
+            }),
+        ),
+        Msg::MsgUpdateAdmin(msg) => (
+            "wasm/MsgUpdateAdmin",
+            json!({
+                "sender": msg.sender,
+                "new_admin": msg.new_admin,
+                "contract": msg.contract,
+            }),
+        ),
+        Msg::MsgClearAdmin(msg) => (
+            "wasm/MsgClearAdmin",
+            json!({
+                "sender": msg.sender,
+                "contract": msg.contract,
+            }),
+        ),
+        _ => return Err(unsupported()),
+    };
+
+    Ok(json!({ "type": kind, "value": value }))
+}
+
+/// wasmd `AccessConfig`, its permission by the amino name of cosmjs
+/// `accessTypeToString`.
+fn access_config(config: &AccessConfig) -> Value {
+    let permission = match AccessType::from_i32(config.permission).unwrap_or_default() {
+        AccessType::Unspecified => "Unspecified",
+        AccessType::Nobody => "Nobody",
+        AccessType::OnlyAddress => "OnlyAddress",
+        AccessType::Everybody => "Everybody",
+        AccessType::AnyOfAddresses => "AnyOfAddresses",
+    };
+    let mut value = json!({ "permission": permission });
+    if !config.address.is_empty() {
+        value["address"] = config.address.clone().into();
+    }
+    if !config.addresses.is_empty() {
+        value["addresses"] = config.addresses.clone().into();
+    }
+
+    value
+}
+
+fn coin(coin: &Coin) -> Value {
+    json!({ "amount": coin.amount, "denom": coin.denom })
+}
+
+fn coins(coins: &[Coin]) -> Value {
+    coins.iter().map(coin).collect()
+}
+
+/// Drop `keys` left to their default value, like cosmjs `omitDefault`.
+fn omit_default(mut value: Value, keys: &[&str]) -> Value {
+    if let Value::Object(map) = &mut value {
+        for key in keys {
+            if matches!(map.get(*key), Some(Value::Null) | None)
+                || map
+                    .get(*key)
+                    .and_then(Value::as_str)
+                    .is_some_and(|v| v.is_empty() || v == "0")
+            {
+                map.remove(*key);
+            }
+        }
+    }
+
+    value
+}
+
+fn sorted(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries = map.into_iter().collect::<Vec<(String, Value)>>();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, sorted(value)))
+                    .collect::<Map<String, Value>>(),
+            )
+        }
+        Value::Array(values) => Value::Array(values.into_iter().map(sorted).collect()),
+        value => value,
+    }
+}
@@ -0,0 +1,149 @@
+use crate::error::CosmosClient;
+use cosmos_sdk_proto::cosmos::base::v1beta1::Coin;
+use cosmos_sdk_proto::tendermint::abci::Event;
+use std::collections::BTreeMap;
+
+/// Net balance change of every address and denom touched by a tx, positive
+/// when the address receives coins.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BalanceChanges(pub BTreeMap<String, BTreeMap<String, i128>>);
+
+impl BalanceChanges {
+    /// Read the bank `coin_spent` and `coin_received` events, or the
+    /// `transfer` events on chains that do not emit them.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if an event amount is not a list of coins
+    pub fn from_events(events: &[Event]) -> Result<Self, CosmosClient> {
+        let mut changes = BalanceChanges::default();
+        let has_coin_events = events
+            .iter()
+            .any(|event| event.r#type == "coin_spent" || event.r#type == "coin_received");
+
+        for event in events {
+            let attribute = |key: &str| {
+                event
+                    .attributes
+                    .iter()
+                    .find(|attribute| attribute.key == key)
+                    .map(|attribute| attribute.value.as_str())
+            };
+
+            match event.r#type.as_str() {
+                "coin_spent" if has_coin_events => {
+                    if let (Some(spender), Some(amount)) =
+                        (attribute("spender"), attribute("amount"))
+                    {
+                        changes.sub_coins(spender, amount)?;
+                    }
+                }
+                "coin_received" if has_coin_events => {
+                    if let (Some(receiver), Some(amount)) =
+                        (attribute("receiver"), attribute("amount"))
+                    {
+                        changes.add_coins(receiver, amount)?;
+                    }
+                }
+                "transfer" if !has_coin_events => {
+                    if let (Some(sender), Some(recipient), Some(amount)) = (
+                        attribute("sender"),
+                        attribute("recipient"),
+                        attribute("amount"),
+                    ) {
+                        changes.sub_coins(sender, amount)?;
+                        changes.add_coins(recipient, amount)?;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(changes)
+    }
+
+    /// Add `amount` of `denom` to the change of `address`, negative for a
+    /// debit.
+    pub fn add(&mut self, address: &str, denom: &str, amount: i128) {
+        let denoms = self.0.entry(address.to_string()).or_default();
+        let change = denoms.entry(denom.to_string()).or_default();
+        *change += amount;
+
+        if *change == 0 {
+            denoms.remove(denom);
+            if denoms.is_empty() {
+                self.0.remove(address);
+            }
+        }
+    }
+
+    /// Net change of `address` in `denom`.
+    #[must_use]
+    pub fn get(&self, address: &str, denom: &str) -> i128 {
+        self.0
+            .get(address)
+            .and_then(|denoms| denoms.get(denom))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Coins `address` ends up sending.
+    #[must_use]
+    pub fn sent(&self, address: &str) -> Vec<Coin> {
+        self.coins(address, |change| {
+            (change < 0).then(|| change.unsigned_abs())
+        })
+    }
+
+    /// Coins `address` ends up receiving.
+    #[must_use]
+    pub fn received(&self, address: &str) -> Vec<Coin> {
+        self.coins(address, |change| {
+            (change > 0).then(|| change.unsigned_abs())
+        })
+    }
+
+    fn coins(&self, address: &str, amount: impl Fn(i128) -> Option<u128>) -> Vec<Coin> {
+        self.0
+            .get(address)
+            .into_iter()
+            .flatten()
+            .filter_map(|(denom, change)| {
+                amount(*change).map(|amount| Coin {
+                    denom: denom.clone(),
+                    amount: amount.to_string(),
+                })
+            })
+            .collect()
+    }
+
+    fn add_coins(&mut self, address: &str, coins: &str) -> Result<(), CosmosClient> {
+        for (amount, denom) in parse_coins(coins)? {
+            self.add(address, denom, i128::try_from(amount)?);
+        }
+        Ok(())
+    }
+
+    fn sub_coins(&mut self, address: &str, coins: &str) -> Result<(), CosmosClient> {
+        for (amount, denom) in parse_coins(coins)? {
+            self.add(address, denom, -i128::try_from(amount)?);
+        }
+        Ok(())
+    }
+}
+
+/// Parse a list of coins like `100uatom,5ibc/27394FB0...`.
+fn parse_coins(coins: &str) -> Result<Vec<(u128, &str)>, CosmosClient> {
+    coins
+        .split(',')
+        .filter(|coin| !coin.is_empty())
+        .map(|coin| {
+            let coin = coin.trim();
+            let split = coin
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(coin.len());
+            let (amount, denom) = coin.split_at(split);
+            Ok((amount.parse()?, denom))
+        })
+        .collect()
+}
@@ -0,0 +1,194 @@
+use crate::error::CosmosClient;
+use crate::proto::cosmos::gov::v1;
+use cosmos_sdk_proto::cosmos::authz::v1beta1::{MsgExec, MsgGrant, MsgRevoke};
+use cosmos_sdk_proto::cosmos::bank::v1beta1::{MsgMultiSend, MsgSend};
+use cosmos_sdk_proto::cosmos::distribution::v1beta1::{
+    MsgFundCommunityPool, MsgSetWithdrawAddress, MsgWithdrawDelegatorReward,
+    MsgWithdrawValidatorCommission,
+};
+use cosmos_sdk_proto::cosmos::feegrant::v1beta1::{MsgGrantAllowance, MsgRevokeAllowance};
+use cosmos_sdk_proto::cosmos::gov::v1beta1;
+use cosmos_sdk_proto::cosmos::slashing::v1beta1::MsgUnjail;
+use cosmos_sdk_proto::cosmos::staking::v1beta1::{
+    MsgBeginRedelegate, MsgCancelUnbondingDelegation, MsgCreateValidator, MsgDelegate,
+    MsgEditValidator, MsgUndelegate,
+};
+use cosmos_sdk_proto::cosmos::tx::v1beta1::{AuthInfo, Fee, SignerInfo, TxBody, TxRaw};
+use cosmos_sdk_proto::cosmwasm::wasm::v1::{
+    MsgClearAdmin, MsgExecuteContract, MsgInstantiateContract, MsgInstantiateContract2,
+    MsgMigrateContract, MsgStoreCode, MsgUpdateAdmin,
+};
+use cosmos_sdk_proto::ibc::applications::transfer::v1::MsgTransfer;
+use cosmos_sdk_proto::Any;
+use prost::Message;
+use tendermint::crypto::default::Sha256;
+use tendermint::crypto::Sha256 as _;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Msg {
+    MsgSend(MsgSend),
+    MsgMultiSend(MsgMultiSend),
+    MsgCreateValidator(MsgCreateValidator),
+    MsgEditValidator(MsgEditValidator),
+    MsgDelegate(MsgDelegate),
+    MsgUndelegate(MsgUndelegate),
+    MsgBeginRedelegate(MsgBeginRedelegate),
+    MsgCancelUnbondingDelegation(MsgCancelUnbondingDelegation),
+    MsgSetWithdrawAddress(MsgSetWithdrawAddress),
+    MsgWithdrawDelegatorReward(MsgWithdrawDelegatorReward),
+    MsgWithdrawValidatorCommission(MsgWithdrawValidatorCommission),
+    MsgFundCommunityPool(MsgFundCommunityPool),
+    MsgSubmitProposalV1beta1(v1beta1::MsgSubmitProposal),
+    MsgVoteV1beta1(v1beta1::MsgVote),
+    MsgVoteWeightedV1beta1(v1beta1::MsgVoteWeighted),
+    MsgDepositV1beta1(v1beta1::MsgDeposit),
+    MsgSubmitProposalV1(v1::MsgSubmitProposal),
+    MsgExecLegacyContentV1(v1::MsgExecLegacyContent),
+    MsgVoteV1(v1::MsgVote),
+    MsgVoteWeightedV1(v1::MsgVoteWeighted),
+    MsgDepositV1(v1::MsgDeposit),
+    MsgGrant(MsgGrant),
+    MsgExec(MsgExec),
+    MsgRevoke(MsgRevoke),
+    MsgGrantAllowance(MsgGrantAllowance),
+    MsgRevokeAllowance(MsgRevokeAllowance),
+    MsgUnjail(MsgUnjail),
+    MsgTransfer(MsgTransfer),
+    MsgStoreCode(MsgStoreCode),
+    MsgInstantiateContract(MsgInstantiateContract),
+    MsgInstantiateContract2(MsgInstantiateContract2),
+    MsgExecuteContract(MsgExecuteContract),
+    MsgMigrateContract(MsgMigrateContract),
+    MsgUpdateAdmin(MsgUpdateAdmin),
+    MsgClearAdmin(MsgClearAdmin),
+    /// Any message whose type url is not known by this crate.
+    Unknown(Any),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecodedTx {
+    /// Uppercase hex sha256 of the raw tx, as indexed by the chain.
+    pub hash: String,
+    pub body: TxBody,
+    pub auth_info: AuthInfo,
+    pub fee: Option<Fee>,
+    pub signer_infos: Vec<SignerInfo>,
+    pub signatures: Vec<Vec<u8>>,
+    pub msgs: Vec<Msg>,
+}
+
+/// # Errors
+///
+/// Will return `Err` if `bytes` is not a protobuf encoded `TxRaw` or if one
+/// of its known messages fails to decode
+pub fn decode_tx(bytes: &[u8]) -> Result<DecodedTx, CosmosClient> {
+    let raw = TxRaw::decode(bytes)?;
+    let body = TxBody::decode(raw.body_bytes.as_slice())?;
+    let auth_info = AuthInfo::decode(raw.auth_info_bytes.as_slice())?;
+    let msgs = body
+        .messages
+        .iter()
+        .map(decode_msg)
+        .collect::<Result<Vec<Msg>, CosmosClient>>()?;
+
+    Ok(DecodedTx {
+        hash: hex::encode_upper(Sha256::digest(bytes)),
+        fee: auth_info.fee.clone(),
+        signer_infos: auth_info.signer_infos.clone(),
+        signatures: raw.signatures,
+        body,
+        auth_info,
+        msgs,
+    })
+}
+
+/// # Errors
+///
+/// Will return `Err` if the type url of `msg` is known but its value fails
+/// to decode
+pub fn decode_msg(msg: &Any) -> Result<Msg, CosmosClient> {
+    let value = msg.value.as_slice();
+
+    Ok(match msg.type_url.as_str() {
+        "/cosmos.bank.v1beta1.MsgSend" => Msg::MsgSend(MsgSend::decode(value)?),
+        "/cosmos.bank.v1beta1.MsgMultiSend" => Msg::MsgMultiSend(MsgMultiSend::decode(value)?),
+        "/cosmos.staking.v1beta1.MsgCreateValidator" => {
+            Msg::MsgCreateValidator(MsgCreateValidator::decode(value)?)
+        }
+        "/cosmos.staking.v1beta1.MsgEditValidator" => {
+            Msg::MsgEditValidator(MsgEditValidator::decode(value)?)
+        }
+        "/cosmos.staking.v1beta1.MsgDelegate" => Msg::MsgDelegate(MsgDelegate::decode(value)?),
+        "/cosmos.staking.v1beta1.MsgUndelegate" => {
+            Msg::MsgUndelegate(MsgUndelegate::decode(value)?)
+        }
+        "/cosmos.staking.v1beta1.MsgBeginRedelegate" => {
+            Msg::MsgBeginRedelegate(MsgBeginRedelegate::decode(value)?)
+        }
+        "/cosmos.staking.v1beta1.MsgCancelUnbondingDelegation" => {
+            Msg::MsgCancelUnbondingDelegation(MsgCancelUnbondingDelegation::decode(value)?)
+        }
+        "/cosmos.distribution.v1beta1.MsgSetWithdrawAddress" => {
+            Msg::MsgSetWithdrawAddress(MsgSetWithdrawAddress::decode(value)?)
+        }
+        "/cosmos.distribution.v1beta1.MsgWithdrawDelegatorReward" => {
+            Msg::MsgWithdrawDelegatorReward(MsgWithdrawDelegatorReward::decode(value)?)
+        }
+        "/cosmos.distribution.v1beta1.MsgWithdrawValidatorCommission" => {
+            Msg::MsgWithdrawValidatorCommission(MsgWithdrawValidatorCommission::decode(value)?)
+        }
+        "/cosmos.distribution.v1beta1.MsgFundCommunityPool" => {
+            Msg::MsgFundCommunityPool(MsgFundCommunityPool::decode(value)?)
+        }
+        "/cosmos.gov.v1beta1.MsgSubmitProposal" => {
+            Msg::MsgSubmitProposalV1beta1(v1beta1::MsgSubmitProposal::decode(value)?)
+        }
+        "/cosmos.gov.v1beta1.MsgVote" => Msg::MsgVoteV1beta1(v1beta1::MsgVote::decode(value)?),
+        "/cosmos.gov.v1beta1.MsgVoteWeighted" => {
+            Msg::MsgVoteWeightedV1beta1(v1beta1::MsgVoteWeighted::decode(value)?)
+        }
+        "/cosmos.gov.v1beta1.MsgDeposit" => {
+            Msg::MsgDepositV1beta1(v1beta1::MsgDeposit::decode(value)?)
+        }
+        "/cosmos.gov.v1.MsgSubmitProposal" => {
+            Msg::MsgSubmitProposalV1(v1::MsgSubmitProposal::decode(value)?)
+        }
+        "/cosmos.gov.v1.MsgExecLegacyContent" => {
+            Msg::MsgExecLegacyContentV1(v1::MsgExecLegacyContent::decode(value)?)
+        }
+        "/cosmos.gov.v1.MsgVote" => Msg::MsgVoteV1(v1::MsgVote::decode(value)?),
+        "/cosmos.gov.v1.MsgVoteWeighted" => {
+            Msg::MsgVoteWeightedV1(v1::MsgVoteWeighted::decode(value)?)
+        }
+        "/cosmos.gov.v1.MsgDeposit" => Msg::MsgDepositV1(v1::MsgDeposit::decode(value)?),
+        "/cosmos.authz.v1beta1.MsgGrant" => Msg::MsgGrant(MsgGrant::decode(value)?),
+        "/cosmos.authz.v1beta1.MsgExec" => Msg::MsgExec(MsgExec::decode(value)?),
+        "/cosmos.authz.v1beta1.MsgRevoke" => Msg::MsgRevoke(MsgRevoke::decode(value)?),
+        "/cosmos.feegrant.v1beta1.MsgGrantAllowance" => {
+            Msg::MsgGrantAllowance(MsgGrantAllowance::decode(value)?)
+        }
+        "/cosmos.feegrant.v1beta1.MsgRevokeAllowance" => {
+            Msg::MsgRevokeAllowance(MsgRevokeAllowance::decode(value)?)
+        }
+        "/cosmos.slashing.v1beta1.MsgUnjail" => Msg::MsgUnjail(MsgUnjail::decode(value)?),
+        "/ibc.applications.transfer.v1.MsgTransfer" => {
+            Msg::MsgTransfer(MsgTransfer::decode(value)?)
+        }
+        "/cosmwasm.wasm.v1.MsgStoreCode" => Msg::MsgStoreCode(MsgStoreCode::decode(value)?),
+        "/cosmwasm.wasm.v1.MsgInstantiateContract" => {
+            Msg::MsgInstantiateContract(MsgInstantiateContract::decode(value)?)
+        }
+        "/cosmwasm.wasm.v1.MsgInstantiateContract2" => {
+            Msg::MsgInstantiateContract2(MsgInstantiateContract2::decode(value)?)
+        }
+        "/cosmwasm.wasm.v1.MsgExecuteContract" => {
+            Msg::MsgExecuteContract(MsgExecuteContract::decode(value)?)
+        }
+        "/cosmwasm.wasm.v1.MsgMigrateContract" => {
+            Msg::MsgMigrateContract(MsgMigrateContract::decode(value)?)
+        }
+        "/cosmwasm.wasm.v1.MsgUpdateAdmin" => Msg::MsgUpdateAdmin(MsgUpdateAdmin::decode(value)?),
+        "/cosmwasm.wasm.v1.MsgClearAdmin" => Msg::MsgClearAdmin(MsgClearAdmin::decode(value)?),
+        _ => Msg::Unknown(msg.clone()),
+    })
+}
@@ -0,0 +1,548 @@
+use crate::error::CosmosClient;
+use crate::proto::ethermint::types::v1::ExtensionOptionsWeb3Tx;
+use crate::signer::{KeyType, TxSigner};
+use crate::tx::amino::StdSignDoc;
+use crate::tx::decode::{decode_msg, Msg};
+use cosmos_sdk_proto::cosmos::tx::signing::v1beta1::SignMode;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::TxRaw;
+use cosmrs::crypto::secp256k1::{Signature, VerifyingKey};
+use cosmrs::crypto::PublicKey;
+use cosmrs::tx::{Body, Fee, ModeInfo, SignerInfo};
+use cosmrs::AccountId;
+use k256::ecdsa::RecoveryId;
+use prost::Message;
+use prost_types::Any;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha3::{Digest, Keccak256};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Extension option carrying the EIP-712 signature of a tx on ethermint
+/// chains.
+pub const WEB3_TX_TYPE_URL: &str = "/ethermint.types.v1.ExtensionOptionsWeb3Tx";
+
+/// Field of an EIP-712 struct type.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Field {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+}
+
+impl Field {
+    fn new(name: &str, kind: &str) -> Self {
+        Field {
+            name: name.to_string(),
+            kind: kind.to_string(),
+        }
+    }
+}
+
+/// EIP-712 typed data, in the JSON form taken by `eth_signTypedData_v4`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypedData {
+    pub types: BTreeMap<String, Vec<Field>>,
+    pub primary_type: String,
+    pub domain: Value,
+    pub message: Value,
+}
+
+impl TypedData {
+    /// `0x19 0x01 || domainSeparator || hashStruct(message)`, whose keccak256
+    /// hash is signed. An ethermint key signs these bytes as is.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the domain or the message do not match the types
+    pub fn sign_bytes(&self) -> Result<Vec<u8>, CosmosClient> {
+        let mut bytes = vec![0x19, 0x01];
+        bytes.extend(self.hash_struct("EIP712Domain", &self.domain)?);
+        bytes.extend(self.hash_struct(&self.primary_type, &self.message)?);
+
+        Ok(bytes)
+    }
+
+    /// Hash signed by `eth_signTypedData_v4`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the domain or the message do not match the types
+    pub fn hash(&self) -> Result<[u8; 32], CosmosClient> {
+        Ok(Keccak256::digest(self.sign_bytes()?).into())
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - `kind` or one of its fields is not a known type
+    /// - `data` misses a field of `kind` or has an extra one
+    /// - a value does not match the type of its field
+    pub fn hash_struct(&self, kind: &str, data: &Value) -> Result<[u8; 32], CosmosClient> {
+        let fields = self.fields(kind)?;
+        let data = data
+            .as_object()
+            .ok_or_else(|| invalid(format!("{kind} is not an object")))?;
+        if let Some(extra) = data
+            .keys()
+            .find(|key| !fields.iter().any(|field| &&field.name == key))
+        {
+            return Err(invalid(format!("extra field {extra} in {kind}")));
+        }
+
+        let mut encoded = Keccak256::digest(self.encode_type(kind)?).to_vec();
+        for field in fields {
+            let value = data
+                .get(&field.name)
+                .ok_or_else(|| invalid(format!("missing field {} in {kind}", field.name)))?;
+            encoded.extend(self.encode_value(&field.kind, value)?);
+        }
+
+        Ok(Keccak256::digest(encoded).into())
+    }
+
+    /// `kind` and every struct it references, like
+    /// `Mail(Person from,Person to,string contents)Person(string name,address wallet)`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if `kind` is not a known type
+    pub fn encode_type(&self, kind: &str) -> Result<String, CosmosClient> {
+        let mut dependencies = BTreeSet::new();
+        self.dependencies(kind, &mut dependencies);
+        dependencies.remove(kind);
+
+        std::iter::once(kind)
+            .chain(dependencies.iter().map(String::as_str))
+            .map(|kind| {
+                let fields = self
+                    .fields(kind)?
+                    .iter()
+                    .map(|field| format!("{} {}", field.kind, field.name))
+                    .collect::<Vec<String>>();
+                Ok(format!("{kind}({})", fields.join(",")))
+            })
+            .collect()
+    }
+
+    fn fields(&self, kind: &str) -> Result<&Vec<Field>, CosmosClient> {
+        self.types
+            .get(kind)
+            .ok_or_else(|| invalid(format!("unknown type {kind}")))
+    }
+
+    fn dependencies(&self, kind: &str, found: &mut BTreeSet<String>) {
+        let kind = kind.split('[').next().unwrap_or(kind);
+        if found.contains(kind) {
+            return;
+        }
+
+        if let Some(fields) = self.types.get(kind) {
+            found.insert(kind.to_string());
+            for field in fields {
+                self.dependencies(&field.kind, found);
+            }
+        }
+    }
+
+    fn encode_value(&self, kind: &str, value: &Value) -> Result<[u8; 32], CosmosClient> {
+        let mismatch = || invalid(format!("{value} is not a {kind}"));
+
+        if let Some((item, _)) = kind
+            .strip_suffix(']')
+            .and_then(|kind| kind.rsplit_once('['))
+        {
+            let mut encoded = vec![];
+            for value in value.as_array().ok_or_else(mismatch)? {
+                encoded.extend(self.encode_value(item, value)?);
+            }
+            return Ok(Keccak256::digest(encoded).into());
+        }
+        if self.types.contains_key(kind) {
+            return self.hash_struct(kind, value);
+        }
+
+        let mut word = [0u8; 32];
+        match kind {
+            "string" => return Ok(Keccak256::digest(value.as_str().ok_or_else(mismatch)?).into()),
+            "bytes" => return Ok(Keccak256::digest(hex_bytes(value).ok_or_else(mismatch)?).into()),
+            "bool" => word[31] = u8::from(value.as_bool().ok_or_else(mismatch)?),
+            "address" => {
+                let address = hex_bytes(value)
+                    .filter(|address| address.len() == 20)
+                    .ok_or_else(mismatch)?;
+                word[12..].copy_from_slice(&address);
+            }
+            _ if kind.starts_with("bytes") => {
+                let bytes = hex_bytes(value)
+                    .filter(|bytes| bytes.len() <= 32)
+                    .ok_or_else(mismatch)?;
+                word[..bytes.len()].copy_from_slice(&bytes);
+            }
+            _ if kind.starts_with("uint") => {
+                word[16..]
+                    .copy_from_slice(&integer::<u128>(value).ok_or_else(mismatch)?.to_be_bytes());
+            }
+            _ if kind.starts_with("int") => {
+                let int = integer::<i128>(value).ok_or_else(mismatch)?;
+                if int < 0 {
+                    word = [0xff; 32];
+                }
+                word[16..].copy_from_slice(&int.to_be_bytes());
+            }
+            _ => return Err(invalid(format!("unknown type {kind}"))),
+        }
+
+        Ok(word)
+    }
+}
+
+fn invalid(reason: String) -> CosmosClient {
+    CosmosClient::InvalidTypedData(reason)
+}
+
+fn hex_bytes(value: &Value) -> Option<Vec<u8>> {
+    hex::decode(value.as_str()?.trim_start_matches("0x")).ok()
+}
+
+/// Integer given as a JSON number, a decimal string or a `0x` hex string.
+fn integer<T: TryFrom<i128> + std::str::FromStr>(value: &Value) -> Option<T> {
+    match value {
+        Value::Number(number) => number
+            .as_i64()
+            .map(i128::from)
+            .or_else(|| number.as_u64().map(i128::from))
+            .and_then(|number| T::try_from(number).ok()),
+        Value::String(text) => match text.strip_prefix("0x") {
+            Some(hex) => i128::from_str_radix(hex, 16)
+                .ok()
+                .and_then(|number| T::try_from(number).ok()),
+            None => text.parse().ok(),
+        },
+        _ => None,
+    }
+}
+
+/// EIP-155 chain id of an ethermint chain id like `evmos_9001-2`.
+///
+/// # Errors
+///
+/// Will return `Err` if `chain_id` is not an ethermint chain id
+pub fn eip155_chain_id(chain_id: &str) -> Result<u64, CosmosClient> {
+    chain_id
+        .split_once('_')
+        .and_then(|(_, rest)| rest.split_once('-'))
+        .and_then(|(number, _)| number.parse().ok())
+        .ok_or_else(|| invalid(format!("{chain_id} is not an ethermint chain id")))
+}
+
+/// Legacy ethermint EIP-712 typed data of a tx, as built by the chain to
+/// check the signature : its amino JSON sign doc with `fee_payer` in the
+/// fee, under the `Cosmos Web3` domain. Every message must be of the same
+/// type.
+///
+/// # Errors
+///
+/// Will return `Err` if :
+/// - `chain_id` is not an ethermint chain id
+/// - the body has no message, messages of several types, a fee granter or
+///   payer, or a timeout height
+/// - a message has no EIP-712 types in this crate
+pub fn tx_typed_data(
+    body: &Body,
+    fee: &Fee,
+    chain_id: &str,
+    account_number: u64,
+    sequence: u64,
+    fee_payer: &str,
+) -> Result<TypedData, CosmosClient> {
+    let first = body
+        .messages
+        .first()
+        .ok_or_else(|| invalid("tx without message".to_string()))?;
+    if body
+        .messages
+        .iter()
+        .any(|msg| msg.type_url != first.type_url)
+    {
+        return Err(invalid("messages of several types".to_string()));
+    }
+
+    let mut types = BTreeMap::from([
+        (
+            "EIP712Domain".to_string(),
+            vec![
+                Field::new("name", "string"),
+                Field::new("version", "string"),
+                Field::new("chainId", "uint256"),
+                Field::new("verifyingContract", "string"),
+                Field::new("salt", "string"),
+            ],
+        ),
+        (
+            "Tx".to_string(),
+            vec![
+                Field::new("account_number", "string"),
+                Field::new("chain_id", "string"),
+                Field::new("fee", "Fee"),
+                Field::new("memo", "string"),
+                Field::new("msgs", "Msg[]"),
+                Field::new("sequence", "string"),
+            ],
+        ),
+        (
+            "Fee".to_string(),
+            vec![
+                Field::new("feePayer", "string"),
+                Field::new("amount", "Coin[]"),
+                Field::new("gas", "string"),
+            ],
+        ),
+        (
+            "Coin".to_string(),
+            vec![
+                Field::new("denom", "string"),
+                Field::new("amount", "string"),
+            ],
+        ),
+        (
+            "Msg".to_string(),
+            vec![
+                Field::new("type", "string"),
+                Field::new("value", "MsgValue"),
+            ],
+        ),
+    ]);
+    types.extend(msg_types(first)?);
+
+    let mut message = StdSignDoc::new(body, fee, chain_id, account_number, sequence)?.to_json();
+    message["fee"]["feePayer"] = fee_payer.into();
+
+    let typed_data = TypedData {
+        types,
+        primary_type: "Tx".to_string(),
+        domain: json!({
+            "name": "Cosmos Web3",
+            "version": "1.0.0",
+            "chainId": eip155_chain_id(chain_id)?,
+            "verifyingContract": "cosmos",
+            "salt": "0",
+        }),
+        message,
+    };
+    typed_data.hash()?;
+
+    Ok(typed_data)
+}
+
+/// `MsgValue` type of `msg` and the structs it uses, as generated by
+/// ethermint from the message fields.
+fn msg_types(msg: &Any) -> Result<Vec<(String, Vec<Field>)>, CosmosClient> {
+    let amount = || {
+        (
+            "TypeAmount".to_string(),
+            vec![
+                Field::new("denom", "string"),
+                Field::new("amount", "string"),
+            ],
+        )
+    };
+    let value = |fields: &[(&str, &str)]| {
+        (
+            "MsgValue".to_string(),
+            fields
+                .iter()
+                .map(|(name, kind)| Field::new(name, kind))
+                .collect(),
+        )
+    };
+
+    Ok(match decode_msg(msg)? {
+        Msg::MsgSend(_) => vec![
+            value(&[
+                ("from_address", "string"),
+                ("to_address", "string"),
+                ("amount", "TypeAmount[]"),
+            ]),
+            amount(),
+        ],
+        Msg::MsgDelegate(_) | Msg::MsgUndelegate(_) => vec![
+            value(&[
+                ("delegator_address", "string"),
+                ("validator_address", "string"),
+                ("amount", "TypeAmount"),
+            ]),
+            amount(),
+        ],
+        Msg::MsgBeginRedelegate(_) => vec![
+            value(&[
+                ("delegator_address", "string"),
+                ("validator_src_address", "string"),
+                ("validator_dst_address", "string"),
+                ("amount", "TypeAmount"),
+            ]),
+            amount(),
+        ],
+        Msg::MsgWithdrawDelegatorReward(_) => vec![value(&[
+            ("delegator_address", "string"),
+            ("validator_address", "string"),
+        ])],
+        Msg::MsgVoteV1beta1(_) => vec![value(&[
+            ("proposal_id", "uint64"),
+            ("voter", "string"),
+            ("option", "int32"),
+        ])],
+        _ => return Err(CosmosClient::UnsupportedEip712Msg(msg.type_url.clone())),
+    })
+}
+
+/// Tx signed with EIP-712 typed data instead of a Cosmos sign doc, like
+/// `MetaMask` does on ethermint chains. The signature goes in the
+/// `ExtensionOptionsWeb3Tx` extension option, the Cosmos signature is left
+/// empty.
+#[derive(Clone, Debug)]
+pub struct Web3Tx {
+    pub typed_data: TypedData,
+    pub typed_data_chain_id: u64,
+    pub fee_payer: String,
+    body: Body,
+    auth_info_bytes: Vec<u8>,
+    public_key: PublicKey,
+}
+
+impl Web3Tx {
+    /// Tx to be signed by the ethermint key `public_key` of `fee_payer`,
+    /// by this process or by a web wallet given [`Web3Tx::typed_data`].
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - the typed data cannot be built, see [`tx_typed_data`]
+    /// - there is an encode error
+    pub fn new(
+        public_key: PublicKey,
+        fee_payer: &AccountId,
+        body: &Body,
+        fee: Fee,
+        chain_id: &str,
+        account_number: u64,
+        sequence: u64,
+    ) -> Result<Self, CosmosClient> {
+        let typed_data = tx_typed_data(
+            body,
+            &fee,
+            chain_id,
+            account_number,
+            sequence,
+            fee_payer.as_ref(),
+        )?;
+        let auth_info = SignerInfo {
+            public_key: Some(KeyType::EthSecp256k1.signer_public_key(public_key)),
+            mode_info: ModeInfo::single(SignMode::LegacyAminoJson),
+            sequence,
+        }
+        .auth_info(fee);
+
+        Ok(Web3Tx {
+            typed_data,
+            typed_data_chain_id: eip155_chain_id(chain_id)?,
+            fee_payer: fee_payer.to_string(),
+            body: body.clone(),
+            auth_info_bytes: auth_info.into_bytes()?,
+            public_key,
+        })
+    }
+
+    /// Raw tx with `signature` of the typed data, either the 64 bytes
+    /// `r || s` or the 65 bytes `r || s || v` returned by a web wallet.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - `signature` is not a signature of the typed data by the public key
+    /// - there is an encode error
+    pub fn into_tx(self, signature: &[u8]) -> Result<Vec<u8>, CosmosClient> {
+        let hash = self.typed_data.hash()?;
+        let key = VerifyingKey::from_sec1_bytes(&self.public_key.to_bytes())?;
+        let wrong_signature = || invalid("signature of another key".to_string());
+
+        let (rs, recovery_id) = match signature.len() {
+            64 => (
+                Signature::try_from(signature)?,
+                RecoveryId::trial_recovery_from_prehash(
+                    &key,
+                    &hash,
+                    &Signature::try_from(signature)?,
+                )
+                .map_err(|_| wrong_signature())?,
+            ),
+            65 => {
+                let v = signature[64];
+                let recovery_id = RecoveryId::from_byte(if v >= 27 { v - 27 } else { v })
+                    .ok_or_else(|| invalid(format!("invalid recovery id {v}")))?;
+                (Signature::try_from(&signature[..64])?, recovery_id)
+            }
+            length => return Err(invalid(format!("signature of {length} bytes"))),
+        };
+        if VerifyingKey::recover_from_prehash(&hash, &rs, recovery_id).ok() != Some(key) {
+            return Err(wrong_signature());
+        }
+
+        let mut fee_payer_sig = rs.to_vec();
+        fee_payer_sig.push(recovery_id.to_byte() + 27);
+        let mut body = self.body;
+        body.extension_options.push(Any {
+            type_url: WEB3_TX_TYPE_URL.to_string(),
+            value: ExtensionOptionsWeb3Tx {
+                typed_data_chain_id: self.typed_data_chain_id,
+                fee_payer: self.fee_payer,
+                fee_payer_sig,
+            }
+            .encode_to_vec(),
+        });
+
+        Ok(TxRaw {
+            body_bytes: body.into_bytes()?,
+            auth_info_bytes: self.auth_info_bytes,
+            signatures: vec![vec![]],
+        }
+        .encode_to_vec())
+    }
+}
+
+/// Sign `body` as EIP-712 typed data with the ethermint key of `signer`
+/// and build the raw tx.
+///
+/// # Errors
+///
+/// Will return `Err` if :
+/// - `signer` does not hold an ethermint key
+/// - the typed data cannot be built, see [`tx_typed_data`]
+/// - the signer fails to sign
+/// - there is an encode error
+pub async fn sign_web3_tx(
+    signer: &dyn TxSigner,
+    body: &Body,
+    fee: Fee,
+    chain_id: &str,
+    account_number: u64,
+    sequence: u64,
+) -> Result<Vec<u8>, CosmosClient> {
+    let type_url = Any::from(signer.signer_public_key()).type_url;
+    if type_url != KeyType::EthSecp256k1.type_url() {
+        return Err(invalid(format!("{type_url} is not an ethermint key")));
+    }
+
+    let tx = Web3Tx::new(
+        signer.public_key(),
+        &signer.address(),
+        body,
+        fee,
+        chain_id,
+        account_number,
+        sequence,
+    )?;
+    let signature = signer.sign_bytes(&tx.typed_data.sign_bytes()?).await?;
+
+    tx.into_tx(&signature)
+}
@@ -0,0 +1,272 @@
+use crate::error::CosmosClient;
+use crate::tx::decode::{decode_msg, Msg};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use cosmos_sdk_proto::cosmos::base::v1beta1::Coin;
+use cosmos_sdk_proto::cosmos::crypto::multisig::v1beta1::CompactBitArray;
+use cosmos_sdk_proto::cosmos::crypto::multisig::LegacyAminoPubKey;
+use cosmos_sdk_proto::cosmos::crypto::{ed25519, secp256k1};
+use cosmos_sdk_proto::cosmos::gov::v1beta1::VoteOption;
+use cosmos_sdk_proto::cosmos::tx::signing::v1beta1::SignMode;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::{mode_info, AuthInfo, ModeInfo, TxBody};
+use cosmos_sdk_proto::cosmwasm::wasm::v1::AccessType;
+use prost::Message;
+use prost_types::Any;
+use serde_json::{json, Value};
+
+/// Tx in the protobuf JSON format of the Cosmos SDK CLI, as printed by
+/// `tx ... --generate-only` and read by `tx sign` / `tx broadcast`.
+///
+/// # Errors
+///
+/// Will return `Err` if one of the messages, extension options or public
+/// keys has no JSON encoding in this crate
+pub fn tx_to_json(
+    body: &TxBody,
+    auth_info: &AuthInfo,
+    signatures: &[Vec<u8>],
+) -> Result<Value, CosmosClient> {
+    let fee = auth_info.fee.clone().unwrap_or_default();
+
+    Ok(json!({
+        "body": {
+            "messages": body.messages.iter().map(msg_to_json).collect::<Result<Vec<Value>, CosmosClient>>()?,
+            "memo": body.memo,
+            "timeout_height": body.timeout_height.to_string(),
+            "extension_options": body.extension_options.iter().map(msg_to_json).collect::<Result<Vec<Value>, CosmosClient>>()?,
+            "non_critical_extension_options": body.non_critical_extension_options.iter().map(msg_to_json).collect::<Result<Vec<Value>, CosmosClient>>()?,
+        },
+        "auth_info": {
+            "signer_infos": auth_info.signer_infos.iter().map(|signer_info| Ok(json!({
+                "public_key": signer_info.public_key.as_ref().map(public_key_to_json).transpose()?,
+                "mode_info": signer_info.mode_info.as_ref().map(mode_info_to_json),
+                "sequence": signer_info.sequence.to_string(),
+            }))).collect::<Result<Vec<Value>, CosmosClient>>()?,
+            "fee": {
+                "amount": coins(&fee.amount),
+                "gas_limit": fee.gas_limit.to_string(),
+                "payer": fee.payer,
+                "granter": fee.granter,
+            },
+        },
+        "signatures": signatures.iter().map(|signature| STANDARD.encode(signature)).collect::<Vec<String>>(),
+    }))
+}
+
+/// # Errors
+///
+/// Will return `Err` if `public_key` is not a secp256k1, ethermint
+/// `eth_secp256k1`, ed25519 or legacy amino multisig key
+pub fn public_key_to_json(public_key: &Any) -> Result<Value, CosmosClient> {
+    let value = public_key.value.as_slice();
+
+    Ok(match public_key.type_url.as_str() {
+        // eth_secp256k1 keys share the `{ key }` message of secp256k1
+        "/cosmos.crypto.secp256k1.PubKey"
+        | "/ethermint.crypto.v1.ethsecp256k1.PubKey"
+        | "/injective.crypto.v1beta1.ethsecp256k1.PubKey" => json!({
+            "@type": public_key.type_url,
+            "key": STANDARD.encode(secp256k1::PubKey::decode(value)?.key),
+        }),
+        "/cosmos.crypto.ed25519.PubKey" => json!({
+            "@type": public_key.type_url,
+            "key": STANDARD.encode(ed25519::PubKey::decode(value)?.key),
+        }),
+        "/cosmos.crypto.multisig.LegacyAminoPubKey" => {
+            let multisig = LegacyAminoPubKey::decode(value)?;
+            json!({
+                "@type": public_key.type_url,
+                "threshold": multisig.threshold,
+                "public_keys": multisig.public_keys.iter().map(public_key_to_json).collect::<Result<Vec<Value>, CosmosClient>>()?,
+            })
+        }
+        _ => {
+            return Err(CosmosClient::UnsupportedJsonMsg(
+                public_key.type_url.clone(),
+            ))
+        }
+    })
+}
+
+fn mode_info_to_json(mode_info: &ModeInfo) -> Value {
+    match &mode_info.sum {
+        Some(mode_info::Sum::Single(single)) => json!({
+            "single": {
+                "mode": SignMode::from_i32(single.mode).unwrap_or_default().as_str_name(),
+            },
+        }),
+        Some(mode_info::Sum::Multi(multi)) => {
+            let bitarray = multi.bitarray.clone().unwrap_or(CompactBitArray {
+                extra_bits_stored: 0,
+                elems: vec![],
+            });
+            json!({
+                "multi": {
+                    "bitarray": {
+                        "extra_bits_stored": bitarray.extra_bits_stored,
+                        "elems": STANDARD.encode(bitarray.elems),
+                    },
+                    "mode_infos": multi.mode_infos.iter().map(mode_info_to_json).collect::<Vec<Value>>(),
+                },
+            })
+        }
+        None => Value::Null,
+    }
+}
+
+/// Protobuf JSON form of `msg` with its `@type`.
+///
+/// # Errors
+///
+/// Will return `Err` if `msg` fails to decode or has no JSON encoding in this
+/// crate
+#[allow(clippy::too_many_lines)]
+pub fn msg_to_json(msg: &Any) -> Result<Value, CosmosClient> {
+    let vote_option = |option: i32| {
+        VoteOption::from_i32(option)
+            .unwrap_or_default()
+            .as_str_name()
+    };
+
+    let mut value = match decode_msg(msg)? {
+        Msg::MsgSend(msg) => json!({
+            "from_address": msg.from_address,
+            "to_address": msg.to_address,
+            "amount": coins(&msg.amount),
+        }),
+        Msg::MsgMultiSend(msg) => json!({
+            "inputs": msg.inputs.iter().map(|input| json!({
+                "address": input.address,
+                "coins": coins(&input.coins),
+            })).collect::<Vec<Value>>(),
+            "outputs": msg.outputs.iter().map(|output| json!({
+                "address": output.address,
+                "coins": coins(&output.coins),
+            })).collect::<Vec<Value>>(),
+        }),
+        Msg::MsgDelegate(msg) => json!({
+            "delegator_address": msg.delegator_address,
+            "validator_address": msg.validator_address,
+            "amount": msg.amount.as_ref().map(coin),
+        }),
+        Msg::MsgUndelegate(msg) => json!({
+            "delegator_address": msg.delegator_address,
+            "validator_address": msg.validator_address,
+            "amount": msg.amount.as_ref().map(coin),
+        }),
+        Msg::MsgBeginRedelegate(msg) => json!({
+            "delegator_address": msg.delegator_address,
+            "validator_src_address": msg.validator_src_address,
+            "validator_dst_address": msg.validator_dst_address,
+            "amount": msg.amount.as_ref().map(coin),
+        }),
+        Msg::MsgCancelUnbondingDelegation(msg) => json!({
+            "delegator_address": msg.delegator_address,
+            "validator_address": msg.validator_address,
+            "amount": msg.amount.as_ref().map(coin),
+            "creation_height": msg.creation_height.to_string(),
+        }),
+        Msg::MsgSetWithdrawAddress(msg) => json!({
+            "delegator_address": msg.delegator_address,
+            "withdraw_address": msg.withdraw_address,
+        }),
+        Msg::MsgWithdrawDelegatorReward(msg) => json!({
+            "delegator_address": msg.delegator_address,
+            "validator_address": msg.validator_address,
+        }),
+        Msg::MsgWithdrawValidatorCommission(msg) => json!({
+            "validator_address": msg.validator_address,
+        }),
+        Msg::MsgFundCommunityPool(msg) => json!({
+            "amount": coins(&msg.amount),
+            "depositor": msg.depositor,
+        }),
+        Msg::MsgVoteV1beta1(msg) => json!({
+            "proposal_id": msg.proposal_id.to_string(),
+            "voter": msg.voter,
+            "option": vote_option(msg.option),
+        }),
+        Msg::MsgDepositV1beta1(msg) => json!({
+            "proposal_id": msg.proposal_id.to_string(),
+            "depositor": msg.depositor,
+            "amount": coins(&msg.amount),
+        }),
+        Msg::MsgVoteV1(msg) => json!({
+            "proposal_id": msg.proposal_id.to_string(),
+            "voter": msg.voter,
+            "option": vote_option(msg.option),
+            "metadata": msg.metadata,
+        }),
+        Msg::MsgDepositV1(msg) => json!({
+            "proposal_id": msg.proposal_id.to_string(),
+            "depositor": msg.depositor,
+            "amount": coins(&msg.amount),
+        }),
+        Msg::MsgUnjail(msg) => json!({ "validator_addr": msg.validator_addr }),
+        Msg::MsgTransfer(msg) => {
+            let height = msg.timeout_height.unwrap_or_default();
+            json!({
+                "source_port": msg.source_port,
+                "source_channel": msg.source_channel,
+                "token": msg.token.as_ref().map(coin),
+                "sender": msg.sender,
+                "receiver": msg.receiver,
+                "timeout_height": {
+                    "revision_number": height.revision_number.to_string(),
+                    "revision_height": height.revision_height.to_string(),
+                },
+                "timeout_timestamp": msg.timeout_timestamp.to_string(),
+            })
+        }
+        Msg::MsgStoreCode(msg) => json!({
+            "sender": msg.sender,
+            "wasm_byte_code": STANDARD.encode(&msg.wasm_byte_code),
+            "instantiate_permission": msg.instantiate_permission.as_ref().map(|config| json!({
+                "permission": AccessType::from_i32(config.permission).unwrap_or_default().as_str_name(),
+                "address": config.address,
+                "addresses": config.addresses,
+            })),
+        }),
+        Msg::MsgInstantiateContract(msg) => json!({
+            "sender": msg.sender,
+            "admin": msg.admin,
+            "code_id": msg.code_id.to_string(),
+            "label": msg.label,
+            "msg": serde_json::from_slice::<Value>(&msg.msg)?,
+            "funds": coins(&msg.funds),
+        }),
+        Msg::MsgExecuteContract(msg) => json!({
+            "sender": msg.sender,
+            "contract": msg.contract,
+            "msg": serde_json::from_slice::<Value>(&msg.msg)?,
+            "funds": coins(&msg.funds),
+        }),
+        Msg::MsgMigrateContract(msg) => json!({
+            "sender": msg.sender,
+            "contract": msg.contract,
+            "code_id": msg.code_id.to_string(),
+            "msg": serde_json::from_slice::<Value>(&msg.msg)?,
+        }),
+        Msg::MsgUpdateAdmin(msg) => json!({
+            "sender": msg.sender,
+            "new_admin": msg.new_admin,
+            "contract": msg.contract,
+        }),
+        Msg::MsgClearAdmin(msg) => json!({
+            "sender": msg.sender,
+            "contract": msg.contract,
+        }),
+        _ => return Err(CosmosClient::UnsupportedJsonMsg(msg.type_url.clone())),
+    };
+
+    value["@type"] = msg.type_url.clone().into();
+    Ok(value)
+}
+
+fn coin(coin: &Coin) -> Value {
+    json!({ "denom": coin.denom, "amount": coin.amount })
+}
+
+fn coins(coins: &[Coin]) -> Value {
+    coins.iter().map(coin).collect()
+}
@@ -0,0 +1,385 @@
+use crate::error::CosmosClient;
+use crate::signer::{KeyType, Signer};
+use crate::tx::decode::{decode_msg, Msg};
+use crate::tx::json::tx_to_json;
+use crate::tx::offline::SignedTx;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use cosmos_sdk_proto::cosmos::tx::signing::v1beta1::SignMode;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::{
+    mode_info, AuthInfo, ModeInfo, SignDoc, SignerInfo, TxRaw,
+};
+use cosmrs::crypto::PublicKey;
+use cosmrs::tx::{Body, Fee};
+use cosmrs::AccountId;
+use prost::Message;
+use prost_types::Any;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::str::FromStr;
+
+/// Account of one signer of a [`MultiSignerTx`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignerData {
+    pub address: String,
+    /// Public key in the cosmrs JSON form, e.g.
+    /// `{"@type":"/cosmos.crypto.secp256k1.PubKey","key":"..."}`.
+    pub public_key: String,
+    pub account_number: u64,
+    pub sequence: u64,
+    /// Type of the key, which sets its signer info public key and the hash
+    /// it signs.
+    #[serde(default)]
+    pub key_type: KeyType,
+}
+
+/// Tx signed by several accounts, e.g. a batch of `MsgSend` from two
+/// accounts. Every signer signs the same body and auth info in direct mode
+/// with its own account number, so the tx can be passed around as JSON and
+/// signed on different machines.
+///
+/// `signers` are kept in the order the chain expects their signatures,
+/// `signatures` are base64 and in the same order.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MultiSignerTx {
+    pub chain_id: String,
+    pub signers: Vec<SignerData>,
+    pub tx: Value,
+    pub body_bytes: String,
+    pub auth_info_bytes: String,
+    pub signatures: Vec<Option<String>>,
+}
+
+impl SignerData {
+    /// Cosmos SDK secp256k1 signer.
+    #[must_use]
+    pub fn new(public_key: &PublicKey, address: &str, account_number: u64, sequence: u64) -> Self {
+        SignerData::new_with_key_type(
+            KeyType::Secp256k1,
+            public_key,
+            address,
+            account_number,
+            sequence,
+        )
+    }
+
+    #[must_use]
+    pub fn new_with_key_type(
+        key_type: KeyType,
+        public_key: &PublicKey,
+        address: &str,
+        account_number: u64,
+        sequence: u64,
+    ) -> Self {
+        SignerData {
+            address: address.to_string(),
+            public_key: public_key.to_json(),
+            account_number,
+            sequence,
+            key_type,
+        }
+    }
+}
+
+impl MultiSignerTx {
+    /// Signers are reordered like the chain expects them : signers of the
+    /// messages in order of appearance, then the fee payer if it is not one
+    /// of them.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - a signer of a message is missing from `signers` or a signer is not
+    ///   required by the tx
+    /// - the signers of a message are not known by this crate
+    /// - the tx cannot be encoded to protobuf or JSON
+    pub fn new(
+        body: &Body,
+        fee: Fee,
+        chain_id: &str,
+        signers: Vec<SignerData>,
+    ) -> Result<Self, CosmosClient> {
+        let body = body.clone().into_proto();
+        let fee: cosmos_sdk_proto::cosmos::tx::v1beta1::Fee = fee.into();
+        let mut signers = signers;
+        let mut ordered = vec![];
+
+        for address in tx_signers(&body.messages, fee.payer.as_str())? {
+            let index = signers
+                .iter()
+                .position(|signer| signer.address == address)
+                .ok_or(CosmosClient::InvalidSigners(format!(
+                    "missing signer {address}"
+                )))?;
+            ordered.push(signers.remove(index));
+        }
+        if let Some(signer) = signers.first() {
+            return Err(CosmosClient::InvalidSigners(format!(
+                "{} does not sign the tx",
+                signer.address
+            )));
+        }
+
+        let auth_info = AuthInfo {
+            signer_infos: ordered
+                .iter()
+                .map(|signer| {
+                    Ok(SignerInfo {
+                        public_key: Some(
+                            signer
+                                .key_type
+                                .signer_public_key(PublicKey::from_json(&signer.public_key)?)
+                                .into(),
+                        ),
+                        mode_info: Some(ModeInfo {
+                            sum: Some(mode_info::Sum::Single(mode_info::Single {
+                                mode: SignMode::Direct.into(),
+                            })),
+                        }),
+                        sequence: signer.sequence,
+                    })
+                })
+                .collect::<Result<Vec<SignerInfo>, CosmosClient>>()?,
+            fee: Some(fee),
+            ..AuthInfo::default()
+        };
+
+        Ok(MultiSignerTx {
+            chain_id: chain_id.to_string(),
+            signatures: vec![None; ordered.len()],
+            signers: ordered,
+            tx: tx_to_json(&body, &auth_info, &[])?,
+            body_bytes: STANDARD.encode(body.encode_to_vec()),
+            auth_info_bytes: STANDARD.encode(auth_info.encode_to_vec()),
+        })
+    }
+
+    /// Add the signature of `signer`, without any network access.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - `signer` is not a signer of the tx
+    /// - `body_bytes` or `auth_info_bytes` are not valid
+    /// - there is a sign error
+    pub fn sign(&mut self, signer: &Signer) -> Result<(), CosmosClient> {
+        let index = self.position(signer.public_address.to_string().as_str())?;
+        if PublicKey::from_json(&self.signers[index].public_key)? != signer.public_key
+            || self.signers[index].key_type != signer.key_type
+        {
+            return Err(CosmosClient::InvalidSigners(format!(
+                "public key mismatch for {}",
+                signer.public_address
+            )));
+        }
+
+        let signature = signer.private_key.sign(&self.sign_bytes(index)?)?;
+        self.signatures[index] = Some(STANDARD.encode(signature.to_vec()));
+        Ok(())
+    }
+
+    /// Add a signature of `address` made on another machine.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - `address` is not a signer of the tx
+    /// - `signature` is not a valid signature of `address` over the tx
+    pub fn add_signature(&mut self, address: &str, signature: &str) -> Result<(), CosmosClient> {
+        let index = self.position(address)?;
+        let signer = &self.signers[index];
+
+        signer
+            .key_type
+            .verify(
+                &PublicKey::from_json(&signer.public_key)?,
+                &self.sign_bytes(index)?,
+                &STANDARD.decode(signature)?,
+            )
+            .map_err(|_| CosmosClient::InvalidSigners(format!("invalid signature of {address}")))?;
+
+        self.signatures[index] = Some(signature.to_string());
+        Ok(())
+    }
+
+    /// Add the signatures of `other`, the same tx signed elsewhere.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - `other` is not the same tx
+    /// - one of its signatures is invalid
+    pub fn merge(&mut self, other: &MultiSignerTx) -> Result<(), CosmosClient> {
+        if other.body_bytes != self.body_bytes
+            || other.auth_info_bytes != self.auth_info_bytes
+            || other.chain_id != self.chain_id
+        {
+            return Err(CosmosClient::InvalidSigners(
+                "cannot merge signatures of another tx".to_string(),
+            ));
+        }
+
+        for (signer, signature) in other.signers.iter().zip(&other.signatures) {
+            if let Some(signature) = signature {
+                self.add_signature(signer.address.as_str(), signature)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Addresses that did not sign yet.
+    #[must_use]
+    pub fn missing_signers(&self) -> Vec<&str> {
+        self.signers
+            .iter()
+            .zip(&self.signatures)
+            .filter(|(_, signature)| signature.is_none())
+            .map(|(signer, _)| signer.address.as_str())
+            .collect()
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - a signer did not sign yet
+    /// - `body_bytes`, `auth_info_bytes` or a signature is not valid base64
+    pub fn signed(&self) -> Result<SignedTx, CosmosClient> {
+        if let Some(address) = self.missing_signers().first() {
+            return Err(CosmosClient::InvalidSigners(format!(
+                "missing signature of {address}"
+            )));
+        }
+
+        SignedTx::new(
+            &TxRaw {
+                body_bytes: STANDARD.decode(&self.body_bytes)?,
+                auth_info_bytes: STANDARD.decode(&self.auth_info_bytes)?,
+                signatures: self
+                    .signatures
+                    .iter()
+                    .flatten()
+                    .map(|signature| STANDARD.decode(signature))
+                    .collect::<Result<Vec<Vec<u8>>, base64::DecodeError>>()?,
+            }
+            .encode_to_vec(),
+        )
+    }
+
+    fn position(&self, address: &str) -> Result<usize, CosmosClient> {
+        self.signers
+            .iter()
+            .position(|signer| signer.address == address)
+            .ok_or(CosmosClient::InvalidSigners(format!(
+                "{address} does not sign the tx"
+            )))
+    }
+
+    /// Direct mode sign doc of the signer at `index`.
+    pub(crate) fn sign_bytes(&self, index: usize) -> Result<Vec<u8>, CosmosClient> {
+        Ok(SignDoc {
+            body_bytes: STANDARD.decode(&self.body_bytes)?,
+            auth_info_bytes: STANDARD.decode(&self.auth_info_bytes)?,
+            chain_id: self.chain_id.clone(),
+            account_number: self.signers[index].account_number,
+        }
+        .encode_to_vec())
+    }
+}
+
+/// Addresses that must sign a tx with `msgs`, in the order the chain expects
+/// their signatures. `fee_payer` is added last if it is set and is not a
+/// signer of the messages.
+///
+/// # Errors
+///
+/// Will return `Err` if the signers of a message are not known by this crate
+pub fn tx_signers(msgs: &[Any], fee_payer: &str) -> Result<Vec<String>, CosmosClient> {
+    let mut signers: Vec<String> = vec![];
+
+    for msg in msgs {
+        for signer in msg_signers(msg)? {
+            if !signers.contains(&signer) {
+                signers.push(signer);
+            }
+        }
+    }
+    if !fee_payer.is_empty() && !signers.iter().any(|signer| signer == fee_payer) {
+        signers.push(fee_payer.to_string());
+    }
+
+    Ok(signers)
+}
+
+/// Addresses that must sign `msg`.
+///
+/// # Errors
+///
+/// Will return `Err` if `msg` fails to decode or its signers are not known by
+/// this crate
+pub fn msg_signers(msg: &Any) -> Result<Vec<String>, CosmosClient> {
+    Ok(match decode_msg(msg)? {
+        Msg::MsgSend(msg) => vec![msg.from_address],
+        Msg::MsgMultiSend(msg) => msg.inputs.into_iter().map(|input| input.address).collect(),
+        Msg::MsgCreateValidator(msg) => {
+            // SDK 0.47 `GetSigners` : the delegator, then the validator
+            // account if it is another one
+            let validator = validator_account(&msg.validator_address)?;
+            if msg.delegator_address == validator {
+                vec![validator]
+            } else {
+                vec![msg.delegator_address, validator]
+            }
+        }
+        Msg::MsgEditValidator(msg) => vec![validator_account(&msg.validator_address)?],
+        Msg::MsgWithdrawValidatorCommission(msg) => {
+            vec![validator_account(&msg.validator_address)?]
+        }
+        Msg::MsgUnjail(msg) => vec![validator_account(&msg.validator_addr)?],
+        Msg::MsgDelegate(msg) => vec![msg.delegator_address],
+        Msg::MsgUndelegate(msg) => vec![msg.delegator_address],
+        Msg::MsgBeginRedelegate(msg) => vec![msg.delegator_address],
+        Msg::MsgCancelUnbondingDelegation(msg) => vec![msg.delegator_address],
+        Msg::MsgSetWithdrawAddress(msg) => vec![msg.delegator_address],
+        Msg::MsgWithdrawDelegatorReward(msg) => vec![msg.delegator_address],
+        Msg::MsgFundCommunityPool(msg) => vec![msg.depositor],
+        Msg::MsgSubmitProposalV1beta1(msg) => vec![msg.proposer],
+        Msg::MsgVoteV1beta1(msg) => vec![msg.voter],
+        Msg::MsgVoteWeightedV1beta1(msg) => vec![msg.voter],
+        Msg::MsgDepositV1beta1(msg) => vec![msg.depositor],
+        Msg::MsgSubmitProposalV1(msg) => vec![msg.proposer],
+        Msg::MsgExecLegacyContentV1(msg) => vec![msg.authority],
+        Msg::MsgVoteV1(msg) => vec![msg.voter],
+        Msg::MsgVoteWeightedV1(msg) => vec![msg.voter],
+        Msg::MsgDepositV1(msg) => vec![msg.depositor],
+        Msg::MsgGrant(msg) => vec![msg.granter],
+        Msg::MsgExec(msg) => vec![msg.grantee],
+        Msg::MsgRevoke(msg) => vec![msg.granter],
+        Msg::MsgGrantAllowance(msg) => vec![msg.granter],
+        Msg::MsgRevokeAllowance(msg) => vec![msg.granter],
+        Msg::MsgTransfer(msg) => vec![msg.sender],
+        Msg::MsgStoreCode(msg) => vec![msg.sender],
+        Msg::MsgInstantiateContract(msg) => vec![msg.sender],
+        Msg::MsgInstantiateContract2(msg) => vec![msg.sender],
+        Msg::MsgExecuteContract(msg) => vec![msg.sender],
+        Msg::MsgMigrateContract(msg) => vec![msg.sender],
+        Msg::MsgUpdateAdmin(msg) => vec![msg.sender],
+        Msg::MsgClearAdmin(msg) => vec![msg.sender],
+        Msg::Unknown(msg) => {
+            return Err(CosmosClient::InvalidSigners(format!(
+                "unknown signers of {}",
+                msg.type_url
+            )))
+        }
+    })
+}
+
+/// Account address of a `valoper` address.
+fn validator_account(address: &str) -> Result<String, CosmosClient> {
+    let validator = AccountId::from_str(address)?;
+    let prefix = validator
+        .prefix()
+        .strip_suffix("valoper")
+        .unwrap_or(validator.prefix());
+
+    Ok(AccountId::new(prefix, &validator.to_bytes())?.to_string())
+}
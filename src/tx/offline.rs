@@ -0,0 +1,138 @@
+use crate::error::CosmosClient;
+use crate::signer::Signer;
+use crate::tx::json::tx_to_json;
+use crate::tx::{SignMode, SignPayload};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::{AuthInfo, TxBody, TxRaw};
+use cosmrs::tx::{Body, Fee};
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tendermint::crypto::default::Sha256;
+use tendermint::crypto::Sha256 as _;
+
+/// Unsigned tx exported to be signed on an offline machine.
+///
+/// `tx` is the SDK CLI JSON of the tx, that can be reviewed or signed with
+/// `tx sign --offline --chain-id <chain_id> --account-number
+/// <account_number> --sequence <sequence>`. `body_bytes` and
+/// `auth_info_bytes` are the base64 protobuf encoding of the same tx,
+/// which is what [`UnsignedTx::sign`] signs once it checked that `tx` is
+/// their JSON.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct UnsignedTx {
+    pub chain_id: String,
+    pub account_number: u64,
+    pub sequence: u64,
+    pub tx: Value,
+    pub body_bytes: String,
+    pub auth_info_bytes: String,
+}
+
+/// Signed tx ready to be broadcast with `Rpc::broadcast_signed`.
+///
+/// `tx` is the SDK CLI JSON of the tx, as read by `tx broadcast`, and
+/// `tx_bytes` its base64 protobuf `TxRaw` encoding.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SignedTx {
+    /// Uppercase hex sha256 of the raw tx, as indexed by the chain.
+    pub hash: String,
+    pub tx: Value,
+    pub tx_bytes: String,
+}
+
+impl UnsignedTx {
+    /// # Errors
+    ///
+    /// Will return `Err` if the tx cannot be encoded to protobuf or JSON
+    pub fn new(
+        body: &Body,
+        fee: Fee,
+        chain_id: &str,
+        account_number: u64,
+        sequence: u64,
+    ) -> Result<Self, CosmosClient> {
+        let body = body.clone().into_proto();
+        let auth_info = AuthInfo {
+            signer_infos: vec![],
+            fee: Some(fee.into()),
+            ..AuthInfo::default()
+        };
+
+        Ok(UnsignedTx {
+            chain_id: chain_id.to_string(),
+            account_number,
+            sequence,
+            tx: tx_to_json(&body, &auth_info, &[])?,
+            body_bytes: STANDARD.encode(body.encode_to_vec()),
+            auth_info_bytes: STANDARD.encode(auth_info.encode_to_vec()),
+        })
+    }
+
+    /// Sign the tx with `signer`, without any network access.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if :
+    /// - `body_bytes` or `auth_info_bytes` are not valid
+    /// - `tx` is not the JSON of `body_bytes` and `auth_info_bytes`
+    /// - there is a sign or encode error
+    pub fn sign(&self, signer: &Signer, sign_mode: SignMode) -> Result<SignedTx, CosmosClient> {
+        let (body, fee) = self.body_and_fee()?;
+
+        let payload = SignPayload::new(
+            signer.signer_public_key(),
+            sign_mode,
+            &body,
+            fee,
+            self.chain_id.as_str(),
+            self.account_number,
+            self.sequence,
+        )?;
+        let signature = signer.private_key.sign(&payload.sign_bytes)?;
+
+        SignedTx::new(&payload.into_tx(signature.to_vec()))
+    }
+
+    /// Body and fee of the signed bytes, rejected when the reviewed `tx`
+    /// does not show them.
+    pub(crate) fn body_and_fee(&self) -> Result<(Body, Fee), CosmosClient> {
+        let body = TxBody::decode(STANDARD.decode(&self.body_bytes)?.as_slice())?;
+        let auth_info = AuthInfo::decode(STANDARD.decode(&self.auth_info_bytes)?.as_slice())?;
+
+        if tx_to_json(&body, &auth_info, &[])? != self.tx {
+            return Err(CosmosClient::UnsignedTxMismatch);
+        }
+
+        Ok((
+            Body::try_from(body)?,
+            Fee::try_from(auth_info.fee.unwrap_or_default())?,
+        ))
+    }
+}
+
+impl SignedTx {
+    /// # Errors
+    ///
+    /// Will return `Err` if `tx_bytes` is not a protobuf `TxRaw` or if the
+    /// tx cannot be encoded to JSON
+    pub fn new(tx_bytes: &[u8]) -> Result<Self, CosmosClient> {
+        let raw = TxRaw::decode(tx_bytes)?;
+        let body = TxBody::decode(raw.body_bytes.as_slice())?;
+        let auth_info = AuthInfo::decode(raw.auth_info_bytes.as_slice())?;
+
+        Ok(SignedTx {
+            hash: hex::encode_upper(Sha256::digest(tx_bytes)),
+            tx: tx_to_json(&body, &auth_info, &raw.signatures)?,
+            tx_bytes: STANDARD.encode(tx_bytes),
+        })
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if `tx_bytes` is not valid base64
+    pub fn to_bytes(&self) -> Result<Vec<u8>, CosmosClient> {
+        Ok(STANDARD.decode(&self.tx_bytes)?)
+    }
+}
@@ -0,0 +1,220 @@
+use crate::error::CosmosClient;
+use crate::tx::decode::{decode_msg, Msg};
+use crate::tx::{simulation_tx, SignMode};
+use cosmos_sdk_proto::cosmos::base::v1beta1::Coin;
+use cosmrs::tx::{Body, Fee};
+
+/// Size of a secp256k1 signature in a raw tx.
+const SIGNATURE_SIZE: usize = 64;
+
+/// On-chain limits a tx is checked against before signing, see
+/// `Rpc::limits`. A zero limit or an empty `min_deposit` is not checked.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Limits {
+    /// `auth` param `max_memo_characters`.
+    pub max_memo_characters: u64,
+    /// `auth` param `tx_sig_limit`.
+    pub tx_sig_limit: u64,
+    /// Consensus param `block.max_bytes`, a tx cannot be larger than a block.
+    pub max_tx_bytes: u64,
+    /// `gov` deposit param `min_deposit`.
+    pub min_deposit: Vec<Coin>,
+}
+
+/// Reason a tx would be rejected by the chain.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Violation {
+    MemoTooLong {
+        length: u64,
+        max: u64,
+    },
+    TxTooLarge {
+        size: u64,
+        max: u64,
+    },
+    TooManySignatures {
+        count: u64,
+        max: u64,
+    },
+    /// Initial deposit of a proposal below the min deposit, the proposal
+    /// would not enter its voting period.
+    DepositTooLow {
+        deposit: Vec<Coin>,
+        min_deposit: Vec<Coin>,
+    },
+    InvalidDenom(String),
+    /// Amount that is not a positive integer.
+    InvalidAmount {
+        denom: String,
+        amount: String,
+    },
+    /// Coins that are not sorted by denom or have a duplicated denom.
+    UnsortedCoins(Vec<Coin>),
+}
+
+/// Check `body`, paying `fee` and signed with `signatures` keys, against
+/// `limits` and the coins rules of the Cosmos SDK.
+///
+/// # Errors
+///
+/// Will return `Err` if :
+/// - the tx breaks one of the limits, with all the violations found
+/// - a known message fails to decode
+/// - there is an encode error
+pub fn validate(
+    body: &Body,
+    fee: &Fee,
+    signatures: u64,
+    sign_mode: SignMode,
+    limits: &Limits,
+) -> Result<(), CosmosClient> {
+    let violations = violations(body, fee, signatures, sign_mode, limits)?;
+
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(CosmosClient::InvalidTx(violations))
+    }
+}
+
+/// Every violation of `limits` by the tx, see [`validate`].
+///
+/// # Errors
+///
+/// Will return `Err` if a known message fails to decode or if there is an
+/// encode error
+pub fn violations(
+    body: &Body,
+    fee: &Fee,
+    signatures: u64,
+    sign_mode: SignMode,
+    limits: &Limits,
+) -> Result<Vec<Violation>, CosmosClient> {
+    let mut violations = vec![];
+
+    let length = body.memo.len() as u64;
+    if limits.max_memo_characters > 0 && length > limits.max_memo_characters {
+        violations.push(Violation::MemoTooLong {
+            length,
+            max: limits.max_memo_characters,
+        });
+    }
+
+    if limits.tx_sig_limit > 0 && signatures > limits.tx_sig_limit {
+        violations.push(Violation::TooManySignatures {
+            count: signatures,
+            max: limits.tx_sig_limit,
+        });
+    }
+
+    let size = (simulation_tx(body, fee.clone(), None, 0, sign_mode)?.len()
+        + SIGNATURE_SIZE * usize::try_from(signatures)?) as u64;
+    if limits.max_tx_bytes > 0 && size > limits.max_tx_bytes {
+        violations.push(Violation::TxTooLarge {
+            size,
+            max: limits.max_tx_bytes,
+        });
+    }
+
+    let fee: cosmos_sdk_proto::cosmos::tx::v1beta1::Fee = fee.clone().into();
+    check_coins(&fee.amount, &mut violations);
+
+    for msg in &body.messages {
+        match decode_msg(msg)? {
+            Msg::MsgSend(msg) => check_coins(&msg.amount, &mut violations),
+            Msg::MsgMultiSend(msg) => {
+                for coins in msg
+                    .inputs
+                    .iter()
+                    .map(|input| &input.coins)
+                    .chain(msg.outputs.iter().map(|output| &output.coins))
+                {
+                    check_coins(coins, &mut violations);
+                }
+            }
+            Msg::MsgDelegate(msg) => check_coin(msg.amount.as_ref(), &mut violations),
+            Msg::MsgUndelegate(msg) => check_coin(msg.amount.as_ref(), &mut violations),
+            Msg::MsgBeginRedelegate(msg) => check_coin(msg.amount.as_ref(), &mut violations),
+            Msg::MsgCancelUnbondingDelegation(msg) => {
+                check_coin(msg.amount.as_ref(), &mut violations);
+            }
+            Msg::MsgFundCommunityPool(msg) => check_coins(&msg.amount, &mut violations),
+            Msg::MsgSubmitProposalV1beta1(msg) => {
+                check_deposit(&msg.initial_deposit, &limits.min_deposit, &mut violations);
+            }
+            Msg::MsgSubmitProposalV1(msg) => {
+                check_deposit(&msg.initial_deposit, &limits.min_deposit, &mut violations);
+            }
+            Msg::MsgDepositV1beta1(msg) => check_coins(&msg.amount, &mut violations),
+            Msg::MsgDepositV1(msg) => check_coins(&msg.amount, &mut violations),
+            Msg::MsgTransfer(msg) => check_coin(msg.token.as_ref(), &mut violations),
+            Msg::MsgInstantiateContract(msg) => check_coins(&msg.funds, &mut violations),
+            Msg::MsgInstantiateContract2(msg) => check_coins(&msg.funds, &mut violations),
+            Msg::MsgExecuteContract(msg) => check_coins(&msg.funds, &mut violations),
+            _ => {}
+        }
+    }
+
+    Ok(violations)
+}
+
+fn check_deposit(deposit: &[Coin], min_deposit: &[Coin], violations: &mut Vec<Violation>) {
+    check_coins(deposit, violations);
+
+    let amount = |coins: &[Coin], denom: &str| {
+        coins
+            .iter()
+            .find(|coin| coin.denom == denom)
+            .and_then(|coin| coin.amount.parse::<u128>().ok())
+            .unwrap_or_default()
+    };
+    if min_deposit
+        .iter()
+        .any(|min| amount(deposit, &min.denom) < amount(min_deposit, &min.denom))
+    {
+        violations.push(Violation::DepositTooLow {
+            deposit: deposit.to_vec(),
+            min_deposit: min_deposit.to_vec(),
+        });
+    }
+}
+
+/// SDK `Coins` rules : valid coins sorted by denom without duplicates.
+fn check_coins(coins: &[Coin], violations: &mut Vec<Violation>) {
+    for coin in coins {
+        check_coin(Some(coin), violations);
+    }
+
+    if coins
+        .windows(2)
+        .any(|pair| pair[0].denom.as_str() >= pair[1].denom.as_str())
+    {
+        violations.push(Violation::UnsortedCoins(coins.to_vec()));
+    }
+}
+
+fn check_coin(coin: Option<&Coin>, violations: &mut Vec<Violation>) {
+    let Some(coin) = coin else {
+        return;
+    };
+
+    if !is_valid_denom(&coin.denom) {
+        violations.push(Violation::InvalidDenom(coin.denom.clone()));
+    }
+    if !coin.amount.parse::<u128>().is_ok_and(|amount| amount > 0) {
+        violations.push(Violation::InvalidAmount {
+            denom: coin.denom.clone(),
+            amount: coin.amount.clone(),
+        });
+    }
+}
+
+/// SDK denom regex `[a-zA-Z][a-zA-Z0-9/:._-]{2,127}`.
+#[must_use]
+pub fn is_valid_denom(denom: &str) -> bool {
+    let mut chars = denom.chars();
+
+    (3..=128).contains(&denom.len())
+        && chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || "/:._-".contains(c))
+}